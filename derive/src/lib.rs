@@ -0,0 +1,197 @@
+//! Derive macros for [`egui-editable-combobox`](https://docs.rs/egui-editable-combobox).
+//!
+//! This crate is re-exported by `egui-editable-combobox` with the `derive` feature,
+//! and should not be depended on directly.
+
+#![warn(clippy::pedantic, missing_docs)]
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, Ident, LitStr, Variant, parse_macro_input};
+
+/// Derives `Value` and `ValueOption` for a fieldless enum or a newtype struct.
+///
+/// See the documentation of `ComboValue` in `egui-editable-combobox` for details.
+#[proc_macro_derive(ComboValue, attributes(combo))]
+pub fn derive_combo_value(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    if !input.generics.params.is_empty() {
+        return syn::Error::new_spanned(
+            &input.generics,
+            "ComboValue cannot be derived for generic types",
+        )
+        .into_compile_error()
+        .into();
+    }
+    let output = match &input.data {
+        Data::Enum(data) => data
+            .variants
+            .iter()
+            .map(parse_variant)
+            .collect::<syn::Result<Vec<_>>>()
+            .map(|variants| derive_enum(&input.ident, &variants)),
+        Data::Struct(data) if is_newtype(&data.fields) => Ok(derive_newtype(&input.ident)),
+        _ => Err(syn::Error::new_spanned(
+            &input.ident,
+            "ComboValue can only be derived for fieldless enums and newtype structs",
+        )),
+    };
+    output.unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+/// Whether `fields` are those of a tuple struct with a single field.
+fn is_newtype(fields: &Fields) -> bool {
+    matches!(fields, Fields::Unnamed(fields) if fields.unnamed.len() == 1)
+}
+
+/// A variant of a derived enum and the label it is displayed and typed as.
+struct LabeledVariant {
+    ident: Ident,
+    label: LitStr,
+}
+
+/// Reads the label of `variant` from `#[combo(label = "...")]`,
+/// defaulting to the name of the variant.
+fn parse_variant(variant: &Variant) -> syn::Result<LabeledVariant> {
+    if !matches!(variant.fields, Fields::Unit) {
+        return Err(syn::Error::new_spanned(
+            &variant.fields,
+            "ComboValue can only be derived for enums without fields",
+        ));
+    }
+
+    let mut label = LitStr::new(&variant.ident.to_string(), variant.ident.span());
+    for attr in variant.attrs.iter().filter(|attr| attr.path().is_ident("combo")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("label") {
+                label = meta.value()?.parse()?;
+                Ok(())
+            } else {
+                Err(meta.error("unknown combo attribute, expected `label`"))
+            }
+        })?;
+    }
+    Ok(LabeledVariant { ident: variant.ident.clone(), label })
+}
+
+fn derive_enum(name: &Ident, variants: &[LabeledVariant]) -> TokenStream {
+    let idents: Vec<_> = variants.iter().map(|variant| &variant.ident).collect();
+    let labels: Vec<_> = variants.iter().map(|variant| &variant.label).collect();
+    let krate = quote!(::egui_editable_combobox);
+    let private = quote!(#krate::__private);
+    // `this` is the option as a `&#name`.
+    let option_impl = |option: TokenStream, this: TokenStream| {
+        quote! {
+            impl #krate::ValueOption<#name> for #option {
+                fn filter_by_text(
+                    &self,
+                    text: &str,
+                    _: #krate::FilterState,
+                ) -> #krate::FilterResult {
+                    #krate::FilterResult::from_case_insensitive_substring(self.label(), text)
+                }
+
+                fn display(&self, _text: &str) -> impl #private::egui::IntoAtoms<'_> {
+                    self.label()
+                }
+
+                fn display_highlighted(
+                    &self,
+                    text: &str,
+                    highlight: &#krate::MatchHighlight,
+                ) -> ::core::option::Option<#private::egui::text::LayoutJob> {
+                    ::core::option::Option::Some(highlight.layout_substring(self.label(), text))
+                }
+
+                fn into_value(self, _text: &str) -> #name {
+                    match self { #(#name::#idents => #name::#idents,)* }
+                }
+
+                fn equals_value(&self, value: &#name, _text: &str) -> bool {
+                    ::core::mem::discriminant(#this) == ::core::mem::discriminant(value)
+                }
+
+                fn editable_text(&self, _text: &str) -> ::core::option::Option<#private::String> {
+                    ::core::option::Option::Some(#private::ToString::to_string(self.label()))
+                }
+            }
+        }
+    };
+    let owned_impl = option_impl(quote!(#name), quote!(self));
+    let borrowed_impl = option_impl(quote!(&#name), quote!(*self));
+    let count = variants.len();
+
+    quote! {
+        impl #name {
+            /// All variants in declaration order, to be passed as the options of a combo box.
+            pub const VARIANTS: [Self; #count] = [#(#name::#idents),*];
+
+            /// The label this variant is displayed and typed as.
+            pub fn label(&self) -> &'static str {
+                match self { #(#name::#idents => #labels,)* }
+            }
+        }
+
+        impl #krate::Value for #name {
+            fn to_editable(&self) -> #private::String {
+                #private::ToString::to_string(self.label())
+            }
+
+            fn try_from_editable(text: &str) -> ::core::option::Option<Self> {
+                match text {
+                    #(#labels => ::core::option::Option::Some(#name::#idents),)*
+                    _ => ::core::option::Option::None,
+                }
+            }
+        }
+
+        #owned_impl
+        #borrowed_impl
+    }
+}
+
+fn derive_newtype(name: &Ident) -> TokenStream {
+    let krate = quote!(::egui_editable_combobox);
+    let private = quote!(#krate::__private);
+
+    quote! {
+        impl #krate::Value for #name {
+            fn to_editable(&self) -> #private::String { #private::ToString::to_string(&self.0) }
+
+            fn try_from_editable(text: &str) -> ::core::option::Option<Self> {
+                ::core::result::Result::ok(::core::str::FromStr::from_str(text)).map(#name)
+            }
+        }
+
+        impl #krate::ValueOption<#name> for #name {
+            fn filter_by_text(&self, text: &str, _: #krate::FilterState) -> #krate::FilterResult {
+                #krate::FilterResult::from_case_insensitive_substring(
+                    #private::ToString::to_string(&self.0),
+                    text,
+                )
+            }
+
+            fn display(&self, _text: &str) -> impl #private::egui::IntoAtoms<'_> {
+                #private::ToString::to_string(&self.0)
+            }
+
+            fn display_highlighted(
+                &self,
+                text: &str,
+                highlight: &#krate::MatchHighlight,
+            ) -> ::core::option::Option<#private::egui::text::LayoutJob> {
+                ::core::option::Option::Some(
+                    highlight.layout_substring(&#private::ToString::to_string(&self.0), text),
+                )
+            }
+
+            fn into_value(self, _text: &str) -> #name { self }
+
+            fn equals_value(&self, value: &#name, _text: &str) -> bool { self.0 == value.0 }
+
+            fn editable_text(&self, _text: &str) -> ::core::option::Option<#private::String> {
+                ::core::option::Option::Some(#private::ToString::to_string(&self.0))
+            }
+        }
+    }
+}
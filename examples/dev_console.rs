@@ -0,0 +1,114 @@
+//! A developer console for a game, driven entirely from the keyboard.
+//!
+//! Type a command name, fuzzy matched so that "tp" finds "teleport",
+//! and press Enter to move on to its argument.
+//! Pressing Enter on the argument executes the command,
+//! and focus returns to the command name.
+//! Up recalls the previously executed command names.
+//!
+//! The console is also driven by the `dev_console` integration test.
+
+use egui_editable_combobox::{
+    EditableComboBox, FuzzyOption, HistoryMode, InteractionConfig, ShowOutput,
+};
+
+/// A console command and the suggested values of its argument.
+pub struct Command {
+    pub name:      &'static str,
+    pub arguments: &'static [&'static str],
+}
+
+pub const COMMANDS: &[Command] = &[
+    Command { name: "give", arguments: &["gold", "potion", "shield", "sword"] },
+    Command { name: "god", arguments: &["on", "off"] },
+    Command { name: "spawn", arguments: &["dragon", "goblin", "merchant"] },
+    Command { name: "teleport", arguments: &["castle", "forest", "harbor", "village"] },
+    Command { name: "time", arguments: &["dawn", "noon", "dusk", "midnight"] },
+];
+
+fn main() -> eframe::Result {
+    eframe::run_native(
+        "Developer console",
+        eframe::NativeOptions::default(),
+        Box::new(|_cc| Ok(Box::new(App { console: Console::default() }))),
+    )
+}
+
+struct App {
+    console: Console,
+}
+
+impl eframe::App for App {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        egui::CentralPanel::default().show(ctx, |ui| self.console.ui(ui));
+    }
+}
+
+pub struct Console {
+    pub command:   String,
+    pub argument:  String,
+    /// The executed command lines, oldest first.
+    pub log:       Vec<String>,
+    focus_command: bool,
+}
+
+impl Default for Console {
+    fn default() -> Self {
+        Self {
+            command:       String::new(),
+            argument:      String::new(),
+            log:           Vec::new(),
+            focus_command: true,
+        }
+    }
+}
+
+impl Console {
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        egui::ScrollArea::vertical().max_height(200.0).stick_to_bottom(true).show(ui, |ui| {
+            for line in &self.log {
+                ui.monospace(line);
+            }
+        });
+
+        ui.horizontal(|ui| {
+            let command = EditableComboBox::new("console command")
+                .interaction(InteractionConfig {
+                    history: HistoryMode::Readline,
+                    ..Default::default()
+                })
+                .show_with_output(
+                    ui,
+                    &mut self.command,
+                    COMMANDS.iter().map(|command| FuzzyOption(command.name)),
+                );
+            if self.focus_command {
+                command.response.request_focus();
+                self.focus_command = false;
+            }
+
+            let arguments = COMMANDS
+                .iter()
+                .find(|command| command.name == self.command)
+                .map_or(&[][..], |command| command.arguments);
+            let argument =
+                EditableComboBox::new("console argument").allow_free_text(true).show_with_output(
+                    ui,
+                    &mut self.argument,
+                    arguments.iter().copied().map(FuzzyOption),
+                );
+
+            if picked(&command) {
+                // Continue with the argument of the command.
+                argument.response.request_focus();
+            } else if picked(&argument) && !self.command.is_empty() {
+                self.log.push(format!("> {} {}", self.command, self.argument));
+                self.argument.clear();
+                command.response.request_focus();
+            }
+        });
+    }
+}
+
+/// Whether a value was committed in this frame, even if it is the current value.
+fn picked(output: &ShowOutput) -> bool { output.changed() || output.reselected.is_some() }
@@ -0,0 +1,50 @@
+use egui_editable_combobox::EditableComboBox;
+
+const FRUITS: [&str; 5] = ["Apple", "Banana", "Cherry", "Durian", "Elderberry"];
+
+fn main() -> eframe::Result {
+    eframe::run_native(
+        "Modal example",
+        eframe::NativeOptions::default(),
+        Box::new(|_cc| Ok(Box::new(App { fruit: String::from("Apple"), editing: None }))),
+    )
+}
+
+struct App {
+    fruit:   String,
+    /// The value being edited in the modal dialog, if it is open.
+    editing: Option<String>,
+}
+
+impl eframe::App for App {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.label(format!("Favourite fruit: {}", self.fruit));
+            if ui.button("Change…").clicked() {
+                self.editing = Some(self.fruit.clone());
+            }
+        });
+
+        let Some(editing) = &mut self.editing else { return };
+        let mut confirmed = false;
+        // The popup is displayed above the dimmed background of the modal.
+        // The first Escape closes the popup and keeps focus in the editor,
+        // and the next Escape closes the modal.
+        let modal = egui::Modal::new(egui::Id::new("fruit modal")).show(ctx, |ui| {
+            ui.heading("Choose a fruit");
+            EditableComboBox::new("fruit").show(ui, editing, FRUITS);
+            ui.horizontal(|ui| {
+                confirmed = ui.button("OK").clicked();
+                if ui.button("Cancel").clicked() {
+                    ui.close();
+                }
+            });
+        });
+        if confirmed {
+            self.fruit = editing.clone();
+            self.editing = None;
+        } else if modal.should_close() {
+            self.editing = None;
+        }
+    }
+}
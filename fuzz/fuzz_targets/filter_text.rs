@@ -0,0 +1,99 @@
+//! Feeds arbitrary UTF-8, such as astral-plane characters, bidi marks and combining sequences,
+//! into the matching helpers and the text editing path of the combo box.
+//!
+//! Run with `cargo fuzz run filter_text` from the repository root.
+
+#![no_main]
+
+use egui_editable_combobox::{
+    ComboModel, CompletionScope, CustomOption, CustomValue, EditableComboBox, FilterResult,
+};
+use libfuzzer_sys::arbitrary::{self, Arbitrary};
+use libfuzzer_sys::fuzz_target;
+
+#[derive(Arbitrary, Debug)]
+struct Input {
+    options:   Vec<String>,
+    /// Text typed in successive frames.
+    typed:     Vec<String>,
+    /// Completes the segment between occurrences of this delimiter if set.
+    delimiter: Option<char>,
+}
+
+fuzz_target!(|input: Input| {
+    let options = &input.options[..input.options.len().min(16)];
+    let typed = &input.typed[..input.typed.len().min(8)];
+
+    for option in options {
+        for text in typed {
+            let _ = FilterResult::from_case_insensitive_substring(option, text);
+        }
+    }
+
+    let mut model = ComboModel::<CustomValue<String>, _>::new(
+        options.iter().cloned().map(CustomOption::Value).chain([CustomOption::Custom]),
+    );
+    for text in typed {
+        model.input_text(text);
+    }
+
+    show_typed(options, typed, input.delimiter);
+});
+
+/// Types each text into a focused combo box in its own frame,
+/// moving the caret into the text to exercise the char index math of completion scopes.
+fn show_typed(options: &[String], typed: &[String], delimiter: Option<char>) {
+    let ctx = egui::Context::default();
+    let mut value = String::new();
+    let mut run = |events: Vec<egui::Event>| {
+        let input = egui::RawInput { events, ..Default::default() };
+        let _ = ctx.run(input, |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| {
+                let scope = delimiter.map_or(CompletionScope::Whole, |delimiter| {
+                    CompletionScope::Segment { delimiter }
+                });
+                EditableComboBox::new("fuzz").completion_scope(scope).show(
+                    ui,
+                    &mut value,
+                    options.iter().map(String::as_str),
+                );
+            });
+        });
+    };
+
+    let pos = egui::pos2(20.0, 15.0);
+    run(vec![]);
+    run(vec![
+        egui::Event::PointerMoved(pos),
+        egui::Event::PointerButton {
+            pos,
+            button: egui::PointerButton::Primary,
+            pressed: true,
+            modifiers: egui::Modifiers::NONE,
+        },
+        egui::Event::PointerButton {
+            pos,
+            button: egui::PointerButton::Primary,
+            pressed: false,
+            modifiers: egui::Modifiers::NONE,
+        },
+    ]);
+    for text in typed {
+        run(vec![
+            egui::Event::Text(text.clone()),
+            key(egui::Key::ArrowLeft),
+            key(egui::Key::ArrowDown),
+        ]);
+    }
+    run(vec![key(egui::Key::Enter)]);
+}
+
+fn key(key: egui::Key) -> egui::Event {
+    egui::Event::Key {
+        key,
+        physical_key: None,
+        pressed: true,
+        repeat: false,
+        modifiers: egui::Modifiers::NONE,
+    }
+}
@@ -0,0 +1,95 @@
+use egui::TextBuffer;
+
+use crate::{EditableComboBox, Ids, Value, ValueOption};
+
+/// Progress of creating a new value in
+/// [`show_with_add_new`](EditableComboBox::show_with_add_new).
+pub enum AddNew<V> {
+    /// The user is still creating the value, e.g. filling in a creation dialog.
+    Pending,
+    /// The user has created a new value, which becomes the selected value.
+    Created(V),
+    /// The user has cancelled creation. The selected value is unchanged.
+    Cancelled,
+}
+
+impl<B> EditableComboBox<'_, B>
+where
+    B: TextBuffer + Default + Clone + Send + Sync + 'static,
+{
+    /// Display the combo box followed by a "+" button for creating a new value.
+    ///
+    /// After the button is clicked, `create` is called in every frame
+    /// until it returns [`AddNew::Created`] or [`AddNew::Cancelled`].
+    /// The app would typically show a creation dialog (e.g. an [`egui::Modal`]) in `create`.
+    /// Upon [`AddNew::Created`], the new value is selected
+    /// and the returned response is marked as changed.
+    ///
+    /// # Example
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// use egui_editable_combobox::{AddNew, EditableComboBox};
+    ///
+    /// let mut profiles = vec![String::from("Default")];
+    /// let mut selected = String::from("Default");
+    /// let mut new_name = String::new();
+    ///
+    /// let options = profiles.clone();
+    /// EditableComboBox::new("profile").show_with_add_new(ui, &mut selected, options, |ui| {
+    ///     let modal = egui::Modal::new(egui::Id::new("new profile")).show(ui.ctx(), |ui| {
+    ///         ui.text_edit_singleline(&mut new_name);
+    ///         ui.button("Create").clicked()
+    ///     });
+    ///     if modal.inner {
+    ///         profiles.push(new_name.clone());
+    ///         AddNew::Created(std::mem::take(&mut new_name))
+    ///     } else if modal.should_close() {
+    ///         AddNew::Cancelled
+    ///     } else {
+    ///         AddNew::Pending
+    ///     }
+    /// });
+    /// # });
+    /// ```
+    pub fn show_with_add_new<V, Opt>(
+        self,
+        ui: &mut egui::Ui,
+        value: &mut V,
+        options: impl IntoIterator<Item = Opt>,
+        create: impl FnOnce(&mut egui::Ui) -> AddNew<V>,
+    ) -> egui::Response
+    where
+        V: Value,
+        Opt: ValueOption<V>,
+    {
+        let creating_id = Ids::Creating.id(self.id_salt);
+
+        let (mut resp, clicked) = ui
+            .horizontal(|ui| {
+                let resp = self.show(ui, value, options);
+                let clicked = ui.button("+").on_hover_text("Add new").clicked();
+                (resp, clicked)
+            })
+            .inner;
+
+        if clicked {
+            ui.ctx().memory_mut(|mem| mem.data.insert_temp(creating_id, true));
+        }
+
+        if ui.ctx().memory(|mem| mem.data.get_temp::<bool>(creating_id).unwrap_or(false)) {
+            match create(ui) {
+                AddNew::Pending => {}
+                AddNew::Created(new_value) => {
+                    *value = new_value;
+                    resp.mark_changed();
+                    ui.ctx().memory_mut(|mem| mem.data.remove::<bool>(creating_id));
+                }
+                AddNew::Cancelled => {
+                    ui.ctx().memory_mut(|mem| mem.data.remove::<bool>(creating_id));
+                }
+            }
+        }
+
+        resp
+    }
+}
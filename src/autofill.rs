@@ -0,0 +1,227 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+use egui::text::LayoutJob;
+use egui::{Atom, IntoAtoms};
+
+use crate::value::IntoAtomsEither;
+use crate::{FilterResult, FilterState, MatchHighlight, Value, ValueOption};
+
+/// The semantic type of the text entered in a combo box,
+/// like the `autocomplete` attribute of HTML inputs.
+///
+/// egui has no channel for the suggestions of the platform,
+/// so the app or its integration supplies them with [`set_candidates`](Self::set_candidates),
+/// e.g. from the contacts of the user or from the browser,
+/// and every combo box with the same [`autofill`](crate::EditableComboBox::autofill) hint
+/// lists them after its own options.
+///
+/// # Example
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// use egui_editable_combobox::{AutofillHint, EditableComboBox};
+///
+/// // Once the app has read the addresses known to the platform:
+/// AutofillHint::Email.set_candidates(ui.ctx(), ["ada@example.com", "grace@example.com"]);
+///
+/// let mut recipient = String::new();
+/// EditableComboBox::new("recipient").autofill(AutofillHint::Email).show(
+///     ui,
+///     &mut recipient,
+///     ["team@example.com"],
+/// );
+/// # });
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum AutofillHint {
+    /// An email address.
+    Email,
+    /// The full name of a person.
+    Name,
+    /// A user name or account name.
+    Username,
+    /// A telephone number.
+    Telephone,
+    /// An absolute URL.
+    Url,
+    /// A street address.
+    StreetAddress,
+    /// A postal code.
+    PostalCode,
+    /// The name of a country.
+    Country,
+    /// The name of a company or organization.
+    Organization,
+}
+
+impl AutofillHint {
+    /// The token of the hint in the HTML `autocomplete` attribute,
+    /// e.g. to look up the suggestions of a browser.
+    #[must_use]
+    pub fn html_token(self) -> &'static str {
+        match self {
+            AutofillHint::Email => "email",
+            AutofillHint::Name => "name",
+            AutofillHint::Username => "username",
+            AutofillHint::Telephone => "tel",
+            AutofillHint::Url => "url",
+            AutofillHint::StreetAddress => "street-address",
+            AutofillHint::PostalCode => "postal-code",
+            AutofillHint::Country => "country-name",
+            AutofillHint::Organization => "organization",
+        }
+    }
+
+    /// Replaces the suggestions for this hint, most relevant first.
+    ///
+    /// The suggestions are kept in egui memory without being persisted,
+    /// since they belong to the platform rather than to the app.
+    /// Empty suggestions are ignored.
+    pub fn set_candidates(
+        self,
+        ctx: &egui::Context,
+        candidates: impl IntoIterator<Item = impl Into<String>>,
+    ) {
+        let candidates: Vec<String> = candidates
+            .into_iter()
+            .map(Into::into)
+            .filter(|candidate: &String| !candidate.is_empty())
+            .collect();
+        ctx.memory_mut(|mem| mem.data.insert_temp(self.id(), candidates));
+    }
+
+    /// The suggestions for this hint, most relevant first.
+    #[must_use]
+    pub fn candidates(self, ctx: &egui::Context) -> Vec<String> {
+        ctx.memory(|mem| mem.data.get_temp::<Vec<String>>(self.id())).unwrap_or_default()
+    }
+
+    fn id(self) -> egui::Id { egui::Id::new("egui-editable-combobox autofill").with(self) }
+}
+
+/// An option of the combo box or an [autofill](AutofillHint) suggestion merged after the options.
+pub(crate) enum Autofilled<V, Opt> {
+    Option(Opt),
+    /// The text of the suggestion and the value it is parsed into.
+    Candidate(String, V),
+}
+
+/// Appends the `candidates` that parse into values to `options`,
+/// skipping those equal to the editable text of an option.
+pub(crate) fn merge_autofill<V: Value, Opt: ValueOption<V>>(
+    options: impl IntoIterator<Item = Opt>,
+    mut candidates: Vec<String>,
+) -> impl Iterator<Item = Autofilled<V, Opt>> {
+    candidates.reverse();
+    MergeAutofill { options: options.into_iter(), candidates, _value: PhantomData }
+}
+
+struct MergeAutofill<V, I> {
+    options:    I,
+    /// The candidates not yet matched by an option, in reverse order.
+    candidates: Vec<String>,
+    _value:     PhantomData<fn() -> V>,
+}
+
+impl<V, Opt, I> Iterator for MergeAutofill<V, I>
+where
+    V: Value,
+    Opt: ValueOption<V>,
+    I: Iterator<Item = Opt>,
+{
+    type Item = Autofilled<V, Opt>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(option) = self.options.next() {
+            if let Some(text) = option.editable_text("") {
+                self.candidates.retain(|candidate| *candidate != text);
+            }
+            return Some(Autofilled::Option(option));
+        }
+        // Only reached once all options are consumed, so no duplicates remain.
+        while let Some(candidate) = self.candidates.pop() {
+            if let Some(value) = V::try_from_editable(&candidate) {
+                return Some(Autofilled::Candidate(candidate, value));
+            }
+        }
+        None
+    }
+}
+
+impl<V: Value, Opt: ValueOption<V>> ValueOption<V> for Autofilled<V, Opt> {
+    fn filter_by_text(&self, text: &str, state: FilterState) -> FilterResult {
+        match self {
+            Autofilled::Option(option) => option.filter_by_text(text, state),
+            Autofilled::Candidate(candidate, _) => {
+                FilterResult::from_case_insensitive_substring(candidate, text)
+            }
+        }
+    }
+
+    fn display(&self, text: &str) -> impl IntoAtoms<'_> {
+        match self {
+            Autofilled::Option(option) => IntoAtomsEither::Left(option.display(text)),
+            Autofilled::Candidate(candidate, _) => IntoAtomsEither::Right(candidate.as_str()),
+        }
+    }
+
+    fn display_highlighted(&self, text: &str, highlight: &MatchHighlight) -> Option<LayoutJob> {
+        match self {
+            Autofilled::Option(option) => option.display_highlighted(text, highlight),
+            Autofilled::Candidate(candidate, _) => {
+                Some(highlight.layout_substring(candidate, text))
+            }
+        }
+    }
+
+    fn into_value(self, text: &str) -> V {
+        match self {
+            Autofilled::Option(option) => option.into_value(text),
+            Autofilled::Candidate(_, value) => value,
+        }
+    }
+
+    fn equals_value(&self, value: &V, text: &str) -> bool {
+        match self {
+            Autofilled::Option(option) => option.equals_value(value, text),
+            Autofilled::Candidate(candidate, _) => value.to_editable() == *candidate,
+        }
+    }
+
+    fn editable_text(&self, text: &str) -> Option<String> {
+        match self {
+            Autofilled::Option(option) => option.editable_text(text),
+            Autofilled::Candidate(candidate, _) => Some(candidate.clone()),
+        }
+    }
+
+    fn icon(&self) -> Option<Atom<'_>> {
+        match self {
+            Autofilled::Option(option) => option.icon(),
+            Autofilled::Candidate(..) => None,
+        }
+    }
+
+    fn description(&self) -> Option<&str> {
+        match self {
+            Autofilled::Option(option) => option.description(),
+            Autofilled::Candidate(..) => None,
+        }
+    }
+
+    fn is_separator(&self) -> bool {
+        match self {
+            Autofilled::Option(option) => option.is_separator(),
+            Autofilled::Candidate(..) => false,
+        }
+    }
+
+    fn is_enabled(&self, text: &str) -> bool {
+        match self {
+            Autofilled::Option(option) => option.is_enabled(text),
+            Autofilled::Candidate(..) => true,
+        }
+    }
+}
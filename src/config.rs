@@ -0,0 +1,400 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::mem;
+use core::ops::Range;
+
+use egui::{Key, KeyboardShortcut, Modifiers};
+
+use crate::PopupStyle;
+
+/// Behavior settings of an [`EditableComboBox`](crate::EditableComboBox).
+///
+/// A config can be constructed once, e.g. stored in the app settings,
+/// and passed to many combo boxes through
+/// [`EditableComboBox::interaction`](crate::EditableComboBox::interaction)
+/// for consistent behavior app-wide.
+#[derive(Debug, Clone, PartialEq)]
+#[expect(clippy::struct_excessive_bools)] // each flag is an independent setting
+pub struct InteractionConfig {
+    /// Whether hovering an option with the pointer moves the keyboard cursor to it.
+    pub hover_moves_cursor: bool,
+    /// How long, in seconds, the pointer must rest on an option
+    /// before the cursor moves to it.
+    ///
+    /// This prevents the cursor from jumping around
+    /// when a pen or tablet stylus transiently hovers across rows.
+    pub hover_delay:        f32,
+    /// How far, in points, the pointer must travel after the cursor last moved
+    /// before hovering can move the cursor again.
+    ///
+    /// This keeps the cursor on the row selected with the keyboard
+    /// when the pointer is resting on another row or the list scrolls under it.
+    pub hover_stickiness:   f32,
+    /// Whether Up and Down navigate previously selected values.
+    pub history:            HistoryMode,
+    /// What happens to the text when the editor gains focus.
+    pub focus_text:         FocusText,
+    /// Whether the popup closes when an option is selected.
+    ///
+    /// If unset, the editor keeps focus after a selection
+    /// so that the user can continue browsing the options.
+    /// Inside a modal dialog, e.g. an [`egui::Modal`], the editor keeps focus either way
+    /// so that keyboard focus does not leave the dialog,
+    /// and the popup reopens when the user types, clicks the editor or presses Down.
+    pub close_on_select:    bool,
+    /// Whether Up and Down wrap around at the ends of the list.
+    pub wrap_navigation:    bool,
+    /// The minimum number of characters typed before the popup is displayed,
+    /// e.g. to avoid listing a huge set of options before the user narrows it down.
+    pub min_chars:          usize,
+    /// Whether Tab copies the cursor option into the editor without selecting it,
+    /// like shell completion.
+    ///
+    /// Tab only keeps focus in the editor if it would change the text,
+    /// so pressing it again moves focus to the next widget as usual.
+    pub tab_completes:      bool,
+    /// The keyboard shortcuts acting on the cursor option.
+    pub shortcuts:          Shortcuts,
+    /// Whether the typed text itself is committed when no option matches it,
+    /// as described in [`EditableComboBox::allow_free_text`](crate::EditableComboBox::allow_free_text).
+    pub allow_free_text:    bool,
+    /// Whether the options are displayed in reverse order,
+    /// as described in [`EditableComboBox::reverse_order`](crate::EditableComboBox::reverse_order).
+    pub reverse_order:      bool,
+    /// Whether the option equal to the current value is always displayed first,
+    /// as described in [`EditableComboBox::pin_selected`](crate::EditableComboBox::pin_selected).
+    pub pin_selected:       bool,
+    /// Whether the rest of the cursor option is displayed after the caret,
+    /// as described in [`EditableComboBox::ghost_text`](crate::EditableComboBox::ghost_text).
+    pub ghost_text:         bool,
+    /// Whether fuzzy matches are listed if nothing else matches,
+    /// as described in [`EditableComboBox::fuzzy_fallback`](crate::EditableComboBox::fuzzy_fallback).
+    pub fuzzy_fallback:     bool,
+}
+
+impl Default for InteractionConfig {
+    fn default() -> Self {
+        Self {
+            hover_moves_cursor: true,
+            hover_delay:        0.05,
+            hover_stickiness:   2.0,
+            history:            HistoryMode::default(),
+            focus_text:         FocusText::default(),
+            close_on_select:    true,
+            wrap_navigation:    true,
+            min_chars:          0,
+            tab_completes:      true,
+            shortcuts:          Shortcuts::default(),
+            allow_free_text:    false,
+            reverse_order:      false,
+            pin_selected:       false,
+            ghost_text:         false,
+            fuzzy_fallback:     false,
+        }
+    }
+}
+
+/// Defaults for every combo box shown in an egui context,
+/// registered with [`EditableComboBox::set_defaults`](crate::EditableComboBox::set_defaults).
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ComboBoxDefaults {
+    /// The behavior settings used unless
+    /// [`EditableComboBox::interaction`](crate::EditableComboBox::interaction) is called.
+    ///
+    /// Fields with their own builder setter, like
+    /// [`allow_free_text`](crate::EditableComboBox::allow_free_text),
+    /// are only overridden by that setter.
+    pub interaction: InteractionConfig,
+    /// The popup visuals used unless
+    /// [`EditableComboBox::popup_style`](crate::EditableComboBox::popup_style) is called.
+    ///
+    /// [`dense`](crate::EditableComboBox::dense) and
+    /// [`row_spacing`](crate::EditableComboBox::row_spacing) are only overridden by their setters.
+    pub popup_style: PopupStyle,
+}
+
+/// What happens to the text of a combo box when its editor gains focus,
+/// set in [`InteractionConfig::focus_text`].
+///
+/// The text is never cleared with a [`CompletionScope`] other than
+/// [`Whole`](CompletionScope::Whole), since the text is the value itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FocusText {
+    /// The text is cleared, showing the current value as hint text,
+    /// so that all options are listed instead of only those matching the current value.
+    #[default]
+    Clear,
+    /// The text is kept and filters the options.
+    Keep,
+    /// The current value is kept as real text, so that it can be drag-selected and copied,
+    /// but the options are not filtered until the text is edited.
+    Selectable,
+}
+
+/// Whether Up and Down navigate previously selected values of a combo box.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HistoryMode {
+    /// Up and Down only move the cursor in the popup.
+    #[default]
+    Off,
+    /// Like readline, Up replaces an empty text with the last selected value,
+    /// and further Up and Down presses step through older and newer values
+    /// instead of moving the cursor in the popup.
+    /// Stepping past the newest value clears the text again.
+    ///
+    /// The history is kept per combo box in egui memory,
+    /// or in the [`StateStore`](crate::StateStore) of the combo box if set.
+    Readline,
+}
+
+/// The keyboard shortcuts acting on the cursor option of a combo box,
+/// and the shortcuts left to the app.
+///
+/// Up, Down, Home, End, Page Up, Page Down and Enter are not remappable,
+/// since they follow the conventions of every text editor.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Shortcuts {
+    /// Copies the cursor option into the editor without selecting it.
+    /// Defaults to Ctrl+Space.
+    pub complete:       KeyboardShortcut,
+    /// Starts renaming the cursor option if
+    /// [`on_rename`](crate::EditableComboBox::on_rename) is set.
+    /// Defaults to F2.
+    pub rename:         KeyboardShortcut,
+    /// Triggers the action of the cursor option if
+    /// [`row_action`](crate::EditableComboBox::row_action) is set.
+    /// Defaults to Alt+Enter.
+    pub row_action:     KeyboardShortcut,
+    /// Moves the cursor to the first option of the next group
+    /// separated by [`with_separators`](crate::with_separators).
+    /// Defaults to Ctrl+Down.
+    pub next_group:     KeyboardShortcut,
+    /// Moves the cursor to the first option of the previous group.
+    /// Defaults to Ctrl+Up.
+    pub previous_group: KeyboardShortcut,
+    /// Shortcuts that the combo box ignores even while it is focused,
+    /// so that the app can handle them, e.g. F5 to refresh or Ctrl+S to save.
+    ///
+    /// Their key events are hidden from the editor and the popup,
+    /// and restored for the widgets shown after the combo box.
+    /// Text typed by these keys and clipboard events such as copying with Ctrl+C
+    /// are still handled by the editor.
+    /// Empty by default.
+    pub pass_through:   Vec<KeyboardShortcut>,
+    /// How the keys of the shortcuts are matched with the pressed keys.
+    pub resolution:     KeyResolution,
+}
+
+impl Default for Shortcuts {
+    fn default() -> Self {
+        Self {
+            complete:       KeyboardShortcut::new(Modifiers::CTRL, Key::Space),
+            rename:         KeyboardShortcut::new(Modifiers::NONE, Key::F2),
+            row_action:     KeyboardShortcut::new(Modifiers::ALT, Key::Enter),
+            next_group:     KeyboardShortcut::new(Modifiers::CTRL, Key::ArrowDown),
+            previous_group: KeyboardShortcut::new(Modifiers::CTRL, Key::ArrowUp),
+            pass_through:   Vec::new(),
+            resolution:     KeyResolution::default(),
+        }
+    }
+}
+
+impl Shortcuts {
+    /// Whether `shortcut` was pressed in this frame.
+    pub(crate) fn pressed(&self, input: &egui::InputState, shortcut: KeyboardShortcut) -> bool {
+        input.events.iter().any(|event| {
+            matches!(event, egui::Event::Key { pressed: true, .. }) && self.matches(event, shortcut)
+        })
+    }
+
+    /// Whether `event` is a press or release of the key of `shortcut`.
+    fn matches(&self, event: &egui::Event, shortcut: KeyboardShortcut) -> bool {
+        match event {
+            egui::Event::Key { key, physical_key, modifiers, .. } => {
+                let key = match self.resolution {
+                    KeyResolution::Logical => *key,
+                    KeyResolution::Physical => physical_key.unwrap_or(*key),
+                };
+                key == shortcut.logical_key && modifiers.matches_logically(shortcut.modifiers)
+            }
+            _ => false,
+        }
+    }
+
+    /// Removes the key events of the [`pass_through`](Self::pass_through) shortcuts
+    /// from the input of this frame, returning them to be restored afterwards.
+    pub(crate) fn hide_pass_through(&self, ctx: &egui::Context) -> Vec<egui::Event> {
+        if self.pass_through.is_empty() {
+            return Vec::new();
+        }
+        ctx.input_mut(|input| {
+            let (passed, kept) = mem::take(&mut input.events).into_iter().partition(|event| {
+                self.pass_through.iter().any(|shortcut| self.matches(event, *shortcut))
+            });
+            input.events = kept;
+            passed
+        })
+    }
+}
+
+/// How the keys of [`Shortcuts`] are matched with the pressed keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum KeyResolution {
+    /// Match the key produced by the current keyboard layout.
+    #[default]
+    Logical,
+    /// Match the physical position of the key, named after the US QWERTY layout,
+    /// so that shortcuts stay in the same place on any layout,
+    /// e.g. the digit row on AZERTY keyboards, which produces digits only with Shift.
+    ///
+    /// Falls back to the logical key if the integration does not report physical keys.
+    Physical,
+}
+
+/// Whether a selection may be committed, returned by
+/// [`EditableComboBox::on_before_commit`](crate::EditableComboBox::on_before_commit).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommitGuard {
+    /// Commit the selection.
+    Allow,
+    /// Keep the current value and display the reason in the popup.
+    Deny(String),
+}
+
+/// Which part of the user text is used to filter and complete options.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum CompletionScope {
+    /// The whole text filters the options, and selecting an option sets the value.
+    #[default]
+    Whole,
+    /// The text contains multiple values separated by `delimiter`,
+    /// e.g. the recipients in an email "To" field.
+    ///
+    /// Only the segment under the caret filters the options,
+    /// and selecting an option replaces that segment with its
+    /// [`ValueOption::editable_text`](crate::ValueOption::editable_text)
+    /// while keeping the editor focused.
+    /// The whole text is committed as the value through
+    /// [`Value::try_from_editable`](crate::Value::try_from_editable).
+    /// If the completed text is not a valid value,
+    /// the selection is rejected like a denied [`CommitGuard`] and the popup stays open.
+    /// The text is not cleared when the editor gains focus.
+    Segment {
+        /// The character separating the segments, e.g. `,`.
+        delimiter: char,
+    },
+    /// Only the text before the caret filters the options,
+    /// enabling mid-string completion workflows such as inserting template variables.
+    ///
+    /// Selecting an option replaces the text before the caret with its
+    /// [`ValueOption::editable_text`](crate::ValueOption::editable_text),
+    /// keeping the text after the caret.
+    /// Like [`Segment`](CompletionScope::Segment),
+    /// the whole text is committed as the value through
+    /// [`Value::try_from_editable`](crate::Value::try_from_editable).
+    BeforeCaret,
+}
+
+impl CompletionScope {
+    /// Returns the byte range of `text` filtering the options,
+    /// given the char index of the caret,
+    /// or `None` if the whole text is used for [`Whole`](CompletionScope::Whole).
+    pub(crate) fn filter_range(&self, text: &str, caret: usize) -> Option<Range<usize>> {
+        let caret = text.char_indices().nth(caret).map_or(text.len(), |(index, _)| index);
+
+        match self {
+            CompletionScope::Whole => None,
+            CompletionScope::Segment { delimiter } => {
+                let start = text[..caret].rfind(*delimiter).map_or(0, |i| i + delimiter.len_utf8());
+                let end = text[caret..].find(*delimiter).map_or(text.len(), |i| caret + i);
+
+                // Surrounding whitespace is not part of the segment.
+                let segment = &text[start..end];
+                let start = start + (segment.len() - segment.trim_start().len());
+                let end = start + segment.trim().len();
+                Some(start..end)
+            }
+            CompletionScope::BeforeCaret => Some(0..caret),
+        }
+    }
+}
+
+/// How options are compared with the current value
+/// to mark them as selected in the popup.
+///
+/// [`ValueOption::equals_value`](crate::ValueOption::equals_value)
+/// is consulted for every option on every frame by default,
+/// which may be expensive or ambiguous for values without cheap equality,
+/// e.g. floats or fuzzy-equal strings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EqualityPolicy {
+    /// Compare every option with the current value.
+    ///
+    /// If multiple options are equal to the current value,
+    /// only the first one is marked as selected,
+    /// and a warning is painted next to the editor in debug builds.
+    #[default]
+    EveryOption,
+    /// Stop comparing once an option equal to the current value is found.
+    ///
+    /// Unlike [`EveryOption`](EqualityPolicy::EveryOption),
+    /// ambiguous equality is not detected.
+    FirstEqual,
+    /// Never compare options with the current value.
+    ///
+    /// No option is marked as selected,
+    /// and the cursor does not jump to the current value when the popup opens.
+    Never,
+}
+
+/// Where the popup is placed relative to the editor,
+/// set with [`popup_placement`](crate::EditableComboBox::popup_placement).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PopupPlacement {
+    /// Below the editor, or wherever egui finds room if it does not fit below.
+    #[default]
+    Auto,
+    /// Below the editor, or above it if it does not fit below.
+    BelowOrAbove,
+    /// Above the editor, or below it if it does not fit above,
+    /// e.g. for combo boxes in a bottom panel.
+    AboveOrBelow,
+    /// Always at the given alignment relative to the editor, even if it does not fit.
+    Fixed(egui::RectAlign),
+}
+
+impl PopupPlacement {
+    /// The preferred alignment and the alternatives to try if it does not fit,
+    /// or `None` to use the defaults of [`egui::Popup`].
+    pub(crate) fn aligns(self) -> Option<(egui::RectAlign, &'static [egui::RectAlign])> {
+        use egui::RectAlign;
+
+        match self {
+            PopupPlacement::Auto => None,
+            PopupPlacement::BelowOrAbove => {
+                Some((RectAlign::BOTTOM_START, &[RectAlign::TOP_START]))
+            }
+            PopupPlacement::AboveOrBelow => {
+                Some((RectAlign::TOP_START, &[RectAlign::BOTTOM_START]))
+            }
+            PopupPlacement::Fixed(align) => Some((align, &[])),
+        }
+    }
+}
+
+/// How options wider than
+/// [`max_popup_width`](crate::EditableComboBox::max_popup_width)
+/// or the screen are displayed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PopupOverflow {
+    /// Scroll the popup horizontally.
+    #[default]
+    Scroll,
+    /// Truncate the options with an ellipsis.
+    Truncate,
+    /// Wrap the options onto multiple lines.
+    ///
+    /// Each row is measured when displayed,
+    /// so the scroll bar stays accurate with rows of different heights.
+    Wrap,
+}
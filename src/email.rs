@@ -0,0 +1,226 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use core::hash::Hash;
+use core::str::FromStr;
+
+use egui::IntoAtoms;
+
+use crate::{CompletionScope, EditableComboBox, FilterResult, FilterState, Value, ValueOption};
+
+/// An email address, validated when it is parsed from text.
+///
+/// The validation is a practical subset of RFC 5322:
+/// the local part must not contain whitespace or special characters,
+/// and the domain must consist of at least two dot-separated labels
+/// of ASCII letters, digits and hyphens.
+///
+/// # Example
+/// ```
+/// use egui_editable_combobox::EmailValue;
+///
+/// let address: EmailValue = "ada@example.com".parse().unwrap();
+/// assert_eq!(address.domain(), "example.com");
+/// assert!("ada@localhost".parse::<EmailValue>().is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EmailValue(String);
+
+impl EmailValue {
+    /// The whole address.
+    #[must_use]
+    pub fn as_str(&self) -> &str { &self.0 }
+
+    /// The part of the address before the `@`.
+    #[must_use]
+    pub fn local_part(&self) -> &str { self.0.split_once('@').map_or("", |(local, _)| local) }
+
+    /// The part of the address after the `@`.
+    #[must_use]
+    pub fn domain(&self) -> &str { self.0.split_once('@').map_or("", |(_, domain)| domain) }
+}
+
+impl FromStr for EmailValue {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, String> {
+        let Some((local, domain)) = text.split_once('@') else {
+            return Err(String::from("missing @"));
+        };
+        if local.is_empty() {
+            return Err(String::from("missing name before @"));
+        }
+        if local
+            .chars()
+            .any(|c| c.is_whitespace() || c.is_control() || "@<>()[],;:\"\\".contains(c))
+        {
+            return Err(format!("invalid character in \"{local}\""));
+        }
+        let labels: Vec<&str> = domain.split('.').collect();
+        let valid_label = |label: &&str| {
+            !label.is_empty()
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        };
+        if labels.len() < 2 || !labels.iter().all(valid_label) {
+            return Err(format!("invalid domain \"{domain}\""));
+        }
+        Ok(Self(String::from(text)))
+    }
+}
+
+impl fmt::Display for EmailValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { f.write_str(&self.0) }
+}
+
+impl Value for EmailValue {
+    fn to_editable(&self) -> String { self.0.clone() }
+
+    fn try_from_editable(text: &str) -> Option<Self> { text.parse().ok() }
+}
+
+/// A preset combo box for entering an email address, or a list of recipients.
+///
+/// Typing the `@` of an address suggests the address at each of the [domains](Self::domains)
+/// starting with the text after the `@`.
+/// Any other valid address may be typed too,
+/// and the editor is outlined as an error while the address is invalid.
+///
+/// # Example
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// use egui_editable_combobox::{EmailPicker, EmailValue};
+///
+/// let mut from: Option<EmailValue> = None;
+/// EmailPicker::new("from").show(ui, &mut from);
+///
+/// let mut to: Vec<EmailValue> = Vec::new();
+/// EmailPicker::new("to").domains(["example.com", "example.org"]).show_list(ui, &mut to);
+/// # });
+/// ```
+#[must_use = "You should call .show()"]
+pub struct EmailPicker {
+    id_salt: egui::Id,
+    domains: Vec<String>,
+}
+
+impl EmailPicker {
+    /// The domains suggested by default.
+    pub const COMMON_DOMAINS: &[&str] =
+        &["gmail.com", "outlook.com", "yahoo.com", "icloud.com", "hotmail.com", "proton.me"];
+
+    /// Create a new `EmailPicker` with the given ID.
+    pub fn new(id_salt: impl Hash) -> Self {
+        Self {
+            id_salt: egui::Id::new(id_salt),
+            domains: Self::COMMON_DOMAINS.iter().map(ToString::to_string).collect(),
+        }
+    }
+
+    /// Set the domains suggested after the `@`, e.g. the domains of an organization.
+    pub fn domains(mut self, domains: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.domains = domains.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Display the picker for a single address in the given UI.
+    ///
+    /// Committing empty text is not possible, so `address` is only set to `None` by the caller.
+    pub fn show(self, ui: &mut egui::Ui, address: &mut Option<EmailValue>) -> egui::Response {
+        EditableComboBox::new(self.id_salt)
+            .allow_free_text(true)
+            .validate(|text| text.parse::<EmailValue>().map(drop))
+            .show(ui, address, self.domains.iter().map(|domain| DomainOption(domain)))
+    }
+
+    /// Display the picker for a list of addresses separated by commas in the given UI.
+    ///
+    /// Domains are suggested for the address under the caret.
+    pub fn show_list(self, ui: &mut egui::Ui, addresses: &mut Vec<EmailValue>) -> egui::Response {
+        let mut list = EmailList(core::mem::take(addresses));
+        let response = EditableComboBox::new(self.id_salt)
+            .allow_free_text(true)
+            .completion_scope(CompletionScope::Segment { delimiter: ',' })
+            .validate(|text| EmailList::parse(text).map(drop))
+            .show(ui, &mut list, self.domains.iter().map(|domain| DomainOption(domain)));
+        *addresses = list.0;
+        response
+    }
+}
+
+/// The addresses of [`EmailPicker::show_list`].
+struct EmailList(Vec<EmailValue>);
+
+impl EmailList {
+    /// Parses the addresses separated by commas, ignoring empty ones,
+    /// e.g. after a trailing comma.
+    fn parse(text: &str) -> Result<Self, String> {
+        text.split(',')
+            .map(str::trim)
+            .filter(|address| !address.is_empty())
+            .map(str::parse)
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+}
+
+impl Value for EmailList {
+    fn to_editable(&self) -> String {
+        let addresses: Vec<&str> = self.0.iter().map(EmailValue::as_str).collect();
+        addresses.join(", ")
+    }
+
+    fn try_from_editable(text: &str) -> Option<Self> { Self::parse(text).ok() }
+}
+
+/// Suggests the address typed before the `@` at a domain.
+struct DomainOption<'a>(&'a str);
+
+impl DomainOption<'_> {
+    /// The suggested address for the typed `text`,
+    /// if the text has an `@` and the domain starts with the text after it.
+    fn address(&self, text: &str) -> Option<EmailValue> {
+        let (local, typed) = text.trim().split_once('@')?;
+        let start = self.0.get(..typed.len())?;
+        if !start.eq_ignore_ascii_case(typed) {
+            return None;
+        }
+        format!("{local}@{}", self.0).parse().ok()
+    }
+}
+
+impl<V> ValueOption<V> for DomainOption<'_>
+where
+    V: Value + From<EmailValue>,
+{
+    fn filter_by_text(&self, text: &str, _: FilterState) -> FilterResult {
+        match self.address(text) {
+            Some(address) if address.as_str() == text.trim() => FilterResult::Exact,
+            Some(_) => FilterResult::Partial,
+            None => FilterResult::None,
+        }
+    }
+
+    fn display(&self, text: &str) -> impl IntoAtoms<'_> {
+        self.address(text).map(|address| address.0).unwrap_or_default()
+    }
+
+    fn into_value(self, text: &str) -> V {
+        // Only options suggesting an address for the text are displayed to be selected.
+        V::from(self.address(text).expect("selected option suggests an address"))
+    }
+
+    fn equals_value(&self, value: &V, text: &str) -> bool {
+        self.address(text).is_some_and(|address| address.0 == value.to_editable())
+    }
+
+    fn editable_text(&self, text: &str) -> Option<String> {
+        self.address(text).map(|address| address.0)
+    }
+}
+
+impl From<EmailValue> for EmailList {
+    fn from(address: EmailValue) -> Self { Self(Vec::from([address])) }
+}
@@ -0,0 +1,47 @@
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::sync::Arc;
+
+use egui::{FontSelection, WidgetText};
+
+/// Replaces a control character, such as a newline or a tab, with a space of the same UTF-8 length,
+/// so that byte offsets into the text stay valid.
+pub(crate) fn flatten_char(ch: char) -> char {
+    match ch {
+        // ASCII control characters are one byte long.
+        '\0'..='\x1f' | '\x7f' => ' ',
+        // C1 control characters are two bytes long.
+        '\u{80}'..='\u{9f}' => '\u{a0}',
+        ch => ch,
+    }
+}
+
+/// Replaces the control characters in `text` as in [`flatten_char`],
+/// borrowing `text` if it has none.
+pub(crate) fn flatten_control_chars(text: &str) -> Cow<'_, str> {
+    if text.chars().any(char::is_control) {
+        Cow::Owned(text.chars().map(flatten_char).collect::<String>())
+    } else {
+        Cow::Borrowed(text)
+    }
+}
+
+/// Replaces the control characters in a label of the popup,
+/// so that each option is displayed on a single line.
+///
+/// Pre-laid out galleys are displayed unchanged.
+pub(crate) fn flatten_widget_text(style: &egui::Style, text: WidgetText) -> WidgetText {
+    if !text.text().chars().any(char::is_control) {
+        return text;
+    }
+    match text {
+        WidgetText::Text(text) => WidgetText::Text(flatten_control_chars(&text).into_owned()),
+        WidgetText::Galley(galley) => WidgetText::Galley(galley),
+        text => {
+            let job = text.into_layout_job(style, FontSelection::Default, egui::Align::Center);
+            let mut job = Arc::unwrap_or_clone(job);
+            job.text = flatten_control_chars(&job.text).into_owned();
+            WidgetText::LayoutJob(Arc::new(job))
+        }
+    }
+}
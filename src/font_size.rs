@@ -0,0 +1,87 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::hash::Hash;
+use core::ops::RangeInclusive;
+
+use egui::RichText;
+
+use crate::{
+    EditableComboBox, NumericOption, NumericOptions, NumericValue, ValueOption, load_text_buf,
+};
+
+/// A preset combo box for picking a font size.
+///
+/// Standard sizes are provided as options,
+/// and the user may also type any size, which is clamped into the allowed range.
+/// A preview of the size under the cursor, or else the selected size,
+/// is displayed next to the editor.
+///
+/// # Example
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// use egui_editable_combobox::FontSizePicker;
+///
+/// let mut size = 14.0;
+/// FontSizePicker::new("font size").range(6.0..=96.0).show(ui, &mut size);
+/// # });
+/// ```
+#[must_use = "You should call .show()"]
+pub struct FontSizePicker {
+    id_salt: egui::Id,
+    sizes:   Vec<f32>,
+    range:   RangeInclusive<f32>,
+}
+
+impl FontSizePicker {
+    /// The sizes provided as options by default.
+    pub const STANDARD_SIZES: &[f32] =
+        &[8.0, 9.0, 10.0, 11.0, 12.0, 14.0, 16.0, 18.0, 20.0, 24.0, 28.0, 32.0, 36.0, 48.0, 72.0];
+
+    /// Create a new `FontSizePicker` with the given ID.
+    pub fn new(id_salt: impl Hash) -> Self {
+        Self {
+            id_salt: egui::Id::new(id_salt),
+            sizes:   Self::STANDARD_SIZES.to_vec(),
+            range:   1.0..=200.0,
+        }
+    }
+
+    /// Set the sizes provided as options.
+    pub fn sizes(mut self, sizes: impl IntoIterator<Item = f32>) -> Self {
+        self.sizes = sizes.into_iter().collect();
+        self
+    }
+
+    /// Set the range that typed sizes are clamped into.
+    ///
+    /// An inverted range such as `72.0..=8.0` is treated as `8.0..=72.0`.
+    pub fn range(mut self, range: RangeInclusive<f32>) -> Self {
+        let (start, end) = range.into_inner();
+        self.range = if start <= end { start..=end } else { end..=start };
+        self
+    }
+
+    /// Display the picker in the given UI.
+    pub fn show(self, ui: &mut egui::Ui, size: &mut f32) -> egui::Response {
+        let Self { id_salt, sizes, range } = self;
+        let options: Vec<NumericOption<f32>> =
+            NumericOptions::new(sizes).clamp(range).into_iter().collect();
+
+        ui.horizontal(|ui| {
+            let combo_box = EditableComboBox::new(id_salt);
+            let combo_id = combo_box.id_salt;
+            let mut value = NumericValue(*size);
+            let output = combo_box.show_with_output(ui, &mut value, options.clone());
+            *size = value.0;
+
+            let text = load_text_buf::<String>(ui.ctx(), None, combo_id, "");
+            let preview = output
+                .cursor
+                .and_then(|cursor| options.get(cursor)?.editable_text(&text)?.parse().ok())
+                .unwrap_or(*size);
+            ui.label(RichText::new("Aa").size(preview));
+            output.response
+        })
+        .inner
+    }
+}
@@ -0,0 +1,138 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use egui::text::LayoutJob;
+use egui::{Atom, IntoAtoms};
+
+use crate::flatten::flatten_char;
+use crate::{FilterResult, FilterState, MatchHighlight, ValueOption};
+
+/// Wraps an option to match it by fuzzy subsequence instead of substring.
+///
+/// The option matches if the typed text is a case-insensitive subsequence of its
+/// [`editable_text`](ValueOption::editable_text),
+/// so that typing "eur" or "esa" matches "Eurasia".
+/// Matches are [scored](FilterResult::Scored) by [`fuzzy_score`],
+/// so the closest matches are displayed first.
+/// Options without an editable text fall back to their own filter.
+///
+/// # Example
+/// ```
+/// use egui_editable_combobox::{ComboModel, FuzzyOption};
+///
+/// let mut model = ComboModel::<String, _>::new(["Africa", "Eurasia"].map(FuzzyOption));
+/// model.input_text("esa");
+/// assert_eq!(model.filtered(), [1]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FuzzyOption<Opt>(pub Opt);
+
+impl<Opt> FuzzyOption<Opt> {
+    /// Scores how well `text` matches this option, as in [`fuzzy_score`].
+    ///
+    /// Returns `None` if the option has no editable text or `text` does not match it.
+    pub fn score<V>(&self, text: &str) -> Option<u32>
+    where
+        Opt: ValueOption<V>,
+    {
+        fuzzy_score(&self.0.editable_text(text)?, text)
+    }
+}
+
+impl<V, Opt: ValueOption<V>> ValueOption<V> for FuzzyOption<Opt> {
+    fn filter_by_text(&self, text: &str, state: FilterState) -> FilterResult {
+        match self.0.editable_text(text) {
+            Some(full) if full == text => FilterResult::Exact,
+            #[expect(clippy::cast_precision_loss)] // scores are small
+            Some(full) => fuzzy_score(&full, text)
+                .map_or(FilterResult::None, |score| FilterResult::Scored(score as f32)),
+            None => self.0.filter_by_text(text, state),
+        }
+    }
+
+    fn display(&self, text: &str) -> impl IntoAtoms<'_> { self.0.display(text) }
+
+    /// Highlights the characters matched by the subsequence,
+    /// if the wrapped option is displayed with highlighting at all.
+    fn display_highlighted(&self, text: &str, highlight: &MatchHighlight) -> Option<LayoutJob> {
+        let inner = self.0.display_highlighted(text, highlight)?;
+        let Some(full) = self.0.editable_text(text) else { return Some(inner) };
+        let mut matches = Vec::<Range<usize>>::new();
+        fuzzy_match(&full, text, |range, _, consecutive| match matches.last_mut() {
+            Some(last) if consecutive => last.end = range.end,
+            _ => matches.push(range),
+        })?;
+        Some(highlight.layout(&full, &matches))
+    }
+
+    fn into_value(self, text: &str) -> V { self.0.into_value(text) }
+
+    fn equals_value(&self, value: &V, text: &str) -> bool { self.0.equals_value(value, text) }
+
+    fn editable_text(&self, text: &str) -> Option<String> { self.0.editable_text(text) }
+
+    fn icon(&self) -> Option<Atom<'_>> { self.0.icon() }
+
+    fn description(&self) -> Option<&str> { self.0.description() }
+
+    fn is_separator(&self) -> bool { self.0.is_separator() }
+
+    fn is_enabled(&self, text: &str) -> bool { self.0.is_enabled(text) }
+}
+
+/// Scores how well `input` matches `full` as a case-insensitive subsequence.
+///
+/// Each character of `input` is matched with the earliest remaining character of `full`.
+/// Matched characters score higher at the start of a word or right after the previous match,
+/// so a higher score means a closer match.
+/// Returns `None` if `input` is not a subsequence of `full`.
+#[must_use]
+pub fn fuzzy_score(full: &str, input: &str) -> Option<u32> {
+    let mut score = 0;
+    fuzzy_match(full, input, |_, word_start, consecutive| {
+        score += char_score(word_start, consecutive);
+    })?;
+    Some(score)
+}
+
+/// The score of a matched character in [`fuzzy_score`].
+pub(crate) fn char_score(word_start: bool, consecutive: bool) -> u32 {
+    1 + if word_start { 3 } else { 0 } + if consecutive { 2 } else { 0 }
+}
+
+/// Matches each character of `input` with the earliest remaining character of `full`,
+/// where control characters in `full` are matched as spaces,
+/// calling `on_match` with the byte range of the matched character,
+/// whether it starts a word and whether it immediately follows the previous match.
+///
+/// Returns `None` if `input` is not a subsequence of `full`.
+pub(crate) fn fuzzy_match(
+    full: &str,
+    input: &str,
+    mut on_match: impl FnMut(Range<usize>, bool, bool),
+) -> Option<()> {
+    let mut full_chars = full.char_indices();
+    let mut prev: Option<char> = None;
+    let mut prev_matched = false;
+
+    for wanted in input.chars() {
+        loop {
+            let (index, ch) = full_chars.next()?;
+            let word_start = prev.is_none_or(|prev| {
+                !prev.is_alphanumeric() || (prev.is_lowercase() && ch.is_uppercase())
+            });
+            let ch = flatten_char(ch);
+            let matched = ch.to_lowercase().eq(wanted.to_lowercase());
+            if matched {
+                on_match(index..index + ch.len_utf8(), word_start, prev_matched);
+            }
+            prev = Some(ch);
+            prev_matched = matched;
+            if matched {
+                break;
+            }
+        }
+    }
+    Some(())
+}
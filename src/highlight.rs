@@ -0,0 +1,65 @@
+use core::ops::Range;
+
+use egui::text::{LayoutJob, TextFormat};
+use egui::{Color32, FontId};
+
+use crate::flatten::flatten_control_chars;
+
+/// How the characters matching the typed text are highlighted in the popup.
+///
+/// This is passed to [`ValueOption::display_highlighted`](crate::ValueOption::display_highlighted)
+/// to lay out the text of an option with its matches highlighted.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchHighlight {
+    /// The font of the row text.
+    pub font_id: FontId,
+    /// The color of the matching characters.
+    pub color:   Color32,
+}
+
+impl MatchHighlight {
+    /// Lays out `full` with the byte ranges in `matches` highlighted.
+    ///
+    /// `matches` must be sorted, non-overlapping and on char boundaries.
+    /// The rest of the text uses the default text color of the row.
+    #[must_use]
+    pub fn layout(&self, full: &str, matches: &[Range<usize>]) -> LayoutJob {
+        let plain = TextFormat::simple(self.font_id.clone(), Color32::PLACEHOLDER);
+        let highlighted = TextFormat::simple(self.font_id.clone(), self.color);
+
+        let mut job = LayoutJob::default();
+        let mut end = 0;
+        for range in matches {
+            job.append(&full[end..range.start], 0.0, plain.clone());
+            job.append(&full[range.clone()], 0.0, highlighted.clone());
+            end = range.end;
+        }
+        job.append(&full[end..], 0.0, plain);
+        job
+    }
+
+    /// Lays out `full` with the first case-insensitive occurrence of `input` highlighted,
+    /// as matched by [`FilterResult::from_case_insensitive_substring`](crate::FilterResult::from_case_insensitive_substring).
+    #[must_use]
+    pub fn layout_substring(&self, full: &str, input: &str) -> LayoutJob {
+        let matched = substring_match(full, input);
+        self.layout(full, matched.as_slice())
+    }
+}
+
+/// Finds the byte range of the first case-insensitive occurrence of `input` in `full`.
+///
+/// Returns `None` if `input` is empty, does not occur,
+/// or lowercasing changes the byte offsets of `full`.
+pub(crate) fn substring_match(full: &str, input: &str) -> Option<Range<usize>> {
+    if input.is_empty() {
+        return None;
+    }
+    let lower = flatten_control_chars(full).to_lowercase();
+    if lower.len() != full.len() {
+        return None;
+    }
+    let start = lower.find(&input.to_lowercase())?;
+    let end = start + input.to_lowercase().len();
+    (full.is_char_boundary(start) && full.is_char_boundary(end)).then_some(start..end)
+}
@@ -2,18 +2,156 @@
 //!
 //! The main widget type is [`EditableComboBox`].
 //! See its documentation for details.
+//!
+//! The crate itself is `#![no_std]` and only uses `alloc`,
+//! but egui requires the standard library,
+//! so the widget cannot be used on targets without `std`.
+//!
+//! # Features
+//! - `serde`: Implements `Serialize` and `Deserialize` for [`Recording`],
+//!   so that interaction recordings can be attached to bug reports.
+//! - `nucleo`: Adds `NucleoMatcher`, a [`Matcher`] backed by the `nucleo-matcher` crate.
+//! - `fuzzy-matcher`: Adds `SkimMatcher`, a [`Matcher`] backed by the `fuzzy-matcher` crate.
+//! - `regex`: Adds [`Validator::regex`] to validate custom values with the `regex` crate,
+//!   and `RegexMatcher` with [`EditableComboBox::regex_filter`] to filter options by regex.
+//! - `derive`: Adds the `ComboValue` derive macro implementing [`Value`] and [`ValueOption`].
 
+#![no_std]
 #![warn(clippy::pedantic, missing_docs)]
 
-use std::hash::Hash;
+extern crate alloc;
 
+use alloc::boxed::Box;
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::hash::Hash;
+use core::marker::PhantomData;
+use core::mem;
+use core::ops::Range;
+use core::task::Poll;
+
+use egui::text::{CCursor, CCursorRange, LayoutJob, TextFormat};
 use egui::{
-    Align, Button, Layout, Popup, PopupAnchor, ScrollArea, TextEdit, TextStyle, TextWrapMode,
+    Align, Atoms, Button, EventFilter, Frame, IntoAtoms, Layout, Popup, PopupAnchor, Rect,
+    ScrollArea, Sense, SetOpenCommand, Shape, Stroke, TextBuffer, TextEdit, TextStyle,
+    TextWrapMode, UiBuilder, WidgetInfo, WidgetText, WidgetType,
 };
 
+mod add_new;
+pub use add_new::*;
+mod autofill;
+use autofill::merge_autofill;
+pub use autofill::*;
+mod config;
+pub use config::*;
+mod email;
+pub use email::*;
+mod flatten;
+mod font_size;
+pub use font_size::*;
+mod fuzzy;
+pub use fuzzy::*;
+mod highlight;
+pub use highlight::*;
+mod matcher;
+use matcher::highlight_matched;
+pub use matcher::*;
+mod memory_usage;
+pub use memory_usage::*;
+mod model;
+mod multi;
+mod numeric;
+pub use numeric::*;
+mod optional;
+pub use optional::*;
+mod output;
+pub use output::*;
+mod provider;
+pub use model::ComboModel;
+use model::{
+    Arrangement, DisplayedOption, FilterPass, FilteredOption, Motion, Relevance, cursor_rows,
+    displayed_cursor_index, group_starts, initial_cursor, move_cursor, move_recent_first,
+    separator_fits,
+};
+pub use provider::*;
+mod readonly;
+mod recording;
+pub use recording::*;
+mod rich;
+pub use rich::*;
+mod selection_history;
+pub use selection_history::*;
+use selection_history::{distinct_texts, load_selection_history};
+mod separator;
+pub use separator::*;
+mod setter;
+mod state_store;
+pub use state_store::*;
+mod style;
+pub use style::*;
+mod suggestion_pool;
+pub use suggestion_pool::*;
+mod summary;
+mod url;
+pub use url::*;
+mod validator;
+pub use validator::*;
 mod value;
+/// Derives [`Value`] and [`ValueOption`] for a fieldless enum or a newtype struct,
+/// without the boilerplate of [`ParseDisplayValue`].
+///
+/// For an enum, each variant is displayed and typed as its name,
+/// or as the label given with `#[combo(label = "...")]`.
+/// The options are filtered by case-insensitive substring like `&str` options.
+/// The derive also adds an inherent `VARIANTS` array of all variants
+/// and a `label` method returning the label of a variant.
+/// `ValueOption` is implemented for both the enum and references to it.
+///
+/// For a newtype struct, the inner value is displayed with `Display` and parsed with `FromStr`,
+/// and options are compared with `PartialEq` of the inner value.
+///
+/// # Example
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// use egui_editable_combobox::{ComboValue, EditableComboBox, Value};
+///
+/// #[derive(ComboValue)]
+/// enum Continent {
+///     Africa,
+///     #[combo(label = "North America")]
+///     NorthAmerica,
+///     Eurasia,
+/// }
+///
+/// let mut continent = Continent::NorthAmerica;
+/// EditableComboBox::new("continent").show(ui, &mut continent, &Continent::VARIANTS);
+/// assert_eq!(continent.to_editable(), "North America");
+///
+/// #[derive(ComboValue)]
+/// struct Port(u16);
+///
+/// let mut port = Port(8080);
+/// EditableComboBox::new("port").allow_free_text(true).show(
+///     ui,
+///     &mut port,
+///     [Port(80), Port(443), Port(8080)],
+/// );
+/// # });
+/// ```
+#[cfg(feature = "derive")]
+pub use egui_editable_combobox_derive::ComboValue;
 pub use value::*;
 
+/// Paths used by the code generated by [`ComboValue`], not part of the public API.
+#[doc(hidden)]
+pub mod __private {
+    pub use alloc::string::{String, ToString};
+
+    pub use egui;
+}
+
 /// A combo box that accepts text input for option filtering and custom value entry.
 ///
 /// # Example
@@ -40,13 +178,978 @@ pub use value::*;
 /// );
 /// # });
 /// ```
-pub struct EditableComboBox {
-    id_salt: egui::Id,
+///
+/// # Keyboard
+/// While the editor is focused, the keyboard cursor in the popup is moved with
+/// Up, Down, Home and End, and by the number of visible rows with Page Up and Page Down.
+/// Enter selects the cursor option,
+/// and Ctrl+Space copies its [`ValueOption::editable_text`] into the editor
+/// without selecting it, so that the suggestion can be edited further.
+/// Ctrl+Space and the other shortcuts acting on the cursor option can be remapped through
+/// [`InteractionConfig::shortcuts`].
+///
+/// # Text buffer
+/// The text typed by the user is stored in egui memory between frames.
+/// `B` is the [`TextBuffer`] type used for this storage,
+/// which can be changed with [`text_buffer`](EditableComboBox::text_buffer).
+#[must_use = "You should call .show()"]
+#[expect(clippy::struct_excessive_bools)] // each flag is an independent setting
+pub struct EditableComboBox<'a, B = String> {
+    id_salt:            egui::Id,
+    interaction:        InteractionConfig,
+    on_rename:          Option<Box<dyn FnMut(usize, String) + 'a>>,
+    on_select:          Option<Box<dyn FnMut(usize, egui::Modifiers) + 'a>>,
+    on_commit_feedback: Option<CommitFeedback<'a>>,
+    announcement:       Option<Announcement<'a>>,
+    before_commit:      Option<BeforeCommit<'a>>,
+    validate:           Option<Validate<'a>>,
+    row_action:         Option<RowAction<'a>>,
+    locked_prefix:      Option<String>,
+    completion_scope:   CompletionScope,
+    layouter:           Option<Layouter<'a>>,
+    equality:           EqualityPolicy,
+    unset_placeholder:  Option<String>,
+    clear_to:           Option<String>,
+    highlight_matches:  bool,
+    dropdown_arrow:     bool,
+    scroll_to_popup:    bool,
+    interactive:        bool,
+    flatten_labels:     bool,
+    desired_width:      Option<f32>,
+    max_popup_width:    Option<f32>,
+    min_popup_width:    f32,
+    max_popup_height:   Option<f32>,
+    popup_overflow:     PopupOverflow,
+    popup_placement:    PopupPlacement,
+    filter_budget:      Option<usize>,
+    remember_recent:    usize,
+    suggestion_pool:    Option<SuggestionPool>,
+    autofill:           Option<AutofillHint>,
+    multi_separator:    String,
+    /// The match count above which select all asks for confirmation, if the footer is shown.
+    select_all_footer:  Option<usize>,
+    /// Whether the combo box is shown by [`show_multi`](Self::show_multi).
+    multi:              bool,
+    matcher:            Option<Box<dyn Matcher + 'a>>,
+    recording:          Option<&'a mut Recording>,
+    state_store:        Option<&'a dyn StateStore>,
+    popup_style:        PopupStyle,
+    overridden:         Overridden,
+    _buffer:            PhantomData<fn() -> B>,
+}
+
+type CommitFeedback<'a> = Box<dyn FnMut(&egui::Context) + 'a>;
+
+type Announcement<'a> = Box<dyn Fn(&str) -> String + 'a>;
+
+type BeforeCommit<'a> = Box<dyn FnMut(Option<usize>, &dyn Value) -> CommitGuard + 'a>;
+
+type Validate<'a> = Box<dyn Fn(&str) -> Result<(), String> + 'a>;
+
+type Layouter<'a> = &'a mut dyn FnMut(&egui::Ui, &dyn TextBuffer, f32) -> Arc<egui::Galley>;
+
+/// Which settings were set on the builder instead of using the [`ComboBoxDefaults`].
+#[derive(Clone, Copy, Default)]
+struct Overridden {
+    interaction: bool,
+    popup_style: bool,
+    /// The individual settings set on the builder, as bits indexed by [`Setting`],
+    /// which are kept when the rest of their config is loaded from the defaults.
+    settings:    u8,
+}
+
+/// A field of [`InteractionConfig`] or [`PopupStyle`] with its own builder setter.
+#[derive(Clone, Copy)]
+enum Setting {
+    AllowFreeText,
+    ReverseOrder,
+    PinSelected,
+    GhostText,
+    FuzzyFallback,
+    FocusText,
+    RowSpacing,
+    Dense,
+}
+
+impl Overridden {
+    fn set(&mut self, setting: Setting) { self.settings |= 1 << setting as u8; }
+
+    fn has(self, setting: Setting) -> bool { self.settings & (1 << setting as u8) != 0 }
+}
+
+struct RowAction<'a> {
+    icon:      Box<dyn Fn(usize) -> Option<String> + 'a>,
+    on_action: Box<dyn FnMut(usize) + 'a>,
 }
 
-impl EditableComboBox {
+impl EditableComboBox<'_> {
     /// Create a new `EditableComboBox` with the given ID.
-    pub fn new(id_salt: impl Hash) -> Self { Self { id_salt: egui::Id::new(id_salt) } }
+    ///
+    /// The text being edited is stored in egui memory by this ID,
+    /// which must therefore be unique in the whole context, not only in the parent `Ui`.
+    /// Use [`scoped`](Self::scoped) for combo boxes created in a loop.
+    pub fn new(id_salt: impl Hash) -> Self {
+        Self {
+            id_salt:            egui::Id::new(id_salt),
+            interaction:        InteractionConfig::default(),
+            on_rename:          None,
+            on_select:          None,
+            on_commit_feedback: None,
+            announcement:       None,
+            before_commit:      None,
+            validate:           None,
+            row_action:         None,
+            locked_prefix:      None,
+            completion_scope:   CompletionScope::default(),
+            layouter:           None,
+            equality:           EqualityPolicy::default(),
+            unset_placeholder:  None,
+            clear_to:           None,
+            highlight_matches:  true,
+            dropdown_arrow:     false,
+            scroll_to_popup:    false,
+            interactive:        true,
+            flatten_labels:     true,
+            desired_width:      None,
+            max_popup_width:    None,
+            min_popup_width:    0.0,
+            max_popup_height:   None,
+            popup_overflow:     PopupOverflow::default(),
+            popup_placement:    PopupPlacement::default(),
+            filter_budget:      None,
+            remember_recent:    0,
+            suggestion_pool:    None,
+            autofill:           None,
+            multi_separator:    String::from(", "),
+            select_all_footer:  None,
+            multi:              false,
+            matcher:            None,
+            recording:          None,
+            state_store:        None,
+            popup_style:        PopupStyle::default(),
+            overridden:         Overridden::default(),
+            _buffer:            PhantomData,
+        }
+    }
+
+    /// Create a new `EditableComboBox` with an ID derived from `parent_id` and `key`,
+    /// for combo boxes created in a loop over dynamic data.
+    ///
+    /// `key` should identify the item edited by the combo box, such as a database key,
+    /// rather than its position in the list,
+    /// so that the text being edited stays with the item when items are inserted or removed.
+    /// The other associated functions taking an `id_salt`,
+    /// such as [`is_editing`](Self::is_editing),
+    /// refer to this combo box by `parent_id.with(key)`.
+    ///
+    /// # Example
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// use egui_editable_combobox::EditableComboBox;
+    ///
+    /// struct Task {
+    ///     id:       u64,
+    ///     assignee: String,
+    /// }
+    ///
+    /// let mut tasks = vec![
+    ///     Task { id: 7, assignee: String::from("Alice") },
+    ///     Task { id: 3, assignee: String::from("Bob") },
+    /// ];
+    /// for task in &mut tasks {
+    ///     EditableComboBox::scoped(ui.id(), task.id).show(
+    ///         ui,
+    ///         &mut task.assignee,
+    ///         ["Alice", "Bob", "Carol"],
+    ///     );
+    /// }
+    /// # });
+    /// ```
+    pub fn scoped(parent_id: egui::Id, key: impl Hash) -> Self { Self::new(parent_id.with(key)) }
+
+    /// Register the defaults used by every combo box shown in `ctx`
+    /// unless overridden on the builder,
+    /// so that the settings do not need to be passed through every call site.
+    ///
+    /// # Example
+    /// ```
+    /// use egui_editable_combobox::{ComboBoxDefaults, EditableComboBox, InteractionConfig};
+    ///
+    /// # let ctx = egui::Context::default();
+    /// EditableComboBox::set_defaults(
+    ///     &ctx,
+    ///     ComboBoxDefaults {
+    ///         interaction: InteractionConfig { wrap_navigation: false, ..Default::default() },
+    ///         ..Default::default()
+    ///     },
+    /// );
+    /// ```
+    pub fn set_defaults(ctx: &egui::Context, defaults: ComboBoxDefaults) {
+        ctx.data_mut(|data| data.insert_temp(defaults_id(), defaults));
+    }
+
+    /// Whether the user is editing the combo box with the given ID salt,
+    /// i.e. its editor is focused or its popup is open.
+    ///
+    /// This can be used to suppress global shortcuts while the user is typing.
+    /// Shortcuts that should keep working meanwhile can be listed in
+    /// [`Shortcuts::pass_through`] instead.
+    ///
+    /// # Example
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// use egui_editable_combobox::EditableComboBox;
+    ///
+    /// let mut fruit = String::new();
+    /// EditableComboBox::new("fruit").show(ui, &mut fruit, ["apple", "banana"]);
+    ///
+    /// if !EditableComboBox::is_editing(ui.ctx(), "fruit")
+    ///     && ui.input(|input| input.key_pressed(egui::Key::Delete))
+    /// {
+    ///     fruit.clear();
+    /// }
+    /// # });
+    /// ```
+    #[must_use]
+    pub fn is_editing(ctx: &egui::Context, id_salt: impl Hash) -> bool {
+        let id_salt = egui::Id::new(id_salt);
+        ctx.memory(|mem| {
+            mem.has_focus(Ids::Editor.id(id_salt)) || mem.has_focus(Ids::RenameEdit.id(id_salt))
+        }) || Popup::is_id_open(ctx, Ids::Popup.id(id_salt))
+    }
+}
+
+impl<'a, B> EditableComboBox<'a, B>
+where
+    B: TextBuffer + Default + Clone + Send + Sync + 'static,
+{
+    /// Use `B2` instead of `String` as the text buffer type.
+    ///
+    /// This allows apps to use buffer types with cheaper storage (e.g. inline small strings)
+    /// or share the buffer type with the rest of their text state.
+    pub fn text_buffer<B2>(self) -> EditableComboBox<'a, B2>
+    where
+        B2: TextBuffer + Default + Clone + Send + Sync + 'static,
+    {
+        EditableComboBox {
+            id_salt:            self.id_salt,
+            interaction:        self.interaction,
+            on_rename:          self.on_rename,
+            on_select:          self.on_select,
+            on_commit_feedback: self.on_commit_feedback,
+            announcement:       self.announcement,
+            before_commit:      self.before_commit,
+            validate:           self.validate,
+            row_action:         self.row_action,
+            locked_prefix:      self.locked_prefix,
+            completion_scope:   self.completion_scope,
+            layouter:           self.layouter,
+            equality:           self.equality,
+            unset_placeholder:  self.unset_placeholder,
+            clear_to:           self.clear_to,
+            highlight_matches:  self.highlight_matches,
+            dropdown_arrow:     self.dropdown_arrow,
+            scroll_to_popup:    self.scroll_to_popup,
+            interactive:        self.interactive,
+            flatten_labels:     self.flatten_labels,
+            desired_width:      self.desired_width,
+            max_popup_width:    self.max_popup_width,
+            min_popup_width:    self.min_popup_width,
+            max_popup_height:   self.max_popup_height,
+            popup_overflow:     self.popup_overflow,
+            popup_placement:    self.popup_placement,
+            filter_budget:      self.filter_budget,
+            remember_recent:    self.remember_recent,
+            suggestion_pool:    self.suggestion_pool,
+            autofill:           self.autofill,
+            multi_separator:    self.multi_separator,
+            select_all_footer:  self.select_all_footer,
+            multi:              self.multi,
+            matcher:            self.matcher,
+            recording:          self.recording,
+            state_store:        self.state_store,
+            popup_style:        self.popup_style,
+            overridden:         self.overridden,
+            _buffer:            PhantomData,
+        }
+    }
+
+    /// Set the behavior settings of this combo box.
+    ///
+    /// Overrides the [`ComboBoxDefaults`] registered on the context.
+    pub fn interaction(mut self, interaction: InteractionConfig) -> Self {
+        self.interaction = interaction;
+        self.overridden.interaction = true;
+        self
+    }
+
+    /// Set how options are compared with the current value
+    /// to mark them as selected in the popup.
+    pub fn equality(mut self, equality: EqualityPolicy) -> Self {
+        self.equality = equality;
+        self
+    }
+
+    /// Treat a value with an empty [`Value::to_editable`] text as no selection.
+    ///
+    /// By default, an empty `String` is a value like any other,
+    /// which is marked as selected if an option is equal to it.
+    /// With this setting, the editor of an unset value shows `placeholder` as a hint,
+    /// and no option is marked as selected.
+    pub fn empty_is_unset(mut self, placeholder: impl Into<String>) -> Self {
+        self.unset_placeholder = Some(placeholder.into());
+        self
+    }
+
+    /// Display the options in reverse order, i.e. the last option at the top.
+    ///
+    /// This is useful for sources where the most relevant entries are appended last,
+    /// e.g. logs or recent items.
+    /// Keyboard navigation follows the displayed order.
+    pub fn reverse_order(mut self, reverse_order: bool) -> Self {
+        self.interaction.reverse_order = reverse_order;
+        self.overridden.set(Setting::ReverseOrder);
+        self
+    }
+
+    /// Always display the option equal to the current value as the first row,
+    /// even if it does not match the text,
+    /// so that the user can see and re-select the current value while searching.
+    pub fn pin_selected(mut self, pin_selected: bool) -> Self {
+        self.interaction.pin_selected = pin_selected;
+        self.overridden.set(Setting::PinSelected);
+        self
+    }
+
+    /// Highlight the characters matching the typed text in the popup.
+    ///
+    /// Enabled by default.
+    /// Options are highlighted through [`ValueOption::display_highlighted`]
+    /// in the color of [`PopupStyle::highlight`].
+    pub fn highlight_matches(mut self, highlight_matches: bool) -> Self {
+        self.highlight_matches = highlight_matches;
+        self
+    }
+
+    /// Display the rest of the cursor option after the caret in a weak color
+    /// while the typed text is the start of its [editable text](ValueOption::editable_text),
+    /// like the address bar of a browser.
+    /// Right-to-left text is right-aligned, so its completion is displayed on its left.
+    ///
+    /// Tab or the completion shortcut accepts the displayed completion.
+    /// Disabled by default.
+    pub fn ghost_text(mut self, ghost_text: bool) -> Self {
+        self.interaction.ghost_text = ghost_text;
+        self.overridden.set(Setting::GhostText);
+        self
+    }
+
+    /// Display an arrow button after the editor, like [`egui::ComboBox`],
+    /// to show that the editor has a dropdown.
+    ///
+    /// Clicking the arrow opens the popup, or closes it if it is open.
+    pub fn dropdown_arrow(mut self, dropdown_arrow: bool) -> Self {
+        self.dropdown_arrow = dropdown_arrow;
+        self
+    }
+
+    /// Scroll the parent scroll areas when the popup opens,
+    /// so that both the editor and the popup are visible.
+    ///
+    /// This is useful for combo boxes at the bottom of a scrollable form,
+    /// where the popup would otherwise cover the area below the visible region.
+    pub fn scroll_to_popup(mut self, scroll_to_popup: bool) -> Self {
+        self.scroll_to_popup = scroll_to_popup;
+        self
+    }
+
+    /// Display a clear button at the trailing edge of the editor,
+    /// which resets the value to `default` and clears the text.
+    /// The trailing edge is the left edge if the text is right-to-left.
+    ///
+    /// `default` is the [editable text](Value::to_editable) of the value to reset to,
+    /// parsed with [`Value::try_from_editable`].
+    /// Pass an empty string to reset to an empty value.
+    /// The button is hidden if the value is already the default or `default` is not a valid value.
+    pub fn clear_button(mut self, default: impl Into<String>) -> Self {
+        self.clear_to = Some(default.into());
+        self
+    }
+
+    /// Commit the typed text itself when no option matches it,
+    /// instead of discarding it.
+    ///
+    /// When the user presses Enter or the editor loses focus with no option under the cursor,
+    /// the full text is parsed with [`Value::try_from_editable`] and committed if it is valid.
+    /// Empty text is never committed, since the editor may be cleared when focused.
+    pub fn allow_free_text(mut self, allow_free_text: bool) -> Self {
+        self.interaction.allow_free_text = allow_free_text;
+        self.overridden.set(Setting::AllowFreeText);
+        self
+    }
+
+    /// Validate the typed text while [free text](Self::allow_free_text) is allowed.
+    ///
+    /// While `validate` returns an error for the full text,
+    /// the editor is outlined in the error color with the error as its hover text,
+    /// and the text cannot be committed as free text.
+    /// Selecting an option is not affected.
+    ///
+    /// # Example
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// use egui_editable_combobox::EditableComboBox;
+    ///
+    /// let mut host = String::from("localhost");
+    /// EditableComboBox::new("host")
+    ///     .allow_free_text(true)
+    ///     .validate(|text| {
+    ///         let valid = text.split('.').all(|label| {
+    ///             !label.is_empty() && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+    ///         });
+    ///         if valid { Ok(()) } else { Err("not a valid hostname".into()) }
+    ///     })
+    ///     .show(ui, &mut host, ["localhost", "example.com"]);
+    /// # });
+    /// ```
+    pub fn validate(mut self, validate: impl Fn(&str) -> Result<(), String> + 'a) -> Self {
+        self.validate = Some(Box::new(validate));
+        self
+    }
+
+    /// Whether the combo box can be interacted with.
+    ///
+    /// A combo box that is not interactive is displayed greyed out with the current value,
+    /// its text cannot be edited and its popup never opens,
+    /// like a widget added with [`egui::Ui::add_enabled`].
+    /// The same applies in a disabled `Ui`, e.g. inside [`egui::Ui::add_enabled_ui`].
+    ///
+    /// Enabled by default.
+    pub fn interactive(mut self, interactive: bool) -> Self {
+        self.interactive = interactive;
+        self
+    }
+
+    /// Display newlines and other control characters in options as spaces,
+    /// so that each option is displayed on a single line.
+    ///
+    /// Enabled by default.
+    /// Disable it to display options spanning multiple lines, e.g. addresses.
+    /// The control characters are also matched as spaces when filtering,
+    /// regardless of this setting.
+    pub fn flatten_labels(mut self, flatten_labels: bool) -> Self {
+        self.flatten_labels = flatten_labels;
+        self
+    }
+
+    /// Keep the current value as real text when the editor is focused,
+    /// instead of clearing it into hint text.
+    ///
+    /// Disabled by default.
+    /// Enable it to let users drag-select and copy the current value
+    /// without having to retype it.
+    /// Options are not filtered until the text is edited.
+    /// This sets [`InteractionConfig::focus_text`] to [`FocusText::Selectable`],
+    /// or back to [`FocusText::Clear`] if disabled.
+    pub fn selectable_value(mut self, selectable_value: bool) -> Self {
+        if selectable_value {
+            self.interaction.focus_text = FocusText::Selectable;
+        } else if self.interaction.focus_text == FocusText::Selectable {
+            self.interaction.focus_text = FocusText::Clear;
+        }
+        self.overridden.set(Setting::FocusText);
+        self
+    }
+
+    /// Set the width of the widget, including the prefix and the dropdown arrow,
+    /// limited to the available width.
+    ///
+    /// By default, the editor is as wide as the
+    /// [`text_edit_width`](egui::style::Spacing::text_edit_width) of the style.
+    /// The popup is at least as wide as the editor and the dropdown arrow.
+    pub fn desired_width(mut self, desired_width: f32) -> Self {
+        self.desired_width = Some(desired_width);
+        self
+    }
+
+    /// Fill the available width, same as [`desired_width(f32::INFINITY)`](Self::desired_width).
+    pub fn width_full(self) -> Self { self.desired_width(f32::INFINITY) }
+
+    /// Limit the width of the popup.
+    ///
+    /// By default, the popup grows to fit the widest option up to the screen width.
+    /// Options wider than the limit are displayed according to
+    /// [`popup_overflow`](Self::popup_overflow).
+    pub fn max_popup_width(mut self, max_popup_width: f32) -> Self {
+        self.max_popup_width = Some(max_popup_width);
+        self
+    }
+
+    /// Set the minimum width of the popup in points.
+    ///
+    /// The popup is at least as wide as the editor by default.
+    /// Like other egui sizes, points scale with the zoom factor and the display DPI,
+    /// so the popup keeps the same proportions on any screen.
+    pub fn min_popup_width(mut self, min_popup_width: f32) -> Self {
+        self.min_popup_width = min_popup_width;
+        self
+    }
+
+    /// Limit the height of the popup, scrolling the options beyond it.
+    ///
+    /// Defaults to [`Spacing::combo_height`](egui::style::Spacing::combo_height).
+    pub fn max_popup_height(mut self, max_popup_height: f32) -> Self {
+        self.max_popup_height = Some(max_popup_height);
+        self
+    }
+
+    /// Set the vertical spacing between the rows of the popup.
+    ///
+    /// Defaults to the vertical [`item_spacing`](egui::style::Spacing::item_spacing)
+    /// of the style, or zero if [`dense`](Self::dense).
+    pub fn row_spacing(mut self, row_spacing: f32) -> Self {
+        self.popup_style.row_spacing = Some(row_spacing);
+        self.overridden.set(Setting::RowSpacing);
+        self
+    }
+
+    /// Display the rows of the popup compactly,
+    /// without vertical padding or spacing, to fit more rows in the popup.
+    ///
+    /// Rows are as tall as their text instead of the
+    /// [`interact_size`](egui::style::Spacing::interact_size) of the style.
+    /// A [`row_spacing`](Self::row_spacing) is still applied.
+    pub fn dense(mut self, dense: bool) -> Self {
+        self.popup_style.dense = dense;
+        self.overridden.set(Setting::Dense);
+        self
+    }
+
+    /// Set how options wider than [`max_popup_width`](Self::max_popup_width)
+    /// or the screen are displayed.
+    pub fn popup_overflow(mut self, popup_overflow: PopupOverflow) -> Self {
+        self.popup_overflow = popup_overflow;
+        self
+    }
+
+    /// Set where the popup opens relative to the editor.
+    ///
+    /// Use [`PopupPlacement::AboveOrBelow`] for combo boxes near the bottom of the window,
+    /// so that the popup opens above the editor instead of being clipped.
+    pub fn popup_placement(mut self, popup_placement: PopupPlacement) -> Self {
+        self.popup_placement = popup_placement;
+        self
+    }
+
+    /// Allow renaming options inline in the popup.
+    ///
+    /// Pressing F2 or clicking the pencil icon on the cursor row
+    /// switches the row into a text editor prefilled with
+    /// [`ValueOption::editable_text`].
+    /// Pressing Enter calls `on_rename` with the index of the option in the `options` iterator
+    /// and the new label; pressing Escape cancels renaming.
+    ///
+    /// The combo box does not modify the options itself;
+    /// the app should apply the rename to its option source.
+    pub fn on_rename(mut self, on_rename: impl FnMut(usize, String) + 'a) -> Self {
+        self.on_rename = Some(Box::new(on_rename));
+        self
+    }
+
+    /// Call `on_select` with the index of the option in the `options` iterator
+    /// whenever the user selects an option.
+    ///
+    /// The value only carries what [`ValueOption::into_value`] converts,
+    /// so this allows the app to look up the original record of the selected option.
+    ///
+    /// # Example
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// use egui_editable_combobox::EditableComboBox;
+    ///
+    /// struct User {
+    ///     id:   u64,
+    ///     name: String,
+    /// }
+    ///
+    /// # let users: Vec<User> = Vec::new();
+    /// let mut name = String::new();
+    /// let mut selected_id = None;
+    /// EditableComboBox::new("user").on_select(|index| selected_id = Some(users[index].id)).show(
+    ///     ui,
+    ///     &mut name,
+    ///     users.iter().map(|user| user.name.as_str()),
+    /// );
+    /// # });
+    /// ```
+    pub fn on_select(self, mut on_select: impl FnMut(usize) + 'a) -> Self {
+        self.on_select_with_modifiers(move |index, _| on_select(index))
+    }
+
+    /// Like [`on_select`](Self::on_select),
+    /// but also passes the modifier keys held when the option was clicked or Enter was pressed,
+    /// e.g. to open the selected document in a new tab on Ctrl+click.
+    ///
+    /// Replaces any callback set by [`on_select`](Self::on_select).
+    ///
+    /// # Example
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// use egui_editable_combobox::EditableComboBox;
+    ///
+    /// # let mut value = String::new();
+    /// # let documents = ["README.md", "CHANGELOG.md"];
+    /// let mut open_in_new_tab = None;
+    /// EditableComboBox::new("document")
+    ///     .on_select_with_modifiers(|index, modifiers| {
+    ///         if modifiers.command {
+    ///             open_in_new_tab = Some(index);
+    ///         }
+    ///     })
+    ///     .show(ui, &mut value, documents);
+    /// # });
+    /// ```
+    pub fn on_select_with_modifiers(
+        mut self,
+        on_select: impl FnMut(usize, egui::Modifiers) + 'a,
+    ) -> Self {
+        self.on_select = Some(Box::new(on_select));
+        self
+    }
+
+    /// Call `on_commit_feedback` whenever the user selects an option or commits free text,
+    /// so that selections can trigger feedback such as a click sound or a haptic pulse
+    /// without the app diffing responses.
+    ///
+    /// egui itself has no sound or haptic output;
+    /// the hook receives the [`egui::Context`] so that the app can reach its own backend,
+    /// e.g. through [`egui::Context::data_mut`],
+    /// or emit [`OutputEvent`](egui::output::OutputEvent)s through [`egui::Context::output_mut`]
+    /// for integrations that forward them to the platform.
+    ///
+    /// # Example
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// use egui_editable_combobox::EditableComboBox;
+    ///
+    /// # let mut value = String::new();
+    /// # let options = ["Alpha", "Beta"];
+    /// let mut clicks = 0;
+    /// EditableComboBox::new("sound")
+    ///     .on_commit_feedback(|_ctx| clicks += 1) // e.g. `audio.play(CLICK)`
+    ///     .show(ui, &mut value, options);
+    /// # });
+    /// ```
+    pub fn on_commit_feedback(
+        mut self,
+        on_commit_feedback: impl FnMut(&egui::Context) + 'a,
+    ) -> Self {
+        self.on_commit_feedback = Some(Box::new(on_commit_feedback));
+        self
+    }
+
+    /// Call `guard` before the user selects an option or commits [free text](Self::allow_free_text),
+    /// allowing the app to veto the selection, e.g. if the user lacks the permission to pick it.
+    ///
+    /// `guard` is called with the index of the option in the `options` iterator,
+    /// or `None` for free text, and the value it would be converted into.
+    /// The value is passed as `&dyn Value` since the builder is not tied to a value type;
+    /// like [`on_select`](Self::on_select), the index can be used to look up the original record.
+    /// If the selection is denied, the value is left unchanged,
+    /// the popup stays open and the reason is displayed above the options
+    /// until the user edits the text or selects another option.
+    ///
+    /// # Example
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// use egui_editable_combobox::{CommitGuard, EditableComboBox};
+    ///
+    /// # let mut role = String::from("viewer");
+    /// let is_admin = false;
+    /// EditableComboBox::new("role")
+    ///     .on_before_commit(|_index, candidate| {
+    ///         if candidate.to_editable() == "admin" && !is_admin {
+    ///             CommitGuard::Deny("Only admins can grant the admin role".into())
+    ///         } else {
+    ///             CommitGuard::Allow
+    ///         }
+    ///     })
+    ///     .show(ui, &mut role, ["viewer", "editor", "admin"]);
+    /// # });
+    /// ```
+    pub fn on_before_commit(
+        mut self,
+        guard: impl FnMut(Option<usize>, &dyn Value) -> CommitGuard + 'a,
+    ) -> Self {
+        self.before_commit = Some(Box::new(guard));
+        self
+    }
+
+    /// Announce the selected value to screen readers whenever the user selects an option
+    /// or commits free text.
+    ///
+    /// `message` is called with the [`Value::to_editable`] text of the new value,
+    /// returning the sentence to announce, so that the app can localize it.
+    /// The sentence is emitted as an [`OutputEvent::ValueChanged`](egui::output::OutputEvent::ValueChanged),
+    /// which is read out by egui's built-in screen reader
+    /// and forwarded by integrations with accessibility support.
+    ///
+    /// # Example
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// use egui_editable_combobox::EditableComboBox;
+    ///
+    /// # let mut value = String::new();
+    /// # let continents = ["Africa", "Oceania"];
+    /// EditableComboBox::new("continent")
+    ///     .announce_commit(|value| format!("Continent set to {value}"))
+    ///     .show(ui, &mut value, continents);
+    /// # });
+    /// ```
+    pub fn announce_commit(mut self, message: impl Fn(&str) -> String + 'a) -> Self {
+        self.announcement = Some(Box::new(message));
+        self
+    }
+
+    /// Lock a non-editable prefix in front of the text editor.
+    ///
+    /// The prefix is rendered before the editor but cannot be deleted by the user,
+    /// e.g. the namespace in a picker of `namespace/item` identifiers.
+    /// Options are filtered and converted with the prefix prepended to the user text,
+    /// and the prefix is stripped from [`Value::to_editable`] when populating the editor.
+    pub fn locked_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.locked_prefix = Some(prefix.into());
+        self
+    }
+
+    /// Set which part of the text is used to filter and complete options.
+    ///
+    /// See [`CompletionScope`] for details.
+    pub fn completion_scope(mut self, scope: CompletionScope) -> Self {
+        self.completion_scope = scope;
+        self
+    }
+
+    /// Override how the typed text is laid out in the editor,
+    /// e.g. to colorize valid and invalid parts of the text.
+    ///
+    /// This is passed to [`TextEdit::layouter`];
+    /// the combo box continues to own the text buffer.
+    ///
+    /// # Example
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// use egui_editable_combobox::EditableComboBox;
+    ///
+    /// let mut value = String::from("apple");
+    /// let mut layouter = |ui: &egui::Ui, buf: &dyn egui::TextBuffer, wrap_width: f32| {
+    ///     let color = if buf.as_str().is_ascii() {
+    ///         ui.visuals().text_color()
+    ///     } else {
+    ///         ui.visuals().error_fg_color
+    ///     };
+    ///     let mut job = egui::text::LayoutJob::simple_singleline(
+    ///         buf.as_str().to_owned(),
+    ///         egui::FontId::default(),
+    ///         color,
+    ///     );
+    ///     job.wrap.max_width = wrap_width;
+    ///     ui.fonts_mut(|fonts| fonts.layout_job(job))
+    /// };
+    ///
+    /// EditableComboBox::new("fruit").layouter(&mut layouter).show(
+    ///     ui,
+    ///     &mut value,
+    ///     ["apple", "banana"],
+    /// );
+    /// # });
+    /// ```
+    pub fn layouter(
+        mut self,
+        layouter: &'a mut dyn FnMut(&egui::Ui, &dyn TextBuffer, f32) -> Arc<egui::Galley>,
+    ) -> Self {
+        self.layouter = Some(layouter);
+        self
+    }
+
+    /// Add a secondary action button at the trailing edge of option rows.
+    ///
+    /// `icon` is called with the index of each displayed option in the `options` iterator,
+    /// returning the button text (e.g. `"ℹ"`) or `None` to omit the button for that row.
+    /// Clicking the button, or pressing Alt+Enter on the cursor row,
+    /// calls `on_row_action` with the option index without selecting the option.
+    /// The popup stays open with focus in the editor.
+    pub fn row_action(
+        mut self,
+        icon: impl Fn(usize) -> Option<String> + 'a,
+        on_row_action: impl FnMut(usize) + 'a,
+    ) -> Self {
+        self.row_action =
+            Some(RowAction { icon: Box::new(icon), on_action: Box::new(on_row_action) });
+        self
+    }
+
+    /// The ID of the dropdown popup.
+    ///
+    /// The popup is registered in egui memory while it is shown,
+    /// so this ID can be passed to [`Popup::is_id_open`] to check whether the dropdown is open,
+    /// or to [`Popup::close_id`] to close it from outside the widget.
+    #[must_use]
+    pub fn popup_id(&self) -> egui::Id { Ids::Popup.id(self.id_salt) }
+
+    /// The ID of the scroll area inside the dropdown popup.
+    ///
+    /// This can be used with [`egui::scroll_area::State::load`] and
+    /// [`store`](egui::scroll_area::State::store) to inspect or change the scroll offset.
+    #[must_use]
+    pub fn scroll_id(&self) -> egui::Id {
+        // `ScrollArea` derives its ID from the ID of the parent `Ui`,
+        // which is fixed through `UiBuilder::id` when the popup is shown.
+        egui::Id::new(Ids::ScrollScope.id(self.id_salt)).with(egui::Id::new(Ids::Scroll))
+    }
+
+    /// Filter at most `budget` options per frame.
+    ///
+    /// A pathologically large or slow option source could otherwise freeze the UI thread.
+    /// With a budget, the popup displays the options matched so far
+    /// with a "Still filtering…" row, and filtering continues in the next frame.
+    ///
+    /// The filter results are kept while the user text is unchanged,
+    /// so `options` should yield the same options in the same order every frame;
+    /// options appended to the end are filtered as they appear.
+    pub fn filter_budget(mut self, budget: usize) -> Self {
+        self.filter_budget = Some(budget.max(1));
+        self
+    }
+
+    /// Set the separator between the values of [`show_multi`](Self::show_multi)
+    /// copied to and pasted from the clipboard. Defaults to `", "`.
+    ///
+    /// Pasted text is split at the separator without its surrounding whitespace
+    /// and at line breaks, so that lists copied from other apps can be pasted too.
+    pub fn multi_separator(mut self, separator: impl Into<String>) -> Self {
+        self.multi_separator = separator.into();
+        self
+    }
+
+    /// Display "Select all matches" and "Clear selection" buttons
+    /// at the bottom of the popup of [`show_multi`](Self::show_multi).
+    ///
+    /// Select all adds every option matching the typed text,
+    /// including the rows scrolled out of view,
+    /// and is disabled until [budgeted filtering](Self::filter_budget) has finished.
+    /// If more than `confirm_above` options match, the user is asked to confirm first.
+    /// Clear selection removes all values.
+    pub fn select_all_footer(mut self, confirm_above: usize) -> Self {
+        self.select_all_footer = Some(confirm_above);
+        self
+    }
+
+    /// Remember the last `count` values committed in this combo box,
+    /// and list their options in a "Recent" section at the top of the popup
+    /// while the filter text is empty, most recent first.
+    ///
+    /// The values are read from the [selection history](Self::selection_history),
+    /// so they are restored across app restarts if egui persistence is enabled,
+    /// and at most 50 are listed.
+    /// Recent values are matched to options by their [`editable_text`](ValueOption::editable_text).
+    pub fn remember_recent(mut self, count: usize) -> Self {
+        self.remember_recent = count;
+        self
+    }
+
+    /// Add the values committed in this combo box to a [`SuggestionPool`]
+    /// shared with other combo boxes, which may list them as options.
+    pub fn suggestion_pool(mut self, pool: SuggestionPool) -> Self {
+        self.suggestion_pool = Some(pool);
+        self
+    }
+
+    /// List the [candidates](AutofillHint::set_candidates) supplied for `hint`
+    /// after the options, skipping those equal to the editable text of an option
+    /// and those not parsed by [`Value::try_from_editable`].
+    pub fn autofill(mut self, hint: AutofillHint) -> Self {
+        self.autofill = Some(hint);
+        self
+    }
+
+    /// Set how the typed text is matched against the [editable text](ValueOption::editable_text)
+    /// of the options, instead of the filtering of the options themselves.
+    ///
+    /// Options without an editable text, such as [`CustomOption::Custom`],
+    /// are filtered as usual, and options matching beyond their editable text,
+    /// such as by the keywords of [`Keyworded`], still match.
+    /// The options keep their order and [separators](with_separators).
+    /// This replaces the regex filter of the `regex` feature if set before.
+    ///
+    /// # Example
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// use egui_editable_combobox::{EditableComboBox, MatchMode};
+    ///
+    /// let mut function = String::new();
+    /// EditableComboBox::new("function").match_mode(MatchMode::PrefixOnly).show(
+    ///     ui,
+    ///     &mut function,
+    ///     ["print", "println", "sprintf"],
+    /// );
+    /// # });
+    /// ```
+    pub fn match_mode(mut self, mode: MatchMode) -> Self {
+        self.matcher = Some(Box::new(mode));
+        self
+    }
+
+    /// List the options matching the typed text as a fuzzy subsequence,
+    /// as matched by [`SubsequenceMatcher`], if no option matches it otherwise,
+    /// with a "Showing fuzzy matches" note at the top of the popup.
+    ///
+    /// Only options with an [editable text](ValueOption::editable_text) are fuzzy matched.
+    /// Whether the fallback is displayed is reported in [`ShowOutput::match_stats`].
+    /// Disabled by default.
+    pub fn fuzzy_fallback(mut self, fuzzy_fallback: bool) -> Self {
+        self.interaction.fuzzy_fallback = fuzzy_fallback;
+        self.overridden.set(Setting::FuzzyFallback);
+        self
+    }
+
+    /// Interpret the user text as a case-insensitive regular expression
+    /// matching the editable text of the options, as described in [`RegexMatcher`].
+    ///
+    /// Options without an editable text, such as [`CustomOption::Custom`],
+    /// are filtered as usual.
+    #[cfg(feature = "regex")]
+    pub fn regex_filter(mut self, regex_filter: bool) -> Self {
+        self.matcher = regex_filter.then(|| Box::new(RegexMatcher::default()) as Box<dyn Matcher>);
+        self
+    }
+
+    /// Set the visuals of the rows in the popup.
+    ///
+    /// Overrides the [`ComboBoxDefaults`] registered on the context.
+    pub fn popup_style(mut self, popup_style: PopupStyle) -> Self {
+        self.popup_style = popup_style;
+        self.overridden.popup_style = true;
+        self
+    }
+
+    /// Append the inputs and state transitions of this combo box to `recording`
+    /// every time it is shown, for reproducing bugs.
+    ///
+    /// See [`Recording`] for details.
+    pub fn record(mut self, recording: &'a mut Recording) -> Self {
+        self.recording = Some(recording);
+        self
+    }
+
+    /// Keep the typed text, the popup cursor and the history in `store`
+    /// instead of egui memory.
+    ///
+    /// See [`StateStore`] for details.
+    pub fn state_store(mut self, store: &'a dyn StateStore) -> Self {
+        self.state_store = Some(store);
+        self
+    }
 
     /// Display the combo box as a singleline text editor in the given UI,
     /// and display a dropdown popup with the given options when focused.
@@ -60,253 +1163,2306 @@ impl EditableComboBox {
         V: Value,
         Opt: ValueOption<V>,
     {
-        let hint = value.to_editable();
-        let mut text = load_text_buf(ui.ctx(), self.id_salt, value);
-        let mut text_resp = TextEdit::singleline(&mut text).hint_text(&hint).show(ui).response;
+        self.show_with_output(ui, value, options).response
+    }
 
-        if !text_resp.has_focus() && !text_resp.lost_focus() {
-            // Check that text buffer is consistent with the given value
-            // when the previous frame was not focusing on the editor.
+    /// Same as [`show`](Self::show),
+    /// but also returns the state of the popup and how the value was changed in this frame.
+    pub fn show_with_output<V, Opt>(
+        self,
+        ui: &mut egui::Ui,
+        value: &mut V,
+        options: impl IntoIterator<Item = Opt>,
+    ) -> ShowOutput
+    where
+        V: Value,
+        Opt: ValueOption<V>,
+    {
+        self.show_polled(ui, value, |_| Poll::Ready(options))
+    }
+
+    /// Same as [`show_with_output`](Self::show_with_output),
+    /// but the options are only requested with the filter text when the popup is displayed,
+    /// and may still be pending.
+    pub(crate) fn show_polled<V, Opt, I>(
+        mut self,
+        ui: &mut egui::Ui,
+        value: &mut V,
+        options: impl FnOnce(&str) -> Poll<I>,
+    ) -> ShowOutput
+    where
+        V: Value,
+        Opt: ValueOption<V>,
+        I: IntoIterator<Item = Opt>,
+    {
+        if !self.interactive {
+            self.interactive = true;
+            return ui.add_enabled_ui(false, |ui| self.show_polled(ui, value, options)).inner;
+        }
+        self.load_defaults(ui.ctx());
+        if !ui.is_enabled() {
+            self.stop_editing(ui.ctx());
+        }
+        let passed = self.interaction.shortcuts.hide_pass_through(ui.ctx());
+        let candidates = self.autofill.map(|hint| hint.candidates(ui.ctx())).unwrap_or_default();
+        let options = |text: &str| options(text).map(|options| merge_autofill(options, candidates));
+        let output = self.show_with_input(ui, value, options);
+        ui.ctx().input_mut(|input| input.events.extend(passed));
+        output
+    }
+
+    /// Shows the combo box with the input of this frame,
+    /// from which the pass-through shortcuts have been removed.
+    fn show_with_input<V, Opt, I>(
+        mut self,
+        ui: &mut egui::Ui,
+        value: &mut V,
+        options: impl FnOnce(&str) -> Poll<I>,
+    ) -> ShowOutput
+    where
+        V: Value,
+        Opt: ValueOption<V>,
+        I: IntoIterator<Item = Opt>,
+    {
+        let mut reselected = None;
+        let full_hint = value.to_editable();
+        let prefix = self.locked_prefix.take().unwrap_or_default();
+        let prefix = prefix.as_str();
+        // Only the part after the locked prefix is editable.
+        let hint = full_hint.strip_prefix(prefix).unwrap_or(&full_hint);
+        let mut text = load_text_buf::<B>(ui.ctx(), self.state_store, self.id_salt, hint);
+        let placeholder = self.unset_placeholder.take().filter(|_| full_hint.is_empty());
+        let origin = ui.cursor().min;
+        store_replay_origin(ui.ctx(), self.id_salt, origin);
+        let editor = self.show_editor(ui, &mut text, hint, placeholder.as_deref(), prefix, value);
+        let (mut text_resp, caret, clicked) = (editor.response, editor.caret, editor.clicked);
+        let mut change = editor.cleared.then_some(ValueChange::Cleared);
+        self.record_frame(ui, origin, &text_resp, text.as_str());
+        let completing = self.completion_scope != CompletionScope::Whole;
 
-            if text != hint {
-                text = hint;
+        let rename = load_rename(ui.ctx(), self.id_salt);
+        let renaming = matches!(rename, Some(RenameState::Editing { .. }));
+        let reopened = self.reopen_dismissed(ui, &text_resp, &mut text, clicked);
+        let opened =
+            self.sync_text_buf(ui, &text_resp, &mut text, hint, rename.as_ref(), completing)
+                || reopened;
+
+        let cancelled = !renaming && self.cancel_editing(ui, &text_resp, &mut text, hint);
+        if text_resp.changed() {
+            store_denial(ui.ctx(), self.id_salt, None);
+        }
 
-                ui.ctx().request_repaint(); // repaint to apply text changes
+        if completing && text_resp.changed() {
+            // The typed text itself is the value when completing.
+            if let Some(new_value) = V::try_from_editable(&[prefix, text.as_str()].concat()) {
+                *value = new_value;
+                change = Some(ValueChange::Typed);
             }
-        } else if text_resp.gained_focus() {
-            text.clear();
-            ui.ctx().request_repaint(); // repaint to apply text changes
         }
 
-        if text_resp.has_focus() || text_resp.lost_focus() {
-            let changed = self.show_options(ui, &text_resp, value, options, &text);
-            if changed {
+        let mut shown = ShownOptions::default();
+        let dismissed = load_dismissed(ui.ctx(), self.id_salt);
+        if !cancelled && (text_resp.has_focus() || text_resp.lost_focus() || renaming) {
+            if text_resp.has_focus()
+                && self.navigate_history(ui.ctx(), text_resp.id, &mut text, prefix)
+            {
+                ui.ctx().request_discard("EditableComboBox history navigated");
+            }
+
+            let completion_range =
+                caret.and_then(|caret| self.completion_scope.filter_range(text.as_str(), caret));
+            let unedited = (self.interaction.focus_text == FocusText::Selectable).then_some(hint);
+            let filter_text =
+                filter_text(text.as_str(), prefix, completion_range.clone(), unedited);
+
+            let too_short = filter_text.chars().count() < self.interaction.min_chars && !renaming;
+            shown = if too_short || dismissed {
+                // Not enough text typed to display the popup yet, or the popup was dismissed.
+                self.forget_popup_state(ui.ctx());
+                ShownOptions::default()
+            } else {
+                let selection = placeholder.is_none().then_some(&*value);
+                self.show_options(ui, &text_resp, selection, options, &filter_text, opened)
+            };
+            let ghost = shown.ghost.take().filter(|_| caret == Some(text.as_str().chars().count()));
+            paint_ghost_text(ui, editor.text_end, editor.rtl, ghost);
+            if let Some(completion) = shown.completion.take() {
+                let range = completion_range.clone();
+                complete_in_range(ui.ctx(), text_resp.id, &mut text, range, prefix, &completion);
+            }
+            if let Some((source_index, option, selected_by, equals)) = shown.selected.take() {
+                let equal = equals && completion_range.is_none();
+                let (candidate, completed) =
+                    commit_candidate(option, text.as_str(), prefix, &filter_text, completion_range);
+                let dyn_candidate = candidate.as_ref().map(|candidate| candidate as &dyn Value);
+                let allowed =
+                    self.allow_commit(ui.ctx(), text_resp.id, Some(source_index), dyn_candidate);
+                if let Some(candidate) = candidate.filter(|_| allowed) {
+                    if let Some((range, completion)) = completed {
+                        complete_text(ui.ctx(), text_resp.id, &mut text, range, &completion);
+                    }
+                    if equal {
+                        // Selecting the current value again does not change it.
+                        reselected = Some(selected_by);
+                    } else {
+                        *value = candidate;
+                        change = Some(selected_by);
+                        text_resp.mark_changed();
+                    }
+                    self.on_committed(ui.ctx(), value, Some(source_index));
+                    self.focus_after_commit(ui.ctx(), text_resp.id, &mut text, value, prefix);
+                }
+            }
+            if shown.cursor.is_none()
+                && text_resp.lost_focus()
+                && self.commit_free_text(&text_resp, text.as_str(), value, prefix, &full_hint)
+            {
+                change = Some(ValueChange::FreeText);
                 text_resp.mark_changed();
             }
         } else {
             self.forget_popup_state(ui.ctx());
         }
 
-        store_text_buf(ui.ctx(), self.id_salt, text);
+        store_text_buf(ui.ctx(), self.state_store, self.id_salt, text);
+
+        ShowOutput {
+            // The popup closes in the next frame after the editor loses focus.
+            popup_open: (text_resp.has_focus() || renaming)
+                && Popup::is_id_open(ui.ctx(), self.popup_id()),
+            response: text_resp,
+            filtered_count: shown.filtered_count,
+            cursor: shown.cursor,
+            change,
+            reselected,
+            match_stats: shown.match_stats,
+        }
+    }
+
+    /// Commits the typed text in the editor of `text_resp` as the value
+    /// if [free text](Self::allow_free_text) is allowed and the commit is not vetoed.
+    ///
+    /// Returns whether the value was changed.
+    fn commit_free_text<V: Value>(
+        &mut self,
+        text_resp: &egui::Response,
+        text: &str,
+        value: &mut V,
+        prefix: &str,
+        full_hint: &str,
+    ) -> bool {
+        if !self.interaction.allow_free_text {
+            return false;
+        }
+        let Some(free) = free_text(text, prefix, full_hint) else { return false };
+        if self.free_text_error(prefix, text).is_some() {
+            return false;
+        }
+        if !self.allow_commit(&text_resp.ctx, text_resp.id, None, Some(&free)) {
+            return false;
+        }
+        *value = free;
+        self.on_committed(&text_resp.ctx, value, None);
+        true
+    }
 
-        text_resp
+    /// The error returned by the [`validate`](Self::validate) hook for the typed text, if any.
+    fn free_text_error(&self, prefix: &str, text: &str) -> Option<String> {
+        let validate = self
+            .validate
+            .as_ref()
+            .filter(|_| self.interaction.allow_free_text && !text.is_empty())?;
+        validate(&[prefix, text].concat()).err()
     }
 
-    fn show_options<V, Opt>(
+    /// Keeps the text buffer consistent with the value while the editor is not focused,
+    /// and clears it when the popup is opened if configured.
+    ///
+    /// Returns whether the popup has just been opened by focusing the editor.
+    fn sync_text_buf(
         &self,
-        ui: &mut egui::Ui,
+        ui: &egui::Ui,
         text_resp: &egui::Response,
-        selection: &mut V,
+        text: &mut B,
+        hint: &str,
+        rename: Option<&RenameState>,
+        completing: bool,
+    ) -> bool {
+        let renaming = matches!(rename, Some(RenameState::Editing { .. }));
+        // Focus returns to the main editor after renaming an option,
+        // which should not be treated as reopening the popup.
+        // The same applies when focus returns after a selection is denied or kept open.
+        let returning =
+            matches!(rename, Some(RenameState::Returning)) || load_refocus(ui.ctx(), self.id_salt);
+        let opened = text_resp.gained_focus() && !returning;
+        // When completing, the text is the value itself and should not be cleared on focus.
+        let clear_text = opened && !completing && self.clears_on_focus();
+
+        if !text_resp.has_focus() && !text_resp.lost_focus() && !renaming {
+            // Check that text buffer is consistent with the given value
+            // when the previous frame was not focusing on the editor.
+
+            if text.as_str() != hint {
+                text.replace_with(hint);
+
+                // The editor has already been laid out with the stale text in this pass.
+                // Discard the pass instead of painting the stale text and waking another frame.
+                ui.ctx().request_discard("EditableComboBox text buffer out of sync");
+            }
+        } else if clear_text {
+            // An empty text has nothing stale to discard.
+            if !text.as_str().is_empty() {
+                text.clear();
+                ui.ctx().request_discard("EditableComboBox text buffer cleared on focus");
+            }
+        } else if returning && text_resp.gained_focus() {
+            store_rename(ui.ctx(), self.id_salt, None);
+            store_refocus(ui.ctx(), self.id_salt, false);
+        }
+        opened
+    }
+
+    /// Filters the options by the user text.
+    ///
+    /// Also returns the cursor position of the option equal to the current value
+    /// if the popup has just been opened,
+    /// and whether multiple options claimed to be equal to the current value.
+    /// Only the first of them is marked as selected.
+    ///
+    /// With a [`filter_budget`](Self::filter_budget),
+    /// the progress is kept in egui memory to continue in the next frame,
+    /// and the filter results of the options already processed are reused.
+    fn filter_options<V, Opt>(
+        &self,
+        ctx: &egui::Context,
         options: impl IntoIterator<Item = Opt>,
+        selection: Option<&V>,
         text: &str,
-    ) -> bool
+        opened: bool,
+    ) -> FilteredOptions<Opt>
     where
-        V: Value,
         Opt: ValueOption<V>,
     {
+        let mut progress = self
+            .filter_budget
+            .and_then(|_| load_filter_progress(ctx, self.id_salt))
+            .filter(|progress| !opened && progress.text == text)
+            .unwrap_or_else(|| FilterProgress {
+                text: text.into(),
+                seek_selection: opened,
+                ..Default::default()
+            });
+        let budget_end =
+            progress.processed.saturating_add(self.filter_budget.unwrap_or(usize::MAX));
+
         let mut filtered = Vec::new();
+        let mut fuzzy = Vec::new();
         let mut default_cursor_pos = None;
-        let mut had_exact = false;
+        let mut pending = false;
+        let mut prev_matches = mem::take(&mut progress.matches).into_iter().peekable();
+        let mut prev_fuzzy = mem::take(&mut progress.fuzzy).into_iter().peekable();
         for (source_index, option) in options.into_iter().enumerate() {
-            let equals = option.equals_value(selection, text);
+            if source_index < progress.processed {
+                // Filtered in an earlier frame.
+                if let Some((_, equals, relevance)) =
+                    prev_matches.next_if(|&(index, ..)| index == source_index)
+                {
+                    progress.matches.push((source_index, equals, relevance));
+                    let separator = option.is_separator();
+                    filtered.push(DisplayedOption {
+                        equals,
+                        relevance,
+                        ..DisplayedOption::new(source_index, option, separator)
+                    });
+                } else if let Some((_, equals, relevance)) =
+                    prev_fuzzy.next_if(|&(index, ..)| index == source_index)
+                {
+                    progress.fuzzy.push((source_index, equals, relevance));
+                    let option = DisplayedOption::new(source_index, option, false);
+                    fuzzy.push(DisplayedOption { equals, relevance, ..option });
+                }
+                continue;
+            }
+            if source_index >= budget_end {
+                pending = true;
+                break;
+            }
+            progress.processed = source_index + 1;
+
+            if option.is_separator() {
+                if separator_fits(&filtered) {
+                    progress.matches.push((source_index, false, Relevance::Partial));
+                    filtered.push(DisplayedOption::new(source_index, option, true));
+                }
+                continue;
+            }
+
+            let FilteredOption { matches, equals, relevance } = progress.pass.filter(
+                &option,
+                text,
+                selection,
+                self.equality,
+                self.matcher.as_deref(),
+            );
 
             // Set default cursor position to the option matching the current value
             // when the popup is opened initially.
-            if text_resp.gained_focus() && equals {
+            if progress.seek_selection && equals {
                 default_cursor_pos = Some(CursorPos { source_index });
+                progress.seek_selection = false;
             }
 
-            let filter_result = option
-                .filter_by_text(text, FilterState { prev_matches: filtered.len(), had_exact });
-            match filter_result {
-                FilterResult::Partial => {
-                    filtered.push(DisplayedOption { source_index, option, equals })
-                }
-                FilterResult::Exact => {
-                    filtered.push(DisplayedOption { source_index, option, equals });
-                    had_exact = true;
+            let pinned = self.interaction.pin_selected && equals;
+            if !matches && !pinned {
+                if let Some(score) = progress.pass.filter_fuzzy(&option, text) {
+                    let relevance = Relevance::Scored(score);
+                    progress.fuzzy.push((source_index, equals, relevance));
+                    let option = DisplayedOption::new(source_index, option, false);
+                    fuzzy.push(DisplayedOption { equals, relevance, ..option });
                 }
-                FilterResult::None => {}
+                continue;
             }
+            progress.matches.push((source_index, equals, relevance));
+            filtered.push(DisplayedOption {
+                equals,
+                relevance,
+                ..DisplayedOption::new(source_index, option, false)
+            });
         }
-
-        let mut cursor_pos = default_cursor_pos
-            // Try to load the previous cursor position.
-            .or_else(|| load_cursor_pos(ui.ctx(), self.id_salt))
-            // If the previous selected value is no longer an available option,
-            // reset cursor position to the first option.
-            .unwrap_or(CursorPos { source_index: 0 });
-
-        move_cursor_pos(ui.ctx(), &mut cursor_pos, &filtered);
-        store_cursor_pos(ui.ctx(), self.id_salt, cursor_pos.clone());
-
-        // Display cursor position as the smallest index greater than or equal to the current
-        // cursor position, or clamp to the last one (if any) if beyond the end.
-        let mut cursor_filtered_index =
-            filtered.partition_point(|d| d.source_index < cursor_pos.source_index);
-        if cursor_filtered_index >= filtered.len()
-            && let Some(prev) = filtered.len().checked_sub(1)
-        {
-            cursor_filtered_index = prev;
+        let stats = self.fall_back_to_fuzzy(&progress.pass, &mut filtered, fuzzy, pending);
+        let sorted = progress.pass.scored || stats.fuzzy_fallback;
+        let arrangement = Arrangement {
+            reverse_order: self.interaction.reverse_order,
+            pin_selected: self.interaction.pin_selected,
+            sorted,
+        };
+        arrangement.arrange(&mut filtered);
+        if text.is_empty() && self.remember_recent > 0 {
+            let history = load_selection_history(ctx, self.state_store, self.id_salt);
+            let recent = distinct_texts(&history).into_iter().take(self.remember_recent);
+            move_recent_first(&mut filtered, recent, |option| option.editable_text(""));
         }
 
-        let mut changed = false;
-        Popup::new(
-            Ids::Popup.id(self.id_salt),
-            ui.ctx().clone(),
-            PopupAnchor::ParentRect(text_resp.rect),
-            ui.layer_id(),
-        )
-        .show(|ui| {
-            ScrollArea::vertical()
-                .id_salt(Ids::Scroll)
-                .max_height(ui.spacing().combo_height)
-                .show_rows(
-                    ui,
-                    ui.text_style_height(&TextStyle::Body),
-                    filtered.len(),
-                    |ui, range| {
-                        ui.set_min_width(text_resp.rect.width());
-                        ui.style_mut().wrap_mode = Some(TextWrapMode::Extend);
-                        ui.with_layout(Layout::top_down_justified(Align::Min), |ui| {
-                            for (filtered_index, displayed) in
-                                filtered.into_iter().enumerate().take(range.end).skip(range.start)
-                            {
-                                let mut button = Button::selectable(
-                                    displayed.equals,
-                                    displayed.option.display(text),
-                                );
-                                let is_cursor = cursor_filtered_index == filtered_index;
-                                if is_cursor {
-                                    button = button
-                                        .frame_when_inactive(true)
-                                        .stroke(ui.visuals().widgets.hovered.bg_stroke)
-                                        .fill(ui.visuals().widgets.hovered.weak_bg_fill);
-                                }
-                                let select_resp = ui.add(button);
-                                if select_resp.clicked()
-                                    || (is_cursor
-                                        && ui.input(|input| input.key_pressed(egui::Key::Enter)))
-                                {
-                                    *selection = displayed.option.into_value(text);
-                                    changed = true;
-                                }
-                            }
-                        });
-                    },
-                );
-        });
+        let ambiguous = progress.pass.claimed_equal > 1;
+        if self.filter_budget.is_some() {
+            store_filter_progress(ctx, self.id_salt, progress);
+        }
 
-        changed
+        FilteredOptions { options: filtered, default_cursor_pos, ambiguous, pending, sorted, stats }
     }
 
-    fn forget_popup_state(&self, ctx: &egui::Context) {
-        ctx.memory_mut(|mem| {
-            // Cursor position is no longer relevant once the popup is closed.
-            // Upon reopening, the cursor position will be recalculated to match the selected value.
-            mem.data.remove::<CursorPos>(Ids::CursorPos.id(self.id_salt));
-        });
+    /// Appends the `fuzzy` matches to the `filtered` options
+    /// if no option matched in the `pass` and the [fallback](Self::fuzzy_fallback) is enabled.
+    ///
+    /// The fallback waits until all options are filtered, i.e. nothing is `pending`,
+    /// so that it does not flash before the options matching later.
+    fn fall_back_to_fuzzy<Opt>(
+        &self,
+        pass: &FilterPass,
+        filtered: &mut Vec<DisplayedOption<Opt>>,
+        fuzzy: Vec<DisplayedOption<Opt>>,
+        pending: bool,
+    ) -> MatchStats {
+        if pass.matches > 0 {
+            return MatchStats { matches: pass.matches, ..MatchStats::default() };
+        }
+        let fuzzy_fallback = self.interaction.fuzzy_fallback && !pending && !fuzzy.is_empty();
+        if fuzzy_fallback {
+            filtered.extend(fuzzy);
+        }
+        MatchStats { matches: 0, fuzzy_matches: pass.fuzzy_matches, fuzzy_fallback }
     }
-}
 
-fn load_text_buf<V: Value>(ctx: &egui::Context, id_salt: egui::Id, value: &V) -> String {
-    ctx.memory(|mem| mem.data.get_temp::<String>(Ids::TextBuf.id(id_salt)))
-        .unwrap_or_else(|| value.to_editable())
-}
+    /// Displays the main text editor, preceded by the locked prefix if any.
+    ///
+    /// The text, or the hint if the text is empty, is right-aligned
+    /// if it starts with right-to-left script, even in left-to-right UIs.
+    /// The clear button and ghost text then move to the left, the trailing edge of such text,
+    /// while the dropdown arrow stays at the right edge of the widget.
+    ///
+    /// The whole widget is a single click target that focuses the editor,
+    /// so clicking the locked prefix opens the popup like clicking the editor itself.
+    ///
+    /// The `placeholder` is displayed instead of the `hint` if set.
+    ///
+    /// The [clear button](Self::clear_button) is displayed inside the trailing edge of the editor
+    /// unless `value` is already the default, and resets `value` when clicked.
+    fn show_editor<V: Value>(
+        &mut self,
+        ui: &mut egui::Ui,
+        text: &mut B,
+        hint: &str,
+        placeholder: Option<&str>,
+        prefix: &str,
+        value: &mut V,
+    ) -> EditorOutput {
+        // The editor may lose focus to the arrow click before the arrow is shown,
+        // so the popup state of the last frame decides whether the arrow opens or closes it.
+        let was_open = Popup::is_id_open(ui.ctx(), self.popup_id());
 
-fn store_text_buf(ctx: &egui::Context, id_salt: egui::Id, text: String) {
-    ctx.memory_mut(|mem| mem.data.insert_temp::<String>(Ids::TextBuf.id(id_salt), text));
-}
+        let shown_hint = placeholder.unwrap_or(hint);
+        let displayed = if text.as_str().is_empty() { shown_hint } else { text.as_str() };
+        let rtl = starts_with_rtl(displayed);
+        let align = if rtl { Align::RIGHT } else { Align::LEFT };
 
-fn load_cursor_pos(ctx: &egui::Context, id_salt: egui::Id) -> Option<CursorPos> {
-    ctx.memory(|mem| mem.data.get_temp::<CursorPos>(Ids::CursorPos.id(id_salt)))
-}
+        let mut edit = TextEdit::singleline(text)
+            .id(Ids::Editor.id(self.id_salt))
+            .hint_text(shown_hint)
+            .horizontal_align(align);
+        if let Some(layouter) = &mut self.layouter {
+            edit = edit.layouter(&mut **layouter);
+        }
+        let clear_to = self.clear_to.as_deref().filter(|default| *default != value.to_editable());
+        let clear_to = clear_to.and_then(V::try_from_editable);
+        let clear = clear_to.is_some();
+        // The default margin of `TextEdit`.
+        let mut margin = egui::Margin::symmetric(4, 2);
+        if clear {
+            // Keep the text from running under the clear button.
+            #[expect(clippy::cast_possible_truncation)] // icons are small
+            let width = clear_button_width(ui).ceil() as i8;
+            if rtl {
+                margin.left += width;
+            } else {
+                margin.right += width;
+            }
+        }
+        edit = edit.margin(margin);
 
-fn store_cursor_pos(ctx: &egui::Context, id_salt: egui::Id, cursor_pos: CursorPos) {
-    ctx.memory_mut(|mem| mem.data.insert_temp::<CursorPos>(Ids::CursorPos.id(id_salt), cursor_pos));
-}
+        let dropdown_arrow = self.dropdown_arrow;
+        let arrow_width = if dropdown_arrow { dropdown_arrow_width(ui) } else { 0.0 };
+        let desired_width = self.desired_width;
+        let start_x = ui.cursor().min.x;
+        let clear_id = Ids::ClearButton.id(self.id_salt);
+        let show_edit = |ui: &mut egui::Ui| {
+            if let Some(width) = desired_width {
+                // The prefix has been laid out before the editor, and the arrow follows it.
+                let outer = (width - (ui.cursor().min.x - start_x)).min(ui.available_width());
+                edit = edit.desired_width((outer - arrow_width - margin.sum().x).max(0.0));
+            }
+            let output = edit.show(ui);
+            let clear_clicked = clear && show_clear_button(ui, clear_id, output.response.rect, rtl);
+            (output, clear_clicked)
+        };
+        let (output, widget_rect, arrow_clicked, clear_clicked) =
+            if prefix.is_empty() && !dropdown_arrow {
+                let (output, clear_clicked) = show_edit(ui);
+                let rect = output.response.rect;
+                (output, rect, false, clear_clicked)
+            } else {
+                let inner = ui.horizontal(|ui| {
+                    ui.spacing_mut().item_spacing.x = 0.0;
+                    if !prefix.is_empty() {
+                        ui.label(prefix);
+                    }
+                    let (output, clear_clicked) = show_edit(ui);
+                    let arrow_clicked = dropdown_arrow
+                        && show_dropdown_arrow(ui, output.response.rect.height()).clicked();
+                    (output, arrow_clicked, clear_clicked)
+                });
+                let (output, arrow_clicked, clear_clicked) = inner.inner;
+                (output, inner.response.rect, arrow_clicked, clear_clicked)
+            };
 
-struct DisplayedOption<Opt> {
-    source_index: usize,
-    option:       Opt,
-    equals:       bool,
-}
+        // Clicking the clear button does not open the popup.
+        let clicked = !clear_clicked
+            && ui.is_enabled()
+            && ui.rect_contains_pointer(widget_rect)
+            && ui.input(|input| input.pointer.primary_clicked());
+        if arrow_clicked {
+            // The popup is displayed while the editor is focused.
+            if was_open {
+                ui.memory_mut(|mem| mem.surrender_focus(output.response.id));
+            } else {
+                ui.memory_mut(|mem| mem.request_focus(output.response.id));
+            }
+        } else if !output.response.has_focus() && clicked {
+            // A click handled by the editor has already focused it.
+            ui.memory_mut(|mem| mem.request_focus(output.response.id));
+        }
 
-#[derive(Clone)]
-struct CursorPos {
-    source_index: usize,
-}
+        let mut response = output.response;
+        if let Some(error) = self.free_text_error(prefix, text.as_str()) {
+            let visuals = ui.visuals();
+            let stroke =
+                Stroke::new(visuals.widgets.active.bg_stroke.width, visuals.error_fg_color);
+            let radius = visuals.widgets.inactive.corner_radius;
+            ui.painter().rect_stroke(response.rect, radius, stroke, egui::StrokeKind::Inside);
+            response = response.on_hover_text(error);
+        }
+        if clear_clicked && let Some(default) = clear_to {
+            *value = default;
+            // Discard the typed text, which is synced with the new value in the next frame.
+            text.replace_with(hint);
+            ui.memory_mut(|mem| mem.surrender_focus(response.id));
+            response.mark_changed();
+            // The editor has already been painted with the old value in this pass.
+            // The pass is not discarded, so that the change is reported to the caller.
+            ui.ctx().request_repaint();
+        }
 
-fn move_cursor_pos<Opt>(
-    ctx: &egui::Context,
-    cursor_pos: &mut CursorPos,
-    displayed_options: &[DisplayedOption<Opt>],
-) {
-    enum Motion {
-        Home,
-        End,
-        Up,
-        Down,
-    }
-
-    let Some(motion) = ctx.input(|input| {
-        [
-            (Motion::Up, egui::Key::ArrowUp),
-            (Motion::Down, egui::Key::ArrowDown),
-            (Motion::Home, egui::Key::Home),
-            (Motion::End, egui::Key::End),
-        ]
-        .into_iter()
-        .find_map(|(motion, key)| if input.key_pressed(key) { Some(motion) } else { None })
-    }) else {
-        return;
-    };
+        let caret = output.cursor_range.map(|range| range.primary.index);
+        let text_end = if rtl {
+            let start = output.galley_pos.x + output.galley.rect.left();
+            output.text_clip_rect.intersect(Rect::everything_left_of(start))
+        } else {
+            let end = output.galley_pos.x + output.galley.rect.right();
+            output.text_clip_rect.intersect(Rect::everything_right_of(end))
+        };
+        EditorOutput { response, caret, clicked, cleared: clear_clicked, text_end, rtl }
+    }
+
+    fn show_options<V, Opt, I>(
+        &mut self,
+        ui: &mut egui::Ui,
+        text_resp: &egui::Response,
+        selection: Option<&V>,
+        options: impl FnOnce(&str) -> Poll<I>,
+        text: &str,
+        opened: bool,
+    ) -> ShownOptions<Opt>
+    where
+        V: Value,
+        Opt: ValueOption<V>,
+        I: IntoIterator<Item = Opt>,
+    {
+        let (options, loading) = match options(text) {
+            Poll::Ready(options) => (Some(options), false),
+            Poll::Pending => (None, true),
+        };
+        let FilteredOptions {
+            options: filtered,
+            default_cursor_pos,
+            ambiguous,
+            pending,
+            sorted,
+            stats: match_stats,
+        } = self.filter_options(ui.ctx(), options.into_iter().flatten(), selection, text, opened);
+        if cfg!(debug_assertions) && ambiguous {
+            ui.ctx().debug_painter().error(
+                text_resp.rect.right_top(),
+                "Multiple options are equal to the current value",
+            );
+        }
+
+        // Try to load the previous cursor position.
+        let cursor_pos = default_cursor_pos
+            .or_else(|| load_cursor_pos(ui.ctx(), self.state_store, self.id_salt));
+
+        let rename = load_rename(ui.ctx(), self.id_salt);
+        // Keys are handled by the rename editor while renaming.
+        let renaming = matches!(rename, Some(RenameState::Editing { .. }));
+        // Keep polling the options and filtering while the popup stays open,
+        // but not in the frame the editor loses focus and the popup closes.
+        if (pending || loading) && (text_resp.has_focus() || renaming) {
+            ui.ctx().request_repaint();
+        }
+
+        let displayed = cursor_rows(&filtered, |option| option.is_enabled(text));
+        let group_starts = group_starts(&filtered, |option| option.is_enabled(text));
+        let mut cursor_pos = CursorPos {
+            source_index: initial_cursor(
+                cursor_pos.map(|pos| pos.source_index),
+                &displayed,
+                sorted,
+            ),
+        };
+        let moved_by_keyboard =
+            !renaming && self.move_cursor_pos(ui, &mut cursor_pos, &displayed, &group_starts);
+        store_cursor_pos(ui.ctx(), self.state_store, self.id_salt, cursor_pos.clone());
+
+        let cursor = displayed
+            .get(displayed_cursor_index(
+                &displayed,
+                cursor_pos.source_index,
+                self.interaction.reverse_order,
+            ))
+            .copied();
+        let cursor_filtered_index = cursor
+            .and_then(|source_index| filtered.iter().position(|d| d.source_index == source_index))
+            .unwrap_or(0);
+
+        let (enter_pressed, row_action_pressed) =
+            if renaming { (false, false) } else { self.enter_pressed(ui, cursor.is_some()) };
+        let cursor_text = (!renaming && text_resp.has_focus())
+            .then(|| filtered.get(cursor_filtered_index)?.option.editable_text(text))
+            .flatten();
+        let ghost = cursor_text.as_deref().filter(|_| self.interaction.ghost_text);
+        let ghost = ghost.and_then(|cursor_text| ghost_suffix(cursor_text, text));
+        let completion = self.requested_completion(ui, text_resp.id, cursor_text, text);
+        let shortcuts = &self.interaction.shortcuts;
+        let rename_pressed = self.on_rename.is_some()
+            && !renaming
+            && text_resp.has_focus()
+            && ui.input(|input| shortcuts.pressed(input, shortcuts.rename));
+
+        let mut rows = RowsState {
+            text,
+            main_edit_id: text_resp.id,
+            cursor_filtered_index,
+            scroll_to_cursor: moved_by_keyboard || opened,
+            enter_pressed,
+            rename_pressed,
+            row_action_pressed,
+            rename,
+            selected: None,
+            hovered_source_index: None,
+            highlight: self.highlight_matches.then(|| MatchHighlight {
+                font_id: TextStyle::Button.resolve(ui.style()),
+                color:   self.popup_style.highlight.unwrap_or(ui.visuals().selection.stroke.color),
+            }),
+            fuzzy_fallback: match_stats.fuzzy_fallback,
+            style: self.popup_style.resolve(ui.visuals()),
+        };
+        self.show_popup(ui, text_resp, filtered, pending, loading, &mut rows);
+
+        let RowsState { rename, selected, hovered_source_index, .. } = rows;
+        store_rename(ui.ctx(), self.id_salt, rename);
+
+        if self.follow_hover(ui.ctx(), &mut cursor_pos, moved_by_keyboard, hovered_source_index) {
+            store_cursor_pos(ui.ctx(), self.state_store, self.id_salt, cursor_pos);
+            ui.ctx().request_discard("EditableComboBox cursor moved by hover");
+        }
+
+        ShownOptions {
+            selected: selected.map(|(row, by)| (row.source_index, row.option, by, row.equals)),
+            completion,
+            ghost,
+            filtered_count: displayed.len(),
+            cursor,
+            match_stats,
+        }
+    }
+
+    /// Returns whether Enter selects the cursor row, if there is one,
+    /// and whether the row action shortcut (Alt+Enter by default) was pressed instead.
+    fn enter_pressed(&self, ui: &egui::Ui, has_cursor: bool) -> (bool, bool) {
+        ui.input(|input| {
+            let enter = input.key_pressed(egui::Key::Enter);
+            let shortcuts = &self.interaction.shortcuts;
+            let row_action =
+                self.row_action.is_some() && shortcuts.pressed(input, shortcuts.row_action);
+            // Nothing is selected if all options are disabled.
+            (enter && !row_action && has_cursor, row_action)
+        })
+    }
+
+    /// Returns the text of the cursor option if the user requested to complete the editor with it
+    /// by the completion shortcut (Ctrl+Space by default) or Tab.
+    ///
+    /// Tab is captured by the editor in the next frame only if it would change the text.
+    fn requested_completion(
+        &self,
+        ui: &egui::Ui,
+        edit_id: egui::Id,
+        cursor_text: Option<String>,
+        text: &str,
+    ) -> Option<String> {
+        let tab_completes = self.interaction.tab_completes
+            && cursor_text.as_ref().is_some_and(|cursor_text| cursor_text != text);
+        if tab_completes {
+            // Keep focus in the editor instead of moving it to the next widget.
+            let filter = EventFilter {
+                tab: true,
+                horizontal_arrows: true,
+                vertical_arrows: true,
+                ..Default::default()
+            };
+            ui.memory_mut(|mem| mem.set_focus_lock_filter(edit_id, filter));
+        }
+
+        let shortcuts = &self.interaction.shortcuts;
+        cursor_text.filter(|_| {
+            ui.input(|input| {
+                shortcuts.pressed(input, shortcuts.complete)
+                    || (tab_completes && input.key_pressed(egui::Key::Tab))
+            })
+        })
+    }
+
+    /// Displays the popup listing the filtered options.
+    fn show_popup<V, Opt>(
+        &mut self,
+        ui: &egui::Ui,
+        text_resp: &egui::Response,
+        filtered: Vec<DisplayedOption<Opt>>,
+        pending: bool,
+        loading: bool,
+        rows: &mut RowsState<'_, Opt>,
+    ) where
+        Opt: ValueOption<V>,
+    {
+        // The popup is shifted horizontally to stay on the screen (and flipped vertically)
+        // by egui, but it must also shrink if it is wider than the screen.
+        let screen_width =
+            ui.ctx().content_rect().width() - Frame::popup(ui.style()).total_margin().sum().x;
+        let max_width = self.max_popup_width.map_or(screen_width, |max| max.min(screen_width));
+        // Only the visible rows are measured in each frame,
+        // so reserve the width from the last frame to avoid resizing the popup
+        // when it is reopened or scrolled.
+        self.forget_stale_measurements(ui.ctx());
+        // The popup is a foreground area above all layers, so it is not clipped by the parent,
+        // e.g. a scroll area, but it is positioned in global coordinates.
+        // The editor may be in a transformed layer, e.g. inside a `Scene`.
+        let to_global = ui.ctx().layer_transform_to_global(ui.layer_id());
+        let anchor_rect = to_global.map_or(text_resp.rect, |to_global| to_global * text_resp.rect);
+        // The popup also spans the dropdown arrow after the editor.
+        let arrow_width = if self.dropdown_arrow { dropdown_arrow_width(ui) } else { 0.0 };
+        let scale = to_global.map_or(1.0, |to_global| to_global.scaling);
+        let min_width = (anchor_rect.width() + arrow_width * scale)
+            .max(self.min_popup_width)
+            .max(load_popup_width(ui.ctx(), self.id_salt))
+            .min(max_width);
+        let (scroll_x, wrap_mode) = match self.popup_overflow {
+            PopupOverflow::Scroll => (true, TextWrapMode::Extend),
+            PopupOverflow::Truncate => (false, TextWrapMode::Truncate),
+            PopupOverflow::Wrap => (false, TextWrapMode::Wrap),
+        };
+        let mut content_width = min_width;
+        // Restore the scroll offset while the filter text is unchanged,
+        // so that rebuilding the list does not lose the browsing position.
+        // A different filter text starts from the top.
+        let stored_offset = load_scroll_offset(ui.ctx(), self.id_salt);
+        let max_height = self.max_popup_height.unwrap_or(ui.spacing().combo_height);
+        let view_height = stored_offset.as_ref().map_or(max_height, |stored| stored.view_height);
+        let mut scroll_offset = stored_offset
+            .filter(|stored| stored.filter_text == rows.text)
+            .map_or(0.0, |stored| stored.offset);
+        if rows.scroll_to_cursor {
+            let (top, bottom) = self.row_span(ui, &filtered, rows.cursor_filtered_index);
+            scroll_offset = scroll_offset.min(top).max(bottom - view_height);
+        }
+        // The footer acts on all filtered options, which are consumed by the visible rows.
+        let matches = (self.multi && self.select_all_footer.is_some()).then(|| {
+            let enabled = |d: &&DisplayedOption<Opt>| {
+                !d.option.is_separator() && d.option.is_enabled(rows.text)
+            };
+            filtered.iter().filter(enabled).map(|d| d.source_index).collect::<Vec<_>>()
+        });
+        let was_open = Popup::is_id_open(ui.ctx(), self.popup_id());
+        let mut popup = Popup::new(
+            self.popup_id(),
+            ui.ctx().clone(),
+            PopupAnchor::ParentRect(anchor_rect),
+            ui.layer_id(),
+        )
+        // Without a width, the area is limited to the default area size of the style.
+        .width(max_width)
+        .open_memory(SetOpenCommand::Bool(true));
+        if let Some((align, alternatives)) = self.popup_placement.aligns() {
+            popup = popup.align(align).align_alternatives(alternatives);
+        }
+        let popup = popup.show(|ui| {
+            ui.scope_builder(UiBuilder::new().id(Ids::ScrollScope.id(self.id_salt)), |ui| {
+                if let Some(reason) = load_denial(ui.ctx(), self.id_salt) {
+                    ui.colored_label(ui.visuals().error_fg_color, reason);
+                }
+                if rows.fuzzy_fallback {
+                    ui.weak("Showing fuzzy matches");
+                }
+                let scroll_output = ScrollArea::new([scroll_x, true])
+                    .max_width(max_width)
+                    .id_salt(Ids::Scroll)
+                    .max_height(max_height)
+                    .vertical_scroll_offset(scroll_offset)
+                    .show_viewport(ui, |ui, viewport| {
+                        ui.set_min_width(min_width);
+                        if wrap_mode != TextWrapMode::Extend {
+                            ui.set_max_width(max_width);
+                        }
+                        ui.style_mut().wrap_mode = Some(wrap_mode);
+                        self.apply_row_spacing(ui.spacing_mut());
+                        self.show_visible_rows(ui, viewport, filtered, rows);
+                        content_width = ui.min_rect().width();
+                    });
+                debug_assert_eq!(scroll_output.id, self.scroll_id(), "scroll_id() is out of sync");
+                if loading {
+                    ui.weak("Loading…");
+                } else if pending {
+                    ui.weak("Still filtering…");
+                }
+                if let Some(matches) = matches {
+                    self.show_select_all_footer(ui, rows, matches, pending || loading);
+                }
+                store_scroll_offset(
+                    ui.ctx(),
+                    self.id_salt,
+                    ScrollOffset {
+                        filter_text: rows.text.into(),
+                        offset:      scroll_output.state.offset.y,
+                        view_height: scroll_output.inner_rect.height(),
+                    },
+                );
+            });
+        });
+
+        // The popup is sized in its first frame, so only scroll once it has been displayed.
+        if self.scroll_to_popup
+            && was_open
+            && let Some(popup) = popup
+        {
+            self.scroll_popup_into_view(ui, text_resp.rect, popup.response.rect);
+        }
+        store_popup_width(ui.ctx(), self.id_salt, content_width);
+    }
+
+    /// Scrolls the parent scroll areas once per opening of the popup,
+    /// so that both the editor and the popup at `popup_rect` in global coordinates are visible.
+    fn scroll_popup_into_view(&self, ui: &egui::Ui, editor_rect: Rect, popup_rect: Rect) {
+        let scrolled = Ids::ScrolledToPopup.id(self.id_salt);
+        if ui.ctx().memory(|mem| mem.data.get_temp::<bool>(scrolled)).unwrap_or(false) {
+            return;
+        }
+        let popup_rect = ui
+            .ctx()
+            .layer_transform_from_global(ui.layer_id())
+            .map_or(popup_rect, |from_global| from_global * popup_rect);
+        ui.scroll_to_rect(editor_rect.union(popup_rect), None);
+        ui.ctx().memory_mut(|mem| mem.data.insert_temp(scrolled, true));
+    }
+
+    /// The top and bottom of the row at `filtered_index` in the scroll area,
+    /// laid out as in [`show_visible_rows`](Self::show_visible_rows).
+    fn row_span<Opt>(
+        &self,
+        ui: &egui::Ui,
+        filtered: &[DisplayedOption<Opt>],
+        filtered_index: usize,
+    ) -> (f32, f32) {
+        let row_heights = load_row_heights(ui.ctx(), self.id_salt);
+        let (estimate, spacing) = self.row_metrics(ui);
+        let height_of = |displayed: &DisplayedOption<Opt>| {
+            row_heights.get(&displayed.source_index).copied().unwrap_or(estimate)
+        };
+        let top: f32 = filtered.iter().take(filtered_index).map(|d| height_of(d) + spacing).sum();
+        (top, top + filtered.get(filtered_index).map_or(0.0, height_of))
+    }
+
+    /// Displays the rows intersecting the `viewport` of the scroll area.
+    ///
+    /// Rows may differ in height, e.g. while renaming,
+    /// so each row is measured when displayed and its height is cached by source index
+    /// to keep the total height and the scroll bar accurate.
+    /// Rows that have never been displayed are estimated to have the height of a button.
+    ///
+    /// The galleys of the rows are not retained here between frames:
+    /// egui already caches laid out text by its layout job,
+    /// so an unchanged row is only hashed and repainted in idle frames.
+    /// The rows themselves must still be added in every frame to receive input.
+    fn show_visible_rows<V, Opt>(
+        &mut self,
+        ui: &mut egui::Ui,
+        viewport: egui::Rect,
+        filtered: Vec<DisplayedOption<Opt>>,
+        rows: &mut RowsState<'_, Opt>,
+    ) where
+        Opt: ValueOption<V>,
+    {
+        let mut row_heights = load_row_heights(ui.ctx(), self.id_salt);
+        let fading = self.fade_in_rows(ui.ctx(), &filtered);
+        let (estimate, spacing) = self.row_metrics(ui);
+        let height_of = |displayed: &DisplayedOption<Opt>| {
+            row_heights.get(&displayed.source_index).copied().unwrap_or(estimate)
+        };
+
+        let total_height: f32 = filtered.iter().map(|d| height_of(d) + spacing).sum();
+        ui.set_height((total_height - spacing).max(0.0));
+
+        // Skip the rows above the viewport.
+        let (mut first, mut top) = (0, 0.0);
+        for displayed in &filtered {
+            let bottom = top + height_of(displayed);
+            if bottom >= viewport.min.y {
+                break;
+            }
+            top = bottom + spacing;
+            first += 1;
+        }
+
+        let mut rect = ui.max_rect();
+        rect.min.y += top;
+        let viewport_bottom = ui.max_rect().top() + viewport.max.y;
+        let mut rows_ui = ui.new_child(
+            UiBuilder::new().max_rect(rect).layout(Layout::top_down_justified(Align::Min)),
+        );
+        rows_ui.skip_ahead_auto_ids(first);
+        let count = filtered.len();
+        let recent_count = filtered.iter().take_while(|displayed| displayed.recent).count();
+        for (filtered_index, displayed) in filtered.into_iter().enumerate() {
+            let row_top = rows_ui.cursor().top();
+            if filtered_index < first || row_top > viewport_bottom {
+                // The cursor row is selected by Enter even if it is out of view,
+                // e.g. when the cursor has moved past the viewport in this frame.
+                if filtered_index == rows.cursor_filtered_index && rows.enter_pressed {
+                    rows.selected = Some((displayed, ValueChange::Entered));
+                }
+                continue;
+            }
+            let source_index = displayed.source_index;
+            let opacity = rows_ui.opacity();
+            if let Some(&fade) = fading.get(&source_index) {
+                rows_ui.multiply_opacity(fade);
+                // Only animate the rows in view.
+                rows_ui.ctx().request_repaint();
+            }
+            // The header and the separator of the recent section are measured with their rows.
+            if filtered_index == 0 && recent_count > 0 {
+                rows_ui.weak("Recent");
+            }
+            self.show_row(&mut rows_ui, filtered_index, displayed, rows);
+            if filtered_index + 1 == recent_count && filtered_index + 1 < count {
+                rows_ui.separator();
+            }
+            rows_ui.set_opacity(opacity);
+            row_heights.insert(source_index, rows_ui.min_rect().bottom() - row_top);
+        }
+        ui.expand_to_include_rect(rows_ui.min_rect());
+
+        store_row_heights(ui.ctx(), self.id_salt, row_heights);
+    }
+
+    /// Applies [`row_spacing`](Self::row_spacing) and [`dense`](Self::dense)
+    /// to the spacing of the rows in the popup.
+    fn apply_row_spacing(&self, spacing: &mut egui::style::Spacing) {
+        if self.popup_style.dense {
+            spacing.item_spacing.y = 0.0;
+            spacing.button_padding.y = 0.0;
+            spacing.interact_size.y = 0.0;
+        }
+        if let Some(row_spacing) = self.popup_style.row_spacing {
+            spacing.item_spacing.y = row_spacing;
+        }
+    }
+
+    /// The estimated height of a row that has never been displayed,
+    /// which is the height of a button, and the spacing between rows.
+    fn row_metrics(&self, ui: &egui::Ui) -> (f32, f32) {
+        let mut spacing = ui.spacing().clone();
+        self.apply_row_spacing(&mut spacing);
+        let estimate = (ui.text_style_height(&TextStyle::Body) + 2.0 * spacing.button_padding.y)
+            .max(spacing.interact_size.y);
+        (estimate, spacing.item_spacing.y)
+    }
+
+    /// The number of rows visible at once in the scroll area of the popup,
+    /// by which Page Up and Page Down move the cursor.
+    ///
+    /// Rows are assumed to have the average height of the rows measured so far.
+    /// Moves the cursor among the `displayed` rows
+    /// according to the navigation keys pressed in this frame.
+    ///
+    /// Returns whether a navigation key was pressed.
+    ///
+    /// The group shortcuts move the cursor to the rows at `group_starts`.
+    fn move_cursor_pos(
+        &self,
+        ui: &egui::Ui,
+        cursor_pos: &mut CursorPos,
+        displayed: &[usize],
+        group_starts: &[usize],
+    ) -> bool {
+        let page_rows = self.page_rows(ui);
+        let shortcuts = &self.interaction.shortcuts;
+        let Some(motion) = ui.input(|input| {
+            // Checked first, since the group shortcuts also press the arrow keys.
+            if shortcuts.pressed(input, shortcuts.next_group) {
+                return Some(Motion::NextGroup);
+            }
+            if shortcuts.pressed(input, shortcuts.previous_group) {
+                return Some(Motion::PreviousGroup);
+            }
+            [
+                (Motion::Up, egui::Key::ArrowUp),
+                (Motion::Down, egui::Key::ArrowDown),
+                (Motion::Home, egui::Key::Home),
+                (Motion::End, egui::Key::End),
+                (Motion::PageUp(page_rows), egui::Key::PageUp),
+                (Motion::PageDown(page_rows), egui::Key::PageDown),
+            ]
+            .into_iter()
+            .find_map(|(motion, key)| if input.key_pressed(key) { Some(motion) } else { None })
+        }) else {
+            return false;
+        };
+
+        let (reverse_order, wrap) =
+            (self.interaction.reverse_order, self.interaction.wrap_navigation);
+        move_cursor(
+            &mut cursor_pos.source_index,
+            displayed,
+            group_starts,
+            reverse_order,
+            wrap,
+            motion,
+        );
+        true
+    }
+
+    fn page_rows(&self, ui: &egui::Ui) -> usize {
+        let row_heights = load_row_heights(ui.ctx(), self.id_salt);
+        let (estimate, spacing) = self.row_metrics(ui);
+        #[expect(clippy::cast_precision_loss)] // row counts are small
+        let row_height = if row_heights.is_empty() {
+            estimate
+        } else {
+            row_heights.values().sum::<f32>() / row_heights.len() as f32
+        };
+        let view_height = self.max_popup_height.unwrap_or(ui.spacing().combo_height);
+        #[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss)] // positive and small
+        let rows = ((view_height + spacing) / (row_height + spacing)).floor() as usize;
+        rows.max(1)
+    }
 
-    match motion {
-        Motion::Home => {
-            if let Some(first) = displayed_options.first() {
-                cursor_pos.source_index = first.source_index;
+    /// Tracks the rows appearing in the popup as the filter changes,
+    /// returning the opacity of the rows still fading in by source index.
+    ///
+    /// Rows are not animated when the popup opens,
+    /// or if animations are disabled with [`egui::Style::animation_time`],
+    /// which apps should set to zero if the user prefers reduced motion.
+    /// The rows in view request a repaint while they fade in.
+    fn fade_in_rows<Opt>(
+        &self,
+        ctx: &egui::Context,
+        filtered: &[DisplayedOption<Opt>],
+    ) -> BTreeMap<usize, f32> {
+        let duration = ctx.style().animation_time;
+        let now = ctx.input(|input| input.time);
+        let prev = ctx.memory(|mem| mem.data.get_temp::<RowFades>(Ids::RowFades.id(self.id_salt)));
+
+        let mut fades = RowFades {
+            displayed: filtered.iter().map(|d| d.source_index).collect(),
+            appeared:  BTreeMap::new(),
+        };
+        if let Some(prev) = prev
+            && duration > 0.0
+        {
+            for &source_index in &fades.displayed {
+                let appeared = if prev.displayed.contains(&source_index) {
+                    prev.appeared.get(&source_index).copied()
+                } else {
+                    Some(now)
+                };
+                if let Some(appeared) = appeared.filter(|&t| now - t < f64::from(duration)) {
+                    fades.appeared.insert(source_index, appeared);
+                }
+            }
+        }
+
+        #[expect(clippy::cast_possible_truncation)] // elapsed time is small
+        let opacities: BTreeMap<usize, f32> = fades
+            .appeared
+            .iter()
+            .map(|(&source_index, &appeared)| {
+                (source_index, ((now - appeared) as f32 / duration).clamp(0.0, 1.0))
+            })
+            .collect();
+        ctx.memory_mut(|mem| mem.data.insert_temp(Ids::RowFades.id(self.id_salt), fades));
+        opacities
+    }
+
+    /// Displays a single option in the popup.
+    fn show_row<V, Opt>(
+        &mut self,
+        ui: &mut egui::Ui,
+        filtered_index: usize,
+        displayed: DisplayedOption<Opt>,
+        rows: &mut RowsState<'_, Opt>,
+    ) where
+        Opt: ValueOption<V>,
+    {
+        if let Some(RenameState::Editing { source_index, text }) = &rows.rename
+            && *source_index == displayed.source_index
+        {
+            rows.rename =
+                self.show_rename_editor(ui, rows.main_edit_id, *source_index, text.clone());
+            return;
+        }
+        if displayed.separator {
+            ui.separator();
+            return;
+        }
+
+        let highlighted =
+            rows.highlight.as_ref().filter(|_| !rows.text.is_empty()).and_then(|highlight| {
+                let matcher = if rows.fuzzy_fallback {
+                    Some(&SubsequenceMatcher as &dyn Matcher)
+                } else {
+                    self.matcher.as_deref()
+                };
+                match matcher {
+                    Some(matcher) => {
+                        highlight_matched(matcher, &displayed.option, rows.text, highlight)
+                    }
+                    None => displayed.option.display_highlighted(rows.text, highlight),
+                }
+            });
+        let mut atoms = match highlighted {
+            Some(job) => Atoms::new(job),
+            None => displayed.option.display(rows.text).into_atoms(),
+        };
+        if self.flatten_labels {
+            atoms.map_texts(|text| flatten::flatten_widget_text(ui.style(), text));
+        }
+        if let Some(icon) = displayed.option.icon() {
+            atoms.push_left(icon);
+        }
+        if let Some(description) = displayed.option.description() {
+            append_description(ui, &mut atoms, description);
+        }
+        let mut button = Button::selectable(displayed.equals, atoms);
+        let enabled = displayed.option.is_enabled(rows.text);
+        let is_cursor = enabled && rows.cursor_filtered_index == filtered_index;
+        if is_cursor {
+            button = button.frame_when_inactive(true).stroke(rows.style.cursor.stroke);
+        }
+        let parent_style = ui.style().clone();
+        rows.style.apply(ui.visuals_mut());
+        let select_resp = ui.add_enabled(enabled, button);
+        ui.set_style(parent_style);
+        if enabled && select_resp.hovered() {
+            rows.hovered_source_index = Some(displayed.source_index);
+        }
+
+        // Trailing icons are placed over the row from right to left.
+        let mut icons_left = select_resp.rect.right();
+        let mut next_icon_rect = || {
+            let size = select_resp.rect.height();
+            icons_left -= size;
+            egui::Rect::from_min_size(
+                egui::pos2(icons_left, select_resp.rect.top()),
+                egui::vec2(size, size),
+            )
+        };
+
+        if let Some(row_action) = &mut self.row_action
+            && let Some(icon) = (row_action.icon)(displayed.source_index)
+        {
+            let icon_resp = ui.put(next_icon_rect(), Button::new(icon).frame(false));
+            if icon_resp.clicked() || (is_cursor && rows.row_action_pressed) {
+                (row_action.on_action)(displayed.source_index);
+                // The editor surrenders focus on Enter, but the popup stays open for other rows.
+                ui.memory_mut(|mem| mem.request_focus(rows.main_edit_id));
+                store_refocus(ui.ctx(), self.id_salt, true);
+            }
+        }
+
+        let label = if is_cursor && self.on_rename.is_some() {
+            displayed.option.editable_text(rows.text)
+        } else {
+            None
+        };
+        if let Some(label) = label {
+            let icon_resp =
+                ui.put(next_icon_rect(), Button::new("✏").frame(false)).on_hover_text("Rename");
+            if icon_resp.clicked() || rows.rename_pressed {
+                rows.rename = Some(RenameState::Editing {
+                    source_index: displayed.source_index,
+                    text:         label,
+                });
+                return;
+            }
+        }
+
+        if select_resp.clicked() {
+            rows.selected = Some((displayed, ValueChange::Clicked));
+        } else if is_cursor && rows.enter_pressed {
+            rows.selected = Some((displayed, ValueChange::Entered));
+        }
+    }
+
+    /// Displays the inline editor for the option being renamed.
+    ///
+    /// Returns the rename state for the next frame.
+    fn show_rename_editor(
+        &mut self,
+        ui: &mut egui::Ui,
+        main_edit_id: egui::Id,
+        source_index: usize,
+        mut new_label: String,
+    ) -> Option<RenameState> {
+        let resp =
+            ui.add(TextEdit::singleline(&mut new_label).id(Ids::RenameEdit.id(self.id_salt)));
+        if !resp.has_focus() && !resp.lost_focus() {
+            // Move focus from the main editor when renaming starts.
+            resp.request_focus();
+        }
+
+        if resp.lost_focus() {
+            let (enter, escape) = ui.input(|input| {
+                (input.key_pressed(egui::Key::Enter), input.key_pressed(egui::Key::Escape))
+            });
+            if enter && let Some(on_rename) = &mut self.on_rename {
+                on_rename(source_index, new_label);
+            }
+            if enter || escape {
+                ui.memory_mut(|mem| mem.request_focus(main_edit_id));
+                return Some(RenameState::Returning);
+            }
+            return None;
+        }
+
+        Some(RenameState::Editing { source_index, text: new_label })
+    }
+
+    /// Moves the cursor to the hovered option once the hover intent is confirmed.
+    ///
+    /// Returns whether the cursor position was changed.
+    fn follow_hover(
+        &self,
+        ctx: &egui::Context,
+        cursor_pos: &mut CursorPos,
+        moved_by_keyboard: bool,
+        hovered_source_index: Option<usize>,
+    ) -> bool {
+        let config = &self.interaction;
+        let pointer = ctx.input(|input| input.pointer.hover_pos());
+        let now = ctx.input(|input| input.time);
+        let mut intent = load_hover_intent(ctx, self.id_salt);
+
+        let mut moved = false;
+        if moved_by_keyboard {
+            // Restart stickiness from the current pointer position
+            // so that a resting pointer does not steal the cursor back.
+            intent = HoverIntent { anchor: pointer, pending: None };
+        } else if let Some(pointer) = pointer
+            && config.hover_moves_cursor
+        {
+            let anchor = *intent.anchor.get_or_insert(pointer);
+            if anchor.distance(pointer) < config.hover_stickiness {
+                intent.pending = None;
+            } else if let Some(hovered) = hovered_source_index
+                && hovered != cursor_pos.source_index
+            {
+                let since = match intent.pending {
+                    Some((pending, since)) if pending == hovered => since,
+                    _ => now,
+                };
+                #[expect(clippy::cast_possible_truncation)] // elapsed time is small
+                let remaining = config.hover_delay - (now - since) as f32;
+                if remaining <= 0.0 {
+                    cursor_pos.source_index = hovered;
+                    intent = HoverIntent { anchor: Some(pointer), pending: None };
+                    moved = true;
+                } else {
+                    intent.pending = Some((hovered, since));
+                    ctx.request_repaint_after_secs(remaining);
+                }
+            } else {
+                intent.pending = None;
+            }
+        } else {
+            intent.pending = None;
+        }
+
+        store_hover_intent(ctx, self.id_salt, intent);
+        moved
+    }
+
+    /// Appends the inputs of the current frame to the recording, if any,
+    /// with positions relative to `origin`.
+    fn record_frame(
+        &mut self,
+        ui: &egui::Ui,
+        origin: egui::Pos2,
+        text_resp: &egui::Response,
+        text: &str,
+    ) {
+        let Some(recording) = &mut self.recording else { return };
+        recording.id_salt = Some(self.id_salt);
+
+        let frame = ui.input(|input| RecordedFrame {
+            time:        input.time,
+            modifiers:   input.modifiers,
+            events:      input.events.iter().filter_map(|e| record_event(e, origin)).collect(),
+            transitions: Vec::new(),
+        });
+        recording.frames.push(frame);
+
+        if text_resp.gained_focus() {
+            self.record_transition(Transition::GainedFocus);
+        }
+        if text_resp.changed() {
+            self.record_transition(Transition::TextEdited(text.into()));
+        }
+        if text_resp.lost_focus() {
+            self.record_transition(Transition::LostFocus);
+        }
+    }
+
+    fn record_transition(&mut self, transition: Transition) {
+        if let Some(recording) = &mut self.recording
+            && let Some(frame) = recording.frames.last_mut()
+        {
+            frame.transitions.push(transition);
+        }
+    }
+
+    /// Forgets the measured popup width and row heights
+    /// if they were measured at a different zoom factor or DPI,
+    /// since text is laid out to whole pixels and measures slightly differently in points.
+    fn forget_stale_measurements(&self, ctx: &egui::Context) {
+        let pixels_per_point = ctx.pixels_per_point();
+        ctx.memory_mut(|mem| {
+            let measured = Ids::MeasuredPixelsPerPoint.id(self.id_salt);
+            if mem.data.get_temp::<f32>(measured) != Some(pixels_per_point) {
+                mem.data.remove::<f32>(Ids::PopupWidth.id(self.id_salt));
+                mem.data.remove::<BTreeMap<usize, f32>>(Ids::RowHeights.id(self.id_salt));
+                mem.data.insert_temp(measured, pixels_per_point);
+            }
+        });
+    }
+
+    /// Replaces the settings not set on the builder with the registered defaults.
+    fn load_defaults(&mut self, ctx: &egui::Context) {
+        let overridden = self.overridden;
+        if overridden.interaction && overridden.popup_style {
+            return;
+        }
+        let Some(defaults) = ctx.data(|data| data.get_temp::<ComboBoxDefaults>(defaults_id()))
+        else {
+            return;
+        };
+        if !overridden.interaction {
+            let set = mem::replace(&mut self.interaction, defaults.interaction);
+            let interaction = &mut self.interaction;
+            if overridden.has(Setting::AllowFreeText) {
+                interaction.allow_free_text = set.allow_free_text;
+            }
+            if overridden.has(Setting::ReverseOrder) {
+                interaction.reverse_order = set.reverse_order;
+            }
+            if overridden.has(Setting::PinSelected) {
+                interaction.pin_selected = set.pin_selected;
+            }
+            if overridden.has(Setting::GhostText) {
+                interaction.ghost_text = set.ghost_text;
+            }
+            if overridden.has(Setting::FuzzyFallback) {
+                interaction.fuzzy_fallback = set.fuzzy_fallback;
+            }
+            if overridden.has(Setting::FocusText) {
+                interaction.focus_text = set.focus_text;
             }
         }
-        Motion::End => {
-            if let Some(last) = displayed_options.last() {
-                cursor_pos.source_index = last.source_index;
+        if !overridden.popup_style {
+            let set = mem::replace(&mut self.popup_style, defaults.popup_style);
+            if overridden.has(Setting::RowSpacing) {
+                self.popup_style.row_spacing = set.row_spacing;
+            }
+            if overridden.has(Setting::Dense) {
+                self.popup_style.dense = set.dense;
             }
         }
-        Motion::Up => {
-            let partition_point =
-                displayed_options.partition_point(|d| d.source_index < cursor_pos.source_index);
-            if let Some(new_index) = partition_point.checked_sub(1)
-                && let Some(option) = displayed_options.get(new_index)
+    }
+
+    /// Consults the [`on_before_commit`](Self::on_before_commit) guard
+    /// before `candidate` is committed from the option at `source_index`, or from free text.
+    ///
+    /// The candidate is `None` if the selected option completes the text into an invalid value,
+    /// which is denied without consulting the guard.
+    /// If the candidate is denied, focus is returned to the editor to keep the popup open,
+    /// with the reason from the guard if any.
+    fn allow_commit(
+        &mut self,
+        ctx: &egui::Context,
+        edit_id: egui::Id,
+        source_index: Option<usize>,
+        candidate: Option<&dyn Value>,
+    ) -> bool {
+        let reason = match (candidate, &mut self.before_commit) {
+            (Some(_), None) => return true,
+            (Some(candidate), Some(guard)) => match guard(source_index, candidate) {
+                CommitGuard::Allow => {
+                    store_denial(ctx, self.id_salt, None);
+                    return true;
+                }
+                CommitGuard::Deny(reason) => Some(reason),
+            },
+            (None, _) => None,
+        };
+        store_denial(ctx, self.id_salt, reason);
+        store_refocus(ctx, self.id_salt, true);
+        ctx.memory_mut(|mem| mem.request_focus(edit_id));
+        ctx.request_discard("EditableComboBox commit denied");
+        false
+    }
+
+    /// Notifies the hooks and records the history after the user selects the option
+    /// at `source_index`, or commits free text.
+    fn on_committed<V: Value>(
+        &mut self,
+        ctx: &egui::Context,
+        value: &V,
+        source_index: Option<usize>,
+    ) {
+        selection_history::record_selection(
+            ctx,
+            self.state_store,
+            self.id_salt,
+            value,
+            source_index,
+        );
+        if let Some(pool) = self.suggestion_pool {
+            pool.add(ctx, value.to_editable());
+        }
+        if let Some(source_index) = source_index {
+            self.record_transition(Transition::Selected { source_index });
+            if let Some(on_select) = &mut self.on_select {
+                on_select(source_index, ctx.input(|input| input.modifiers));
+            }
+        }
+        if let Some(on_commit_feedback) = &mut self.on_commit_feedback {
+            on_commit_feedback(ctx);
+        }
+        if let Some(announcement) = &self.announcement {
+            let info =
+                WidgetInfo::labeled(WidgetType::ComboBox, true, announcement(&value.to_editable()));
+            ctx.output_mut(|output| {
+                output.events.push(egui::output::OutputEvent::ValueChanged(info));
+            });
+        }
+    }
+
+    /// Replaces the text with a previously selected value if Up or Down is pressed
+    /// while the text is empty or showing a history entry.
+    ///
+    /// Returns whether the text was replaced.
+    fn navigate_history(
+        &self,
+        ctx: &egui::Context,
+        edit_id: egui::Id,
+        text: &mut B,
+        prefix: &str,
+    ) -> bool {
+        if self.interaction.history == HistoryMode::Off {
+            return false;
+        }
+
+        let history = load_selection_history(ctx, self.state_store, self.id_salt);
+        let mut history = distinct_texts(&history);
+        history.reverse();
+        let entry_text =
+            |entry: &'_ String| String::from(entry.strip_prefix(prefix).unwrap_or(entry));
+        // Editing the entry stops history navigation.
+        let pos = load_history_pos(ctx, self.id_salt).filter(|&pos| {
+            history.get(pos).is_some_and(|entry| entry_text(entry) == text.as_str())
+        });
+        if pos.is_none() && !text.as_str().is_empty() {
+            return false;
+        }
+
+        // The outer `None` leaves the key to the popup cursor,
+        // the inner `None` steps past the newest entry.
+        let new_pos = ctx.input_mut(|input| {
+            let up = match pos {
+                Some(pos) => Some(pos.saturating_sub(1)),
+                None => history.len().checked_sub(1),
+            };
+            if let Some(up) = up
+                && input.consume_key(egui::Modifiers::NONE, egui::Key::ArrowUp)
+            {
+                Some(Some(up))
+            } else if let Some(pos) = pos
+                && input.consume_key(egui::Modifiers::NONE, egui::Key::ArrowDown)
             {
-                cursor_pos.source_index = option.source_index;
-            } else if let Some(last) = displayed_options.last() {
-                cursor_pos.source_index = last.source_index;
+                Some(Some(pos + 1).filter(|&down| down < history.len()))
+            } else {
+                None
             }
+        });
+        let Some(new_pos) = new_pos else { return false };
+
+        store_history_pos(ctx, self.id_salt, new_pos);
+        let new_text = new_pos.map(|pos| entry_text(history[pos])).unwrap_or_default();
+        complete_text(ctx, edit_id, text, 0..text.as_str().len(), &new_text);
+        true
+    }
+
+    /// Keeps focus in the editor after a selection unless the popup closes on select,
+    /// in which case focus only returns to the editor inside a modal.
+    fn focus_after_commit<V: Value>(
+        &self,
+        ctx: &egui::Context,
+        edit_id: egui::Id,
+        text: &mut B,
+        value: &V,
+        prefix: &str,
+    ) {
+        if !self.interaction.close_on_select {
+            ctx.memory_mut(|mem| mem.request_focus(edit_id));
+            store_refocus(ctx, self.id_salt, true);
+        } else if in_modal(ctx) {
+            let editable = value.to_editable();
+            text.replace_with(editable.strip_prefix(prefix).unwrap_or(&editable));
+            self.dismiss_popup(ctx, edit_id);
         }
-        Motion::Down => {
-            let partition_point =
-                displayed_options.partition_point(|d| d.source_index <= cursor_pos.source_index);
-            if let Some(option) = displayed_options.get(partition_point) {
-                cursor_pos.source_index = option.source_index;
-            } else if let Some(first) = displayed_options.first() {
-                cursor_pos.source_index = first.source_index;
+    }
+
+    /// Cancels editing if Escape is pressed in the editor, as in [`cancel_on_escape`].
+    ///
+    /// Inside a modal, Escape closes the popup before the modal,
+    /// which closes on the next Escape.
+    fn cancel_editing(
+        &self,
+        ui: &egui::Ui,
+        text_resp: &egui::Response,
+        text: &mut B,
+        hint: &str,
+    ) -> bool {
+        let cancelled = cancel_on_escape(ui, text_resp, text, hint);
+        if cancelled && in_modal(ui.ctx()) && Popup::is_id_open(ui.ctx(), self.popup_id()) {
+            self.dismiss_popup(ui.ctx(), text_resp.id);
+        }
+        cancelled
+    }
+
+    /// Closes the popup but returns focus to the editor.
+    ///
+    /// The popup reopens when the text is edited, the editor is clicked or Down is pressed.
+    fn dismiss_popup(&self, ctx: &egui::Context, edit_id: egui::Id) {
+        ctx.memory_mut(|mem| mem.request_focus(edit_id));
+        store_refocus(ctx, self.id_salt, true);
+        store_dismissed(ctx, self.id_salt, true);
+    }
+
+    /// Ends the [dismissal](Self::dismiss_popup) of the popup when the editor loses focus
+    /// or the text is edited.
+    ///
+    /// Returns whether the popup is reopened by clicking the widget or pressing Down,
+    /// which is treated like focusing the editor anew.
+    fn reopen_dismissed(
+        &self,
+        ui: &egui::Ui,
+        text_resp: &egui::Response,
+        text: &mut B,
+        clicked: bool,
+    ) -> bool {
+        if !load_dismissed(ui.ctx(), self.id_salt) {
+            return false;
+        }
+        let reopened = text_resp.has_focus()
+            && (clicked
+                || ui.input_mut(|input| {
+                    input.consume_key(egui::Modifiers::NONE, egui::Key::ArrowDown)
+                }));
+        if reopened || !text_resp.has_focus() || text_resp.changed() {
+            store_dismissed(ui.ctx(), self.id_salt, false);
+        }
+        if reopened
+            && self.completion_scope == CompletionScope::Whole
+            && self.clears_on_focus()
+            && !text.as_str().is_empty()
+        {
+            text.clear();
+            ui.ctx().request_discard("EditableComboBox text buffer cleared on reopening");
+        }
+        reopened
+    }
+
+    /// Whether the text is cleared when the editor gains focus.
+    fn clears_on_focus(&self) -> bool { self.interaction.focus_text == FocusText::Clear }
+
+    /// Closes the popup and discards the typed text while the combo box is disabled,
+    /// so that the current value is displayed and editing does not resume when enabled again.
+    fn stop_editing(&self, ctx: &egui::Context) {
+        let editor_id = Ids::Editor.id(self.id_salt);
+        ctx.memory_mut(|mem| {
+            if mem.has_focus(editor_id) {
+                mem.surrender_focus(editor_id);
             }
+        });
+        discard_text_buf::<B>(ctx, self.state_store, self.id_salt);
+        self.forget_popup_state(ctx);
+    }
+
+    fn forget_popup_state(&self, ctx: &egui::Context) {
+        ctx.memory_mut(|mem| {
+            // Cursor position is no longer relevant once the popup is closed.
+            // Upon reopening, the cursor position will be recalculated to match the selected value.
+            mem.data.remove::<HoverIntent>(Ids::HoverIntent.id(self.id_salt));
+            mem.data.remove::<RenameState>(Ids::Rename.id(self.id_salt));
+            mem.data.remove::<usize>(Ids::HistoryPos.id(self.id_salt));
+            mem.data.remove::<FilterProgress>(Ids::FilterProgress.id(self.id_salt));
+            mem.data.remove::<String>(Ids::Denial.id(self.id_salt));
+            mem.data.remove::<RowFades>(Ids::RowFades.id(self.id_salt));
+            mem.data.remove::<bool>(Ids::ScrolledToPopup.id(self.id_salt));
+        });
+        discard_cursor_pos(ctx, self.state_store, self.id_salt);
+
+        if Popup::is_id_open(ctx, self.popup_id()) {
+            Popup::close_id(ctx, self.popup_id());
+        }
+    }
+}
+
+/// Cancels editing if Escape is pressed in the editor,
+/// restoring the text of the current value and releasing focus to close the popup.
+///
+/// Returns whether editing was cancelled.
+fn cancel_on_escape<B: TextBuffer>(
+    ui: &egui::Ui,
+    text_resp: &egui::Response,
+    text: &mut B,
+    hint: &str,
+) -> bool {
+    // `TextEdit` releases focus on Escape by itself, so the editor may have just lost focus.
+    let editing = text_resp.has_focus() || text_resp.lost_focus();
+    if !editing || !ui.input(|input| input.key_pressed(egui::Key::Escape)) {
+        return false;
+    }
+
+    ui.memory_mut(|mem| mem.surrender_focus(text_resp.id));
+    if text.as_str() != hint {
+        text.replace_with(hint);
+        ui.ctx().request_discard("EditableComboBox editing cancelled");
+    }
+    true
+}
+
+/// The text to filter the options by, which is the completed range of `text` if any.
+///
+/// Otherwise, options are filtered and converted with the full text including the locked prefix.
+/// Text equal to `unedited` has not been typed by the user and filters nothing.
+fn filter_text(
+    text: &str,
+    prefix: &str,
+    completion_range: Option<Range<usize>>,
+    unedited: Option<&str>,
+) -> String {
+    match completion_range {
+        None if unedited == Some(text) => String::new(),
+        Some(range) => String::from(&text[range]),
+        None => [prefix, text].concat(),
+    }
+}
+
+/// Returns the rest of `option_text` if the typed `text` is its start, ignoring ASCII case.
+fn ghost_suffix(option_text: &str, text: &str) -> Option<String> {
+    let start = option_text.get(..text.len())?;
+    let rest = &option_text[text.len()..];
+    (!text.is_empty() && !rest.is_empty() && start.eq_ignore_ascii_case(text))
+        .then(|| String::from(rest))
+}
+
+/// Paints the `ghost` completion, if any, in the `text_end` area of the editor,
+/// aligned against the text on its right if the text is `rtl`.
+fn paint_ghost_text(ui: &egui::Ui, text_end: Rect, rtl: bool, ghost: Option<String>) {
+    let Some(ghost) = ghost else { return };
+    let font_id = egui::FontSelection::default().resolve(ui.style());
+    let color = ui.visuals().weak_text_color();
+    let galley = ui.painter().layout_no_wrap(ghost, font_id, color);
+    let pos = if rtl {
+        text_end.right_top() - egui::vec2(galley.size().x, 0.0)
+    } else {
+        text_end.left_top()
+    };
+    ui.painter().with_clip_rect(text_end).galley(pos, galley, color);
+}
+
+/// Parses the typed text after the locked `prefix` as a free-text value.
+///
+/// Returns `None` if the text is empty, unchanged from the current value `full_hint`,
+/// or not a valid value.
+fn free_text<V: Value>(text: &str, prefix: &str, full_hint: &str) -> Option<V> {
+    let full = [prefix, text].concat();
+    if text.is_empty() || full == full_hint {
+        return None;
+    }
+    V::try_from_editable(&full)
+}
+
+/// Converts the selected option into the value to commit.
+///
+/// When completing a `completion_range`, the option replaces the range
+/// and the whole text is converted into the value instead,
+/// so the range and its replacement are also returned to be applied to the editor.
+fn commit_candidate<V: Value, Opt: ValueOption<V>>(
+    option: Opt,
+    text: &str,
+    prefix: &str,
+    filter_text: &str,
+    completion_range: Option<Range<usize>>,
+) -> (Option<V>, Option<(Range<usize>, String)>) {
+    let Some(range) = completion_range else {
+        return (Some(option.into_value(filter_text)), None);
+    };
+    let Some(completion) = option.editable_text(filter_text) else { return (None, None) };
+
+    let mut completed = String::from(text);
+    completed.replace_range(range.clone(), &completion);
+    let candidate = V::try_from_editable(&[prefix, &completed].concat());
+    (candidate, Some((range, completion)))
+}
+
+/// Replaces `completion_range` of `text` with `completion`,
+/// or the whole text with `completion` after the locked `prefix` if there is no range.
+///
+/// Completing without committing edits the text only.
+fn complete_in_range<B: TextBuffer>(
+    ctx: &egui::Context,
+    edit_id: egui::Id,
+    text: &mut B,
+    completion_range: Option<Range<usize>>,
+    prefix: &str,
+    completion: &str,
+) {
+    let (range, completion) = match completion_range {
+        Some(range) => (range, completion),
+        None => (0..text.as_str().len(), completion.strip_prefix(prefix).unwrap_or(completion)),
+    };
+    complete_text(ctx, edit_id, text, range, completion);
+    ctx.request_discard("EditableComboBox text completed");
+}
+
+/// Replaces `range` of `text` with `completion`,
+/// moving the caret to the end of the completion and keeping the editor focused.
+fn complete_text<B: TextBuffer>(
+    ctx: &egui::Context,
+    edit_id: egui::Id,
+    text: &mut B,
+    range: Range<usize>,
+    completion: &str,
+) {
+    let old = text.as_str();
+    let new_text = [&old[..range.start], completion, &old[range.end..]].concat();
+    let caret = new_text[..range.start + completion.len()].chars().count();
+    text.replace_with(&new_text);
+
+    if let Some(mut state) = TextEdit::load_state(ctx, edit_id) {
+        state.cursor.set_char_range(Some(CCursorRange::one(CCursor::new(caret))));
+        state.store(ctx, edit_id);
+    }
+    // A single-line editor surrenders focus on Enter, but the user may continue typing.
+    ctx.memory_mut(|mem| mem.request_focus(edit_id));
+}
+
+// The state below is kept in the `StateStore` if set, and in egui memory otherwise.
+
+fn load_text_buf<B>(
+    ctx: &egui::Context,
+    store: Option<&dyn StateStore>,
+    id_salt: egui::Id,
+    hint: &str,
+) -> B
+where
+    B: TextBuffer + Default + Clone + Send + Sync + 'static,
+{
+    let from_text = |stored: &str| {
+        let mut text = B::default();
+        text.replace_with(stored);
+        text
+    };
+    match store {
+        Some(store) => from_text(&store.text(ctx, id_salt).unwrap_or_else(|| String::from(hint))),
+        None => ctx
+            .memory(|mem| mem.data.get_temp::<B>(Ids::TextBuf.id(id_salt)))
+            .unwrap_or_else(|| from_text(hint)),
+    }
+}
+
+fn store_text_buf<B>(
+    ctx: &egui::Context,
+    store: Option<&dyn StateStore>,
+    id_salt: egui::Id,
+    text: B,
+) where
+    B: TextBuffer + Default + Clone + Send + Sync + 'static,
+{
+    match store {
+        Some(store) => store.set_text(ctx, id_salt, Some(String::from(text.as_str()))),
+        None => ctx.memory_mut(|mem| mem.data.insert_temp::<B>(Ids::TextBuf.id(id_salt), text)),
+    }
+}
+
+fn discard_text_buf<B>(ctx: &egui::Context, store: Option<&dyn StateStore>, id_salt: egui::Id)
+where
+    B: TextBuffer + Default + Clone + Send + Sync + 'static,
+{
+    match store {
+        Some(store) => store.set_text(ctx, id_salt, None),
+        None => ctx.memory_mut(|mem| mem.data.remove::<B>(Ids::TextBuf.id(id_salt))),
+    }
+}
+
+fn load_cursor_pos(
+    ctx: &egui::Context,
+    store: Option<&dyn StateStore>,
+    id_salt: egui::Id,
+) -> Option<CursorPos> {
+    match store {
+        Some(store) => store.cursor(ctx, id_salt).map(|source_index| CursorPos { source_index }),
+        None => ctx.memory(|mem| mem.data.get_temp::<CursorPos>(Ids::CursorPos.id(id_salt))),
+    }
+}
+
+fn store_cursor_pos(
+    ctx: &egui::Context,
+    store: Option<&dyn StateStore>,
+    id_salt: egui::Id,
+    cursor_pos: CursorPos,
+) {
+    match store {
+        Some(store) => store.set_cursor(ctx, id_salt, Some(cursor_pos.source_index)),
+        None => ctx.memory_mut(|mem| {
+            mem.data.insert_temp::<CursorPos>(Ids::CursorPos.id(id_salt), cursor_pos);
+        }),
+    }
+}
+
+fn discard_cursor_pos(ctx: &egui::Context, store: Option<&dyn StateStore>, id_salt: egui::Id) {
+    match store {
+        Some(store) => store.set_cursor(ctx, id_salt, None),
+        None => ctx.memory_mut(|mem| mem.data.remove::<CursorPos>(Ids::CursorPos.id(id_salt))),
+    }
+}
+
+fn load_popup_width(ctx: &egui::Context, id_salt: egui::Id) -> f32 {
+    ctx.memory(|mem| mem.data.get_temp::<f32>(Ids::PopupWidth.id(id_salt))).unwrap_or_default()
+}
+
+fn store_popup_width(ctx: &egui::Context, id_salt: egui::Id, width: f32) {
+    ctx.memory_mut(|mem| mem.data.insert_temp::<f32>(Ids::PopupWidth.id(id_salt), width));
+}
+
+fn load_scroll_offset(ctx: &egui::Context, id_salt: egui::Id) -> Option<ScrollOffset> {
+    ctx.memory(|mem| mem.data.get_temp::<ScrollOffset>(Ids::ScrollOffset.id(id_salt)))
+}
+
+fn store_scroll_offset(ctx: &egui::Context, id_salt: egui::Id, offset: ScrollOffset) {
+    ctx.memory_mut(|mem| {
+        mem.data.insert_temp::<ScrollOffset>(Ids::ScrollOffset.id(id_salt), offset);
+    });
+}
+
+/// The maximum number of values kept in the history of each combo box.
+const HISTORY_CAPACITY: usize = 50;
+
+fn load_history_pos(ctx: &egui::Context, id_salt: egui::Id) -> Option<usize> {
+    ctx.memory(|mem| mem.data.get_temp::<usize>(Ids::HistoryPos.id(id_salt)))
+}
+
+fn store_history_pos(ctx: &egui::Context, id_salt: egui::Id, pos: Option<usize>) {
+    ctx.memory_mut(|mem| match pos {
+        Some(pos) => mem.data.insert_temp::<usize>(Ids::HistoryPos.id(id_salt), pos),
+        None => mem.data.remove::<usize>(Ids::HistoryPos.id(id_salt)),
+    });
+}
+
+fn load_row_heights(ctx: &egui::Context, id_salt: egui::Id) -> BTreeMap<usize, f32> {
+    ctx.memory(|mem| mem.data.get_temp::<BTreeMap<usize, f32>>(Ids::RowHeights.id(id_salt)))
+        .unwrap_or_default()
+}
+
+fn store_row_heights(ctx: &egui::Context, id_salt: egui::Id, heights: BTreeMap<usize, f32>) {
+    ctx.memory_mut(|mem| {
+        mem.data.insert_temp::<BTreeMap<usize, f32>>(Ids::RowHeights.id(id_salt), heights);
+    });
+}
+
+fn load_filter_progress(ctx: &egui::Context, id_salt: egui::Id) -> Option<FilterProgress> {
+    ctx.memory(|mem| mem.data.get_temp::<FilterProgress>(Ids::FilterProgress.id(id_salt)))
+}
+
+fn store_filter_progress(ctx: &egui::Context, id_salt: egui::Id, progress: FilterProgress) {
+    ctx.memory_mut(|mem| {
+        mem.data.insert_temp::<FilterProgress>(Ids::FilterProgress.id(id_salt), progress);
+    });
+}
+
+fn load_hover_intent(ctx: &egui::Context, id_salt: egui::Id) -> HoverIntent {
+    ctx.memory(|mem| mem.data.get_temp::<HoverIntent>(Ids::HoverIntent.id(id_salt)))
+        .unwrap_or_default()
+}
+
+fn store_hover_intent(ctx: &egui::Context, id_salt: egui::Id, intent: HoverIntent) {
+    ctx.memory_mut(|mem| mem.data.insert_temp::<HoverIntent>(Ids::HoverIntent.id(id_salt), intent));
+}
+
+fn defaults_id() -> egui::Id { egui::Id::new("egui_editable_combobox::ComboBoxDefaults") }
+
+fn load_refocus(ctx: &egui::Context, id_salt: egui::Id) -> bool {
+    ctx.memory(|mem| mem.data.get_temp::<bool>(Ids::Refocus.id(id_salt)).unwrap_or_default())
+}
+
+fn store_refocus(ctx: &egui::Context, id_salt: egui::Id, refocus: bool) {
+    ctx.memory_mut(|mem| mem.data.insert_temp::<bool>(Ids::Refocus.id(id_salt), refocus));
+}
+
+fn load_dismissed(ctx: &egui::Context, id_salt: egui::Id) -> bool {
+    ctx.memory(|mem| mem.data.get_temp::<bool>(Ids::Dismissed.id(id_salt)).unwrap_or_default())
+}
+
+fn store_dismissed(ctx: &egui::Context, id_salt: egui::Id, dismissed: bool) {
+    ctx.memory_mut(|mem| mem.data.insert_temp::<bool>(Ids::Dismissed.id(id_salt), dismissed));
+}
+
+/// Whether a modal dialog is open, e.g. an [`egui::Modal`].
+///
+/// Losing focus inside a modal would leave the keyboard user stranded,
+/// so the popup is [dismissed](EditableComboBox::dismiss_popup) instead.
+fn in_modal(ctx: &egui::Context) -> bool { ctx.memory(|mem| mem.top_modal_layer().is_some()) }
+
+fn load_denial(ctx: &egui::Context, id_salt: egui::Id) -> Option<String> {
+    ctx.memory(|mem| mem.data.get_temp::<String>(Ids::Denial.id(id_salt)))
+}
+
+fn store_denial(ctx: &egui::Context, id_salt: egui::Id, reason: Option<String>) {
+    ctx.memory_mut(|mem| match reason {
+        Some(reason) => mem.data.insert_temp::<String>(Ids::Denial.id(id_salt), reason),
+        None => mem.data.remove::<String>(Ids::Denial.id(id_salt)),
+    });
+}
+
+fn load_rename(ctx: &egui::Context, id_salt: egui::Id) -> Option<RenameState> {
+    ctx.memory(|mem| mem.data.get_temp::<RenameState>(Ids::Rename.id(id_salt)))
+}
+
+fn store_rename(ctx: &egui::Context, id_salt: egui::Id, rename: Option<RenameState>) {
+    ctx.memory_mut(|mem| match rename {
+        Some(rename) => mem.data.insert_temp::<RenameState>(Ids::Rename.id(id_salt), rename),
+        None => mem.data.remove::<RenameState>(Ids::Rename.id(id_salt)),
+    });
+}
+
+/// The options displayed in the popup in the current frame.
+struct FilteredOptions<Opt> {
+    options:            Vec<DisplayedOption<Opt>>,
+    /// The cursor position of the option equal to the current value,
+    /// if the popup has just been opened.
+    default_cursor_pos: Option<CursorPos>,
+    /// Whether multiple options claimed to be equal to the current value.
+    ambiguous:          bool,
+    /// Whether some options are left unfiltered due to the filter budget.
+    pending:            bool,
+    /// Whether the options are sorted by relevance instead of the source order.
+    sorted:             bool,
+    stats:              MatchStats,
+}
+
+/// The progress of filtering the options by `text` over multiple frames.
+#[derive(Clone, Default)]
+struct FilterProgress {
+    text:           String,
+    /// The number of options already filtered.
+    processed:      usize,
+    /// The source indices of the matching options,
+    /// whether they are marked as selected and their relevance.
+    matches:        Vec<(usize, bool, Relevance)>,
+    /// The options matching the text as a fuzzy subsequence, like `matches`,
+    /// which are only collected while `matches` is empty.
+    fuzzy:          Vec<(usize, bool, Relevance)>,
+    pass:           FilterPass,
+    /// Whether the cursor should move to the option equal to the current value once found.
+    seek_selection: bool,
+}
+
+/// State shared by the rows of the popup in the current frame.
+#[expect(clippy::struct_excessive_bools)] // each flag is an independent input of this frame
+struct RowsState<'t, Opt> {
+    text:                  &'t str,
+    main_edit_id:          egui::Id,
+    cursor_filtered_index: usize,
+    /// Whether to scroll the cursor row into view,
+    /// after it is moved by the keyboard or the popup is opened.
+    scroll_to_cursor:      bool,
+    enter_pressed:         bool,
+    rename_pressed:        bool,
+    row_action_pressed:    bool,
+    rename:                Option<RenameState>,
+    /// The row selected by click or Enter.
+    selected:              Option<(DisplayedOption<Opt>, ValueChange)>,
+    hovered_source_index:  Option<usize>,
+    /// How to highlight matches, or `None` if disabled.
+    highlight:             Option<MatchHighlight>,
+    /// Whether the rows are the fuzzy matches of the [fallback](EditableComboBox::fuzzy_fallback).
+    fuzzy_fallback:        bool,
+    style:                 ResolvedPopupStyle,
+}
+
+/// The result of [`EditableComboBox::show_editor`].
+struct EditorOutput {
+    response: egui::Response,
+    /// The char index of the caret, if the editor is focused.
+    caret:    Option<usize>,
+    /// Whether the widget was clicked outside the clear button.
+    clicked:  bool,
+    /// Whether the value was reset by the clear button.
+    cleared:  bool,
+    /// The visible area of the editor after the end of the text,
+    /// which is on its left if the text is right-to-left.
+    text_end: Rect,
+    /// Whether the text is right-aligned as right-to-left text.
+    rtl:      bool,
+}
+
+/// The result of [`EditableComboBox::show_options`].
+struct ShownOptions<Opt> {
+    /// The option selected by click or Enter, with its source index
+    /// and whether it is marked as the current value by the [`EqualityPolicy`].
+    selected:       Option<(usize, Opt, ValueChange, bool)>,
+    /// The text of the option to complete the editor with, if requested.
+    completion:     Option<String>,
+    /// The rest of the cursor option after the typed text, displayed as ghost text.
+    ghost:          Option<String>,
+    /// The number of options displayed, excluding separators.
+    filtered_count: usize,
+    /// The source index of the option under the cursor.
+    cursor:         Option<usize>,
+    match_stats:    MatchStats,
+}
+
+impl<Opt> Default for ShownOptions<Opt> {
+    fn default() -> Self {
+        Self {
+            selected:       None,
+            completion:     None,
+            ghost:          None,
+            filtered_count: 0,
+            cursor:         None,
+            match_stats:    MatchStats::default(),
+        }
+    }
+}
+
+/// The rows displayed in the popup in the last frame, for animating appearing rows.
+#[derive(Clone)]
+struct RowFades {
+    /// The source indices of the displayed rows.
+    displayed: BTreeSet<usize>,
+    /// The time each row still fading in appeared, by source index.
+    appeared:  BTreeMap<usize, f64>,
+}
+
+/// The scroll offset of the popup while filtered by `filter_text`.
+#[derive(Clone)]
+struct ScrollOffset {
+    filter_text: String,
+    offset:      f32,
+    /// The height of the visible part of the list.
+    view_height: f32,
+}
+
+#[derive(Clone)]
+struct CursorPos {
+    source_index: usize,
+}
+
+#[derive(Clone)]
+enum RenameState {
+    /// The option at `source_index` is being renamed to `text`.
+    Editing { source_index: usize, text: String },
+    /// Renaming has finished and focus is returning to the main editor.
+    Returning,
+}
+
+#[derive(Clone, Default)]
+struct HoverIntent {
+    /// Pointer position when the cursor last moved.
+    /// Hovering does not move the cursor until the pointer leaves the stickiness radius.
+    anchor:  Option<egui::Pos2>,
+    /// The hovered option waiting for the hover delay to elapse, and the time it was first hovered.
+    pending: Option<(usize, f64)>,
+}
+
+/// Displays the arrow button toggling the popup, like the icon of [`egui::ComboBox`].
+fn show_dropdown_arrow(ui: &mut egui::Ui, height: f32) -> egui::Response {
+    let size = egui::vec2(dropdown_arrow_width(ui), height);
+    let (rect, response) = ui.allocate_exact_size(size, Sense::click());
+    response
+        .widget_info(|| WidgetInfo::labeled(WidgetType::Button, ui.is_enabled(), "Toggle options"));
+
+    if ui.is_rect_visible(rect) {
+        let visuals = ui.style().interact(&response);
+        let icon = Rect::from_center_size(
+            rect.center(),
+            egui::vec2(ui.spacing().icon_width * 0.7, ui.spacing().icon_width * 0.45),
+        );
+        ui.painter().add(Shape::convex_polygon(
+            alloc::vec![icon.left_top(), icon.right_top(), icon.center_bottom()],
+            visuals.fg_stroke.color,
+            Stroke::NONE,
+        ));
+    }
+    response
+}
+
+/// Appends `description` as a dimmed line under the last text of `atoms`.
+///
+/// The text is laid out into a galley here,
+/// so that it is neither flattened nor truncated to a single row.
+fn append_description(ui: &egui::Ui, atoms: &mut Atoms<'_>, description: &str) {
+    let small = TextStyle::Small.resolve(ui.style());
+    let weak = ui.visuals().weak_text_color();
+    let label = atoms.iter_texts_mut().last();
+    let mut job = label.as_ref().map_or_else(LayoutJob::default, |label| {
+        let job =
+            (*label).clone().into_layout_job(ui.style(), TextStyle::Button.into(), Align::Center);
+        Arc::unwrap_or_clone(job)
+    });
+    if !job.is_empty() {
+        job.append("\n", 0.0, TextFormat::simple(small.clone(), weak));
+    }
+    job.append(description, 0.0, TextFormat::simple(small, weak));
+    let galley = WidgetText::Galley(ui.fonts_mut(|fonts| fonts.layout_job(job)));
+    match label {
+        Some(label) => *label = galley,
+        None => atoms.push_right(galley),
+    }
+}
+
+/// The width of the dropdown arrow after the editor.
+fn dropdown_arrow_width(ui: &egui::Ui) -> f32 {
+    ui.spacing().icon_width + ui.spacing().button_padding.x * 2.0
+}
+
+/// The width of the clear button inside the editor.
+fn clear_button_width(ui: &egui::Ui) -> f32 {
+    ui.spacing().icon_width + ui.spacing().button_padding.x
+}
+
+/// Displays the button clearing the value inside the trailing edge of `edit_rect`,
+/// which is the left edge if the text is `rtl`, returning whether it was clicked.
+fn show_clear_button(ui: &egui::Ui, id: egui::Id, edit_rect: Rect, rtl: bool) -> bool {
+    let width = clear_button_width(ui);
+    let rect = if rtl {
+        Rect::from_min_max(
+            edit_rect.left_top(),
+            egui::pos2(edit_rect.left() + width, edit_rect.bottom()),
+        )
+    } else {
+        Rect::from_min_max(
+            egui::pos2(edit_rect.right() - width, edit_rect.top()),
+            edit_rect.right_bottom(),
+        )
+    };
+    let response = ui.interact(rect, id, Sense::click());
+    response.widget_info(|| WidgetInfo::labeled(WidgetType::Button, ui.is_enabled(), "Clear"));
+
+    if ui.is_rect_visible(rect) {
+        let visuals = ui.style().interact(&response);
+        let icon =
+            Rect::from_center_size(rect.center(), egui::Vec2::splat(ui.spacing().icon_width * 0.5));
+        let stroke = Stroke::new(visuals.fg_stroke.width.max(1.0), visuals.fg_stroke.color);
+        ui.painter().line_segment([icon.left_top(), icon.right_bottom()], stroke);
+        ui.painter().line_segment([icon.right_top(), icon.left_bottom()], stroke);
+    }
+    response.clicked()
+}
+
+/// Whether the first strongly directional character of `text` is right-to-left,
+/// like the `dir="auto"` heuristic of HTML.
+///
+/// Neutral characters such as digits, spaces and punctuation are skipped,
+/// as are the contents of directional isolates,
+/// and the left-to-right, right-to-left and Arabic letter marks are honored.
+/// egui neither reorders laid-out text nor reports its direction,
+/// so letters are looked up in the main right-to-left blocks.
+fn starts_with_rtl(text: &str) -> bool {
+    let mut isolates = 0_usize;
+    for c in text.chars() {
+        match c {
+            // Left-to-right, right-to-left and first strong isolates
+            '\u{2066}'..='\u{2068}' => isolates += 1,
+            // Pop directional isolate
+            '\u{2069}' => isolates = isolates.saturating_sub(1),
+            _ if isolates > 0 => {}
+            // Left-to-right mark
+            '\u{200E}' => return false,
+            // Right-to-left mark and Arabic letter mark
+            '\u{200F}' | '\u{061C}' => return true,
+            _ if c.is_alphabetic() => return is_rtl_letter(c),
+            _ => {}
         }
     }
+    false
+}
+
+/// Whether the letter `c` belongs to a right-to-left script.
+fn is_rtl_letter(c: char) -> bool {
+    matches!(
+        u32::from(c),
+        // Hebrew, Arabic, Syriac, Thaana, NKo, Samaritan, Mandaic and Arabic extensions
+        0x0590..=0x08FF
+            // Hebrew and Arabic presentation forms
+            | 0xFB1D..=0xFDFF
+            | 0xFE70..=0xFEFF
+            // Historic right-to-left scripts and Arabic mathematical symbols
+            | 0x1_0800..=0x1_0FFF
+            | 0x1_E800..=0x1_EFFF
+    )
 }
 
 #[derive(Hash)]
 enum Ids {
-    /// Temp data key for the `TextEdit` buffer. Value has type `String`.
+    /// Temp data key for the `TextEdit` buffer.
+    /// Value has the type of the `B` parameter of `EditableComboBox`.
     TextBuf,
     /// ID salt for showing the dropdown popup.
     Popup,
     /// ID salt for the scroll area inside the popup.
     Scroll,
+    /// ID of the `Ui` containing the scroll area,
+    /// used to derive a stable scroll area ID independent of the popup layout.
+    ScrollScope,
     /// Temp data key for storing the keyboad cursor position.
     /// Value has type `CursorPos`.
     CursorPos,
+    /// Temp data key for the pending hover intent in the popup.
+    /// Value has type `HoverIntent`.
+    HoverIntent,
+    /// Temp data key for whether the user is creating a new value
+    /// in `show_with_add_new`. Value has type `bool`.
+    Creating,
+    /// Temp data key for the inline rename state. Value has type `RenameState`.
+    Rename,
+    /// ID of the inline rename editor.
+    RenameEdit,
+    /// ID of the main editor.
+    Editor,
+    /// Temp data key for the width of the popup contents in the last frame.
+    /// Value has type `f32`.
+    PopupWidth,
+    /// Temp data key for the scroll offset of the popup.
+    /// Value has type `ScrollOffset`.
+    ScrollOffset,
+    /// Temp data key for the history entry shown in the editor.
+    /// Value has type `usize`.
+    HistoryPos,
+    /// Temp data key for the measured height of each row by source index.
+    /// Value has type `BTreeMap<usize, f32>`.
+    RowHeights,
+    /// Temp data key for the progress of filtering with a budget.
+    /// Value has type `FilterProgress`.
+    FilterProgress,
+    /// Temp data key for the reason the last selection was denied by the commit guard.
+    /// Value has type `String`.
+    Denial,
+    /// Temp data key for whether focus is being returned to the main editor
+    /// after a selection, which should not clear the text. Value has type `bool`.
+    Refocus,
+    /// Temp data key for the pixels per point at which `PopupWidth` and `RowHeights`
+    /// were measured. Value has type `f32`.
+    MeasuredPixelsPerPoint,
+    /// Temp data key for the rows fading into the popup.
+    /// Value has type `RowFades`.
+    RowFades,
+    /// ID of the `Ui` containing the editor in `show_multi`,
+    /// used to derive a stable editor ID independent of the number of chips.
+    MultiEditScope,
+    /// Temp data key for the state of the select all footer of `show_multi`.
+    /// Value has type `FooterState`.
+    MultiFooter,
+    /// Temp data key for whether the parent has been scrolled to the opened popup.
+    /// Value has type `bool`.
+    ScrolledToPopup,
+    /// Persisted data key for the values committed recently, oldest first.
+    /// Value has type `Vec<(String, Option<usize>, f64)>`.
+    SelectionHistory,
+    /// ID of the clear button inside the editor.
+    ClearButton,
+    /// Temp data key for whether the popup is closed while the editor keeps focus
+    /// inside a modal. Value has type `bool`.
+    Dismissed,
 }
 
 impl Ids {
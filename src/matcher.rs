@@ -0,0 +1,379 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use egui::text::LayoutJob;
+use egui::{Atom, IntoAtoms};
+
+use crate::flatten::flatten_control_chars;
+use crate::highlight::substring_match;
+use crate::{FilterResult, FilterState, MatchHighlight, ValueOption, fuzzy};
+
+/// Matches the typed text against the text of a candidate option.
+///
+/// Implement this trait to rank options with an existing fuzzy matching library.
+/// [`SubstringMatcher`] and [`SubsequenceMatcher`] are built in,
+/// and the adapters `NucleoMatcher` and `SkimMatcher`
+/// are available with the `nucleo` and `fuzzy-matcher` crate features.
+/// Options are matched with a matcher by wrapping them in [`MatchedOption`].
+pub trait Matcher {
+    /// Matches `text` against `candidate`,
+    /// returning `None` if the candidate does not match.
+    fn match_text(&self, text: &str, candidate: &str) -> Option<TextMatch>;
+
+    /// Whether the scores of this matcher rank the options.
+    ///
+    /// Options matched by an unscored matcher keep their original order.
+    fn is_scored(&self) -> bool { true }
+}
+
+/// A successful match returned by [`Matcher::match_text`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TextMatch {
+    /// The relevance of the match. Higher scores are displayed first.
+    pub score:     f32,
+    /// The byte ranges of the matched characters in the candidate, used for highlighting.
+    ///
+    /// The ranges must be sorted, non-overlapping and on char boundaries.
+    pub positions: Vec<Range<usize>>,
+}
+
+/// Matches candidates containing the typed text as a case-insensitive substring.
+///
+/// Newlines and other control characters in the candidate are matched as spaces.
+/// All matches have the same score, so the options are displayed in their original order.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SubstringMatcher;
+
+impl Matcher for SubstringMatcher {
+    fn match_text(&self, text: &str, candidate: &str) -> Option<TextMatch> {
+        if text.is_empty() {
+            return Some(TextMatch::default());
+        }
+        if !flatten_control_chars(candidate).to_lowercase().contains(&text.to_lowercase()) {
+            return None;
+        }
+        let positions = substring_match(candidate, text).into_iter().collect();
+        Some(TextMatch { score: 0.0, positions })
+    }
+
+    fn is_scored(&self) -> bool { false }
+}
+
+/// Matches candidates containing the typed text as a case-insensitive subsequence,
+/// scored by [`fuzzy_score`](crate::fuzzy_score).
+///
+/// This is the matcher used by [`FuzzyOption`](crate::FuzzyOption).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SubsequenceMatcher;
+
+impl Matcher for SubsequenceMatcher {
+    fn match_text(&self, text: &str, candidate: &str) -> Option<TextMatch> {
+        let mut score = 0;
+        let mut positions = Vec::<Range<usize>>::new();
+        fuzzy::fuzzy_match(candidate, text, |range, word_start, consecutive| {
+            score += fuzzy::char_score(word_start, consecutive);
+            match positions.last_mut() {
+                Some(last) if consecutive => last.end = range.end,
+                _ => positions.push(range),
+            }
+        })?;
+        #[expect(clippy::cast_precision_loss)] // scores are small
+        Some(TextMatch { score: score as f32, positions })
+    }
+}
+
+/// How the typed text is matched against the editable text of the options,
+/// set with [`EditableComboBox::match_mode`](crate::EditableComboBox::match_mode).
+///
+/// Custom options can filter with the same modes through [`FilterResult::from_match_mode`].
+/// All options match empty text in every mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MatchMode {
+    /// The option contains the text, ignoring case, like the built-in options.
+    #[default]
+    CaseInsensitiveSubstring,
+    /// The option contains the text with the same case.
+    CaseSensitiveSubstring,
+    /// The option starts with the text, ignoring case, e.g. for code completion.
+    PrefixOnly,
+    /// The option is the whole text, ignoring case.
+    Exact,
+}
+
+impl Matcher for MatchMode {
+    fn match_text(&self, text: &str, candidate: &str) -> Option<TextMatch> {
+        if text.is_empty() {
+            return Some(TextMatch::default());
+        }
+        let range = match self {
+            MatchMode::CaseInsensitiveSubstring => {
+                return SubstringMatcher.match_text(text, candidate);
+            }
+            MatchMode::CaseSensitiveSubstring => {
+                let start = flatten_control_chars(candidate).find(text)?;
+                start..start + text.len()
+            }
+            MatchMode::PrefixOnly => {
+                let start = candidate.get(..text.len())?;
+                (start.to_lowercase() == text.to_lowercase()).then_some(0..text.len())?
+            }
+            MatchMode::Exact => {
+                (candidate.to_lowercase() == text.to_lowercase()).then_some(0..candidate.len())?
+            }
+        };
+        // Control characters may have been flattened into spaces of another length.
+        let valid =
+            candidate.is_char_boundary(range.start) && candidate.is_char_boundary(range.end);
+        let positions = valid.then_some(range).into_iter().collect();
+        Some(TextMatch { score: 0.0, positions })
+    }
+
+    fn is_scored(&self) -> bool { false }
+}
+
+/// Wraps an option to match it with a [`Matcher`].
+///
+/// The option matches exactly if its [`editable_text`](ValueOption::editable_text)
+/// equals the typed text, and is otherwise [scored](FilterResult::Scored) by the matcher,
+/// or matches [partially](FilterResult::Partial) if the matcher is not
+/// [scored](Matcher::is_scored).
+/// Options without an editable text, and options matching beyond their editable text
+/// such as by the keywords of [`Keyworded`](crate::Keyworded), fall back to their own filter.
+///
+/// # Example
+/// ```
+/// use egui_editable_combobox::{ComboModel, MatchedOption, SubsequenceMatcher};
+///
+/// let matcher = SubsequenceMatcher;
+/// let mut model = ComboModel::<String, _>::new(
+///     ["Africa", "Eurasia"].map(|option| MatchedOption::new(&matcher, option)),
+/// );
+/// model.input_text("esa");
+/// assert_eq!(model.filtered(), [1]);
+/// ```
+#[derive(Debug)]
+pub struct MatchedOption<'m, M: ?Sized, Opt> {
+    /// The matcher shared by all options.
+    pub matcher: &'m M,
+    /// The wrapped option.
+    pub option:  Opt,
+}
+
+impl<'m, M: ?Sized, Opt> MatchedOption<'m, M, Opt> {
+    /// Wraps `option` to be matched with `matcher`.
+    pub fn new(matcher: &'m M, option: Opt) -> Self { Self { matcher, option } }
+}
+
+impl<M: ?Sized, Opt: Clone> Clone for MatchedOption<'_, M, Opt> {
+    fn clone(&self) -> Self { Self { matcher: self.matcher, option: self.option.clone() } }
+}
+
+impl<V, M: Matcher + ?Sized, Opt: ValueOption<V>> ValueOption<V> for MatchedOption<'_, M, Opt> {
+    fn filter_by_text(&self, text: &str, state: FilterState) -> FilterResult {
+        filter_matched(self.matcher, &self.option, text, state)
+    }
+
+    fn display(&self, text: &str) -> impl IntoAtoms<'_> { self.option.display(text) }
+
+    /// Highlights the positions matched by the matcher,
+    /// if the wrapped option is displayed with highlighting at all.
+    fn display_highlighted(&self, text: &str, highlight: &MatchHighlight) -> Option<LayoutJob> {
+        highlight_matched(self.matcher, &self.option, text, highlight)
+    }
+
+    fn into_value(self, text: &str) -> V { self.option.into_value(text) }
+
+    fn equals_value(&self, value: &V, text: &str) -> bool { self.option.equals_value(value, text) }
+
+    fn editable_text(&self, text: &str) -> Option<String> { self.option.editable_text(text) }
+
+    fn icon(&self) -> Option<Atom<'_>> { self.option.icon() }
+
+    fn description(&self) -> Option<&str> { self.option.description() }
+
+    fn is_separator(&self) -> bool { self.option.is_separator() }
+
+    fn is_enabled(&self, text: &str) -> bool { self.option.is_enabled(text) }
+}
+
+/// Filters `option` by matching its editable text with `matcher`,
+/// as described in [`MatchedOption`].
+pub(crate) fn filter_matched<V, M: Matcher + ?Sized, Opt: ValueOption<V>>(
+    matcher: &M,
+    option: &Opt,
+    text: &str,
+    state: FilterState,
+) -> FilterResult {
+    let Some(full) = option.editable_text(text) else { return option.filter_by_text(text, state) };
+    if full == text {
+        return FilterResult::Exact;
+    }
+    match matcher.match_text(text, &full) {
+        Some(_) if text.is_empty() || !matcher.is_scored() => FilterResult::Partial,
+        Some(text_match) => FilterResult::Scored(text_match.score),
+        // A substring of the editable text is left to the matcher,
+        // but keywords or custom logic of the option still match.
+        None if matches!(
+            FilterResult::from_case_insensitive_substring(&full, text),
+            FilterResult::None
+        ) =>
+        {
+            option.filter_by_text(text, state)
+        }
+        None => FilterResult::None,
+    }
+}
+
+/// Highlights the positions of the editable text of `option` matched by `matcher`,
+/// if `option` is displayed with highlighting at all.
+pub(crate) fn highlight_matched<V, M: Matcher + ?Sized, Opt: ValueOption<V>>(
+    matcher: &M,
+    option: &Opt,
+    text: &str,
+    highlight: &MatchHighlight,
+) -> Option<LayoutJob> {
+    let inner = option.display_highlighted(text, highlight)?;
+    let Some(full) = option.editable_text(text) else { return Some(inner) };
+    let text_match = matcher.match_text(text, &full)?;
+    Some(highlight.layout(&full, &text_match.positions))
+}
+
+/// Converts sorted, deduplicated char indices of `candidate` into merged byte ranges.
+#[cfg(any(feature = "nucleo", feature = "fuzzy-matcher"))]
+fn char_ranges(candidate: &str, indices: impl IntoIterator<Item = usize>) -> Vec<Range<usize>> {
+    let mut indices = indices.into_iter().peekable();
+    let mut ranges = Vec::<Range<usize>>::new();
+    for (char_index, (byte_index, ch)) in candidate.char_indices().enumerate() {
+        if indices.next_if_eq(&char_index).is_none() {
+            continue;
+        }
+        let range = byte_index..byte_index + ch.len_utf8();
+        match ranges.last_mut() {
+            Some(last) if last.end == range.start => last.end = range.end,
+            _ => ranges.push(range),
+        }
+    }
+    ranges
+}
+
+/// A [`Matcher`] adapter for the [`nucleo-matcher`](nucleo_matcher) crate.
+///
+/// The typed text is parsed as a [`Pattern`](nucleo_matcher::pattern::Pattern)
+/// with smart case matching and normalization.
+/// The parsed pattern is cached until the typed text changes.
+#[cfg(feature = "nucleo")]
+pub struct NucleoMatcher {
+    matcher: core::cell::RefCell<nucleo_matcher::Matcher>,
+    pattern: core::cell::RefCell<Option<(String, nucleo_matcher::pattern::Pattern)>>,
+}
+
+#[cfg(feature = "nucleo")]
+impl NucleoMatcher {
+    /// Creates a matcher with the given configuration.
+    #[must_use]
+    pub fn new(config: nucleo_matcher::Config) -> Self {
+        Self {
+            matcher: core::cell::RefCell::new(nucleo_matcher::Matcher::new(config)),
+            pattern: core::cell::RefCell::default(),
+        }
+    }
+}
+
+#[cfg(feature = "nucleo")]
+impl Default for NucleoMatcher {
+    fn default() -> Self { Self::new(nucleo_matcher::Config::DEFAULT) }
+}
+
+#[cfg(feature = "nucleo")]
+impl Matcher for NucleoMatcher {
+    fn match_text(&self, text: &str, candidate: &str) -> Option<TextMatch> {
+        use nucleo_matcher::pattern::{CaseMatching, Normalization, Pattern};
+
+        let mut pattern = self.pattern.borrow_mut();
+        if pattern.as_ref().is_none_or(|(parsed, _)| parsed != text) {
+            let parsed = Pattern::parse(text, CaseMatching::Smart, Normalization::Smart);
+            *pattern = Some((text.into(), parsed));
+        }
+        let (_, pattern) = pattern.as_ref()?;
+
+        let mut buf = Vec::new();
+        let mut indices = Vec::new();
+        let haystack = nucleo_matcher::Utf32Str::new(candidate, &mut buf);
+        let score = pattern.indices(haystack, &mut self.matcher.borrow_mut(), &mut indices)?;
+        indices.sort_unstable();
+        indices.dedup();
+        let positions = char_ranges(candidate, indices.into_iter().map(|index| index as usize));
+        #[expect(clippy::cast_precision_loss)] // scores are small
+        Some(TextMatch { score: score as f32, positions })
+    }
+}
+
+/// A [`Matcher`] adapter for the skim algorithm of the [`fuzzy-matcher`](fuzzy_matcher) crate.
+#[cfg(feature = "fuzzy-matcher")]
+#[derive(Default)]
+pub struct SkimMatcher(pub fuzzy_matcher::skim::SkimMatcherV2);
+
+#[cfg(feature = "fuzzy-matcher")]
+impl Matcher for SkimMatcher {
+    fn match_text(&self, text: &str, candidate: &str) -> Option<TextMatch> {
+        use fuzzy_matcher::FuzzyMatcher;
+
+        let (score, indices) = self.0.fuzzy_indices(candidate, text)?;
+        let positions = char_ranges(candidate, indices);
+        #[expect(clippy::cast_precision_loss)] // scores are small
+        Some(TextMatch { score: score as f32, positions })
+    }
+}
+
+/// A [`Matcher`] interpreting the typed text as a case-insensitive regular expression
+/// of the [`regex`] crate, e.g. `^get_.*_id$` to find symbols in a developer tool.
+///
+/// The pattern matches anywhere in the candidate unless it is anchored with `^` and `$`.
+/// While the typed text is not a valid pattern, e.g. `foo(` before the group is closed,
+/// it is matched as a literal substring like [`SubstringMatcher`] instead.
+/// The compiled pattern is cached until the typed text changes.
+///
+/// [`EditableComboBox::regex_filter`](crate::EditableComboBox::regex_filter)
+/// filters all options of a combo box with this matcher.
+///
+/// # Example
+/// ```
+/// use egui_editable_combobox::{ComboModel, MatchedOption, RegexMatcher};
+///
+/// let matcher = RegexMatcher::default();
+/// let mut model = ComboModel::<String, _>::new(
+///     ["get_user_id", "set_user_id", "get_name"]
+///         .map(|option| MatchedOption::new(&matcher, option)),
+/// );
+/// model.input_text("^get_.*id$");
+/// assert_eq!(model.filtered(), [0]);
+/// model.input_text("USER_ID$");
+/// assert_eq!(model.filtered(), [0, 1]);
+/// ```
+#[cfg(feature = "regex")]
+#[derive(Debug, Default)]
+pub struct RegexMatcher {
+    pattern: core::cell::RefCell<Option<(String, Option<regex::Regex>)>>,
+}
+
+#[cfg(feature = "regex")]
+impl Matcher for RegexMatcher {
+    fn match_text(&self, text: &str, candidate: &str) -> Option<TextMatch> {
+        let mut pattern = self.pattern.borrow_mut();
+        if pattern.as_ref().is_none_or(|(compiled, _)| compiled != text) {
+            let regex = regex::RegexBuilder::new(text).case_insensitive(true).build().ok();
+            *pattern = Some((text.into(), regex));
+        }
+        let Some((_, Some(regex))) = pattern.as_ref() else {
+            return SubstringMatcher.match_text(text, candidate);
+        };
+
+        let mut matches = regex.find_iter(candidate).peekable();
+        matches.peek()?;
+        let positions = matches.map(|found| found.range()).filter(|range| !range.is_empty());
+        Some(TextMatch { score: 0.0, positions: positions.collect() })
+    }
+
+    fn is_scored(&self) -> bool { false }
+}
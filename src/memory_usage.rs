@@ -0,0 +1,109 @@
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::hash::Hash;
+use core::iter::Sum;
+use core::ops::Add;
+
+use egui::util::IdTypeMap;
+
+use crate::{
+    CursorPos, EditableComboBox, FilterProgress, HoverIntent, Ids, RenameState, RowFades,
+    ScrollOffset,
+};
+
+/// The state kept in [`egui::Memory`] by a combo box,
+/// as returned by [`EditableComboBox::memory_usage`].
+///
+/// The usages of multiple combo boxes can be added up with `+` or [`Iterator::sum`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemoryUsage {
+    /// The number of entries in [`egui::Memory::data`].
+    pub entries:               usize,
+    /// The number of options whose filter results are cached
+    /// by a [`filter_budget`](EditableComboBox::filter_budget).
+    pub cached_filter_results: usize,
+    /// The number of rows whose measured heights are cached.
+    pub measured_rows:         usize,
+    /// The number of values in the [selection history](EditableComboBox::selection_history),
+    /// which also holds the [`HistoryMode`](crate::HistoryMode) history
+    /// and the [recent values](EditableComboBox::remember_recent).
+    pub history_entries:       usize,
+}
+
+impl Add for MemoryUsage {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            entries:               self.entries + other.entries,
+            cached_filter_results: self.cached_filter_results + other.cached_filter_results,
+            measured_rows:         self.measured_rows + other.measured_rows,
+            history_entries:       self.history_entries + other.history_entries,
+        }
+    }
+}
+
+impl Sum for MemoryUsage {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self { iter.fold(Self::default(), Add::add) }
+}
+
+impl EditableComboBox<'_> {
+    /// Reports the state kept in egui memory by the combo box with the ID `id_salt`,
+    /// so that apps showing hundreds of combo boxes can monitor their memory use.
+    ///
+    /// Only a text buffer of the default `String` type is counted.
+    ///
+    /// # Example
+    /// ```
+    /// use egui_editable_combobox::{EditableComboBox, MemoryUsage};
+    ///
+    /// # let ctx = egui::Context::default();
+    /// let usage: MemoryUsage = ["fruit", "vegetable"]
+    ///     .into_iter()
+    ///     .map(|id_salt| EditableComboBox::memory_usage(&ctx, id_salt))
+    ///     .sum();
+    /// println!("{usage:?}");
+    /// ```
+    pub fn memory_usage(ctx: &egui::Context, id_salt: impl Hash) -> MemoryUsage {
+        let id_salt = egui::Id::new(id_salt);
+        ctx.memory_mut(|mem| usage(&mut mem.data, id_salt))
+    }
+}
+
+fn usage(data: &mut IdTypeMap, id_salt: egui::Id) -> MemoryUsage {
+    fn get<T: 'static + Clone>(data: &IdTypeMap, id: egui::Id) -> Option<T> { data.get_temp(id) }
+    let id = |key: Ids| key.id(id_salt);
+
+    let flags = [
+        get::<String>(data, id(Ids::TextBuf)).is_some(),
+        get::<CursorPos>(data, id(Ids::CursorPos)).is_some(),
+        get::<HoverIntent>(data, id(Ids::HoverIntent)).is_some(),
+        get::<bool>(data, id(Ids::Creating)).is_some(),
+        get::<RenameState>(data, id(Ids::Rename)).is_some(),
+        get::<f32>(data, id(Ids::PopupWidth)).is_some(),
+        get::<ScrollOffset>(data, id(Ids::ScrollOffset)).is_some(),
+        get::<usize>(data, id(Ids::HistoryPos)).is_some(),
+        get::<String>(data, id(Ids::Denial)).is_some(),
+        get::<bool>(data, id(Ids::Refocus)).is_some(),
+        get::<f32>(data, id(Ids::MeasuredPixelsPerPoint)).is_some(),
+        get::<RowFades>(data, id(Ids::RowFades)).is_some(),
+        get::<bool>(data, id(Ids::ScrolledToPopup)).is_some(),
+        get::<bool>(data, id(Ids::Dismissed)).is_some(),
+    ];
+    let selection_history =
+        data.get_persisted::<Vec<(String, Option<usize>, f64)>>(id(Ids::SelectionHistory));
+    let row_heights = get::<BTreeMap<usize, f32>>(data, id(Ids::RowHeights));
+    let progress = get::<FilterProgress>(data, id(Ids::FilterProgress));
+
+    let entries = flags.into_iter().filter(|&present| present).count()
+        + usize::from(selection_history.is_some())
+        + usize::from(row_heights.is_some())
+        + usize::from(progress.is_some());
+    MemoryUsage {
+        entries,
+        cached_filter_results: progress.map_or(0, |progress| progress.matches.len()),
+        measured_rows: row_heights.map_or(0, |heights| heights.len()),
+        history_entries: selection_history.map_or(0, |history| history.len()),
+    }
+}
@@ -0,0 +1,658 @@
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+use crate::matcher::filter_matched;
+use crate::{
+    EqualityPolicy, FilterResult, FilterState, MatchMode, Matcher, SubsequenceMatcher, Value,
+    ValueOption,
+};
+
+/// The filtering and keyboard logic of an [`EditableComboBox`](crate::EditableComboBox)
+/// without any egui rendering, for test-driving the behavior of options.
+///
+/// The model holds the text typed by the user, the rows of the popup filtered by it
+/// and the keyboard cursor among them,
+/// following the same rules as the popup of the combo box:
+/// separators collapse and are skipped by the cursor with disabled options,
+/// and the [`pin_selected`](Self::pin_selected), [`remember_recent`](Self::remember_recent),
+/// [`min_chars`](Self::min_chars) and [`match_mode`](Self::match_mode) settings
+/// arrange the rows like those of the combo box.
+///
+/// The model filters all options at once like a combo box without a
+/// [`filter_budget`](crate::EditableComboBox::filter_budget),
+/// and does not model the editing of the text beyond Backspace.
+///
+/// The combo box does not use the model:
+/// it filters the options passed in each frame instead of owning them,
+/// so the model is a separate reimplementation on top of the same filtering and cursor helpers,
+/// and its behavior is tested against the popup for the same inputs.
+///
+/// # Example
+/// ```
+/// use egui::Key;
+/// use egui_editable_combobox::ComboModel;
+///
+/// let mut model = ComboModel::<String, _>::new(["Africa", "America", "Antarctica"]);
+/// model.input_text("an");
+/// assert_eq!(model.filtered(), [2]);
+///
+/// model.input_text("");
+/// model.key(Key::ArrowDown);
+/// assert_eq!(model.cursor(), Some(1));
+/// assert_eq!(model.key(Key::Enter).as_deref(), Some("America"));
+/// ```
+pub struct ComboModel<V, Opt> {
+    options:         Vec<Opt>,
+    text:            String,
+    /// The source indices of the options matching `text`, in display order.
+    filtered:        Vec<usize>,
+    /// The source indices of the options the cursor can be placed on, in display order.
+    cursor_rows:     Vec<usize>,
+    /// The source index of the cursor option.
+    cursor:          Option<usize>,
+    /// The source index of the option equal to the current value.
+    selected:        Option<usize>,
+    /// The editable texts of the committed values, most recent last.
+    recent:          Vec<String>,
+    arrangement:     Arrangement,
+    remember_recent: usize,
+    min_chars:       usize,
+    matcher:         Option<Box<dyn Matcher>>,
+    wrap:            bool,
+    page_rows:       usize,
+    _value:          PhantomData<fn() -> V>,
+}
+
+impl<V, Opt> ComboModel<V, Opt>
+where
+    V: Value,
+    Opt: ValueOption<V>,
+{
+    /// Create a model with the given options and an empty text.
+    pub fn new(options: impl IntoIterator<Item = Opt>) -> Self {
+        let mut model = Self {
+            options:         options.into_iter().collect(),
+            text:            String::new(),
+            filtered:        Vec::new(),
+            cursor_rows:     Vec::new(),
+            cursor:          None,
+            selected:        None,
+            recent:          Vec::new(),
+            arrangement:     Arrangement {
+                reverse_order: false,
+                pin_selected:  false,
+                sorted:        false,
+            },
+            remember_recent: 0,
+            min_chars:       0,
+            matcher:         None,
+            wrap:            true,
+            page_rows:       10,
+            _value:          PhantomData,
+        };
+        model.refilter();
+        model
+    }
+
+    /// Display the options in reverse order,
+    /// like [`EditableComboBox::reverse_order`](crate::EditableComboBox::reverse_order).
+    #[must_use]
+    pub fn reverse_order(mut self, reverse_order: bool) -> Self {
+        self.arrangement.reverse_order = reverse_order;
+        self.refilter();
+        self
+    }
+
+    /// Always display the option equal to the value passed to [`open`](Self::open)
+    /// or last committed as the first row,
+    /// like [`EditableComboBox::pin_selected`](crate::EditableComboBox::pin_selected).
+    #[must_use]
+    pub fn pin_selected(mut self, pin_selected: bool) -> Self {
+        self.arrangement.pin_selected = pin_selected;
+        self.refilter();
+        self
+    }
+
+    /// List the options of the last `count` committed values first while the text is empty,
+    /// like [`EditableComboBox::remember_recent`](crate::EditableComboBox::remember_recent).
+    #[must_use]
+    pub fn remember_recent(mut self, count: usize) -> Self {
+        self.remember_recent = count;
+        self.refilter();
+        self
+    }
+
+    /// Display no options until at least `min_chars` characters are typed,
+    /// like [`InteractionConfig::min_chars`](crate::InteractionConfig::min_chars).
+    #[must_use]
+    pub fn min_chars(mut self, min_chars: usize) -> Self {
+        self.min_chars = min_chars;
+        self.refilter();
+        self
+    }
+
+    /// Match the options by their editable text according to `mode`,
+    /// like [`EditableComboBox::match_mode`](crate::EditableComboBox::match_mode).
+    #[must_use]
+    pub fn match_mode(mut self, mode: MatchMode) -> Self {
+        self.matcher = Some(Box::new(mode));
+        self.refilter();
+        self
+    }
+
+    /// Whether Up and Down wrap around at the ends of the list,
+    /// like [`InteractionConfig::wrap_navigation`](crate::InteractionConfig::wrap_navigation).
+    #[must_use]
+    pub fn wrap_navigation(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    /// The number of rows visible in the popup, by which Page Up and Page Down move the cursor.
+    ///
+    /// The combo box derives this from the height of the popup and its rows.
+    /// Defaults to 10.
+    #[must_use]
+    pub fn page_rows(mut self, rows: usize) -> Self {
+        self.page_rows = rows.max(1);
+        self
+    }
+
+    /// Clears the text and moves the cursor to the first option equal to `value`,
+    /// like focusing the editor of the combo box.
+    pub fn open(&mut self, value: &V) {
+        let mut pass = FilterPass::default();
+        self.selected = self.options.iter().enumerate().find_map(|(source_index, option)| {
+            pass.filter(option, "", Some(value), EqualityPolicy::FirstEqual, None)
+                .equals
+                .then_some(source_index)
+        });
+        self.cursor = self.selected;
+        self.text.clear();
+        self.refilter();
+    }
+
+    /// The text typed by the user.
+    #[must_use]
+    pub fn text(&self) -> &str { &self.text }
+
+    /// Replaces the text typed by the user and filters the options by it.
+    pub fn input_text(&mut self, text: &str) {
+        self.text.clear();
+        self.text.push_str(text);
+        self.refilter();
+    }
+
+    /// The source indices of the options matching the text, in display order.
+    ///
+    /// Separators are not included.
+    #[must_use]
+    pub fn filtered(&self) -> &[usize] { &self.filtered }
+
+    /// The source index of the option under the keyboard cursor,
+    /// or `None` if no enabled options match the text.
+    #[must_use]
+    pub fn cursor(&self) -> Option<usize> {
+        let cursor = initial_cursor(self.cursor, &self.cursor_rows, self.arrangement.sorted);
+        let index =
+            displayed_cursor_index(&self.cursor_rows, cursor, self.arrangement.reverse_order);
+        self.cursor_rows.get(index).copied()
+    }
+
+    fn refilter(&mut self) {
+        let mut pass = FilterPass::default();
+        let mut rows = Vec::new();
+        let too_short = self.text.chars().count() < self.min_chars;
+        for (source_index, option) in self.options.iter().enumerate().filter(|_| !too_short) {
+            if option.is_separator() {
+                if separator_fits(&rows) {
+                    rows.push(DisplayedOption::new(source_index, option, true));
+                }
+                continue;
+            }
+            let filtered = pass.filter::<V, _>(
+                option,
+                &self.text,
+                None,
+                EqualityPolicy::Never,
+                self.matcher.as_deref(),
+            );
+            let equals = self.selected == Some(source_index);
+            if filtered.matches || (self.arrangement.pin_selected && equals) {
+                rows.push(DisplayedOption {
+                    equals,
+                    relevance: filtered.relevance,
+                    ..DisplayedOption::new(source_index, option, false)
+                });
+            }
+        }
+        self.arrangement.sorted = pass.scored;
+        self.arrangement.arrange(&mut rows);
+        if self.text.is_empty() {
+            let recent = self.recent.iter().rev().take(self.remember_recent);
+            move_recent_first(&mut rows, recent, |option| option.editable_text(""));
+        }
+
+        self.cursor_rows = cursor_rows(&rows, |option| option.is_enabled(&self.text));
+        self.filtered = rows
+            .iter()
+            .filter(|displayed| !displayed.separator)
+            .map(|displayed| displayed.source_index)
+            .collect();
+    }
+}
+
+impl<V, Opt> ComboModel<V, Opt>
+where
+    V: Value,
+    Opt: ValueOption<V> + Clone,
+{
+    /// Handles a key pressed while the editor is focused.
+    ///
+    /// Up, Down, Home, End, Page Up and Page Down move the cursor,
+    /// Backspace deletes the last character,
+    /// and Enter commits the cursor option.
+    /// Returns the committed value, if any.
+    pub fn key(&mut self, key: egui::Key) -> Option<V> {
+        let motion = match key {
+            egui::Key::ArrowUp => Motion::Up,
+            egui::Key::ArrowDown => Motion::Down,
+            egui::Key::Home => Motion::Home,
+            egui::Key::End => Motion::End,
+            egui::Key::PageUp => Motion::PageUp(self.page_rows),
+            egui::Key::PageDown => Motion::PageDown(self.page_rows),
+            egui::Key::Enter => return self.commit(),
+            egui::Key::Backspace => {
+                self.text.pop();
+                self.refilter();
+                return None;
+            }
+            _ => return None,
+        };
+
+        let mut cursor = initial_cursor(self.cursor, &self.cursor_rows, self.arrangement.sorted);
+        move_cursor(
+            &mut cursor,
+            &self.cursor_rows,
+            &[],
+            self.arrangement.reverse_order,
+            self.wrap,
+            motion,
+        );
+        self.cursor = Some(cursor);
+        None
+    }
+
+    /// Converts the cursor option into the value and makes it the current value,
+    /// or returns `None` if no enabled options match the text.
+    pub fn commit(&mut self) -> Option<V> {
+        let source_index = self.cursor()?;
+        let value = self.options[source_index].clone().into_value(&self.text);
+        let entry = value.to_editable();
+        if self.remember_recent > 0 && !entry.is_empty() {
+            self.recent.retain(|old| *old != entry);
+            self.recent.push(entry);
+            let excess = self.recent.len().saturating_sub(self.remember_recent);
+            self.recent.drain(..excess);
+        }
+        self.selected = Some(source_index);
+        self.refilter();
+        Some(value)
+    }
+}
+
+/// The state of a single pass filtering the options in source order.
+#[derive(Clone, Default)]
+pub(crate) struct FilterPass {
+    /// The number of options matching the text so far.
+    pub(crate) matches:       usize,
+    pub(crate) had_exact:     bool,
+    /// The number of options claiming to be equal to the current value.
+    pub(crate) claimed_equal: usize,
+    /// Whether any option returned [`FilterResult::Scored`],
+    /// in which case the options are sorted by [`Relevance`].
+    pub(crate) scored:        bool,
+    /// The number of options matching the text as a fuzzy subsequence
+    /// while no option matched the text otherwise.
+    pub(crate) fuzzy_matches: usize,
+}
+
+/// The result of filtering a single option.
+pub(crate) struct FilteredOption {
+    /// Whether the option matches the text.
+    pub(crate) matches:   bool,
+    /// Whether the option is marked as selected.
+    pub(crate) equals:    bool,
+    pub(crate) relevance: Relevance,
+}
+
+/// How relevant a matching option is to the text, for sorting the options by score.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Relevance {
+    Exact,
+    Scored(f32),
+    Partial,
+}
+
+impl Relevance {
+    /// Orders more relevant options first.
+    pub(crate) fn cmp_more_relevant(self, other: Self) -> core::cmp::Ordering {
+        let key = |relevance| match relevance {
+            Relevance::Exact => (2, 0.0),
+            Relevance::Scored(score) => (1, score),
+            Relevance::Partial => (0, 0.0),
+        };
+        let (self_group, self_score) = key(self);
+        let (other_group, other_score) = key(other);
+        other_group.cmp(&self_group).then(other_score.total_cmp(&self_score))
+    }
+}
+
+impl FilterPass {
+    /// Filters the next option by `text`
+    /// and compares it with `selection` according to `equality`.
+    ///
+    /// Only the first option equal to `selection` is marked as selected.
+    /// If a `text_matcher` is given, it matches the editable text of the option
+    /// instead of the filter of the option itself.
+    pub(crate) fn filter<V, Opt: ValueOption<V>>(
+        &mut self,
+        option: &Opt,
+        text: &str,
+        selection: Option<&V>,
+        equality: EqualityPolicy,
+        text_matcher: Option<&dyn Matcher>,
+    ) -> FilteredOption {
+        let claims_equal = selection.is_some_and(|selection| match equality {
+            EqualityPolicy::EveryOption => option.equals_value(selection, text),
+            EqualityPolicy::FirstEqual => {
+                self.claimed_equal == 0 && option.equals_value(selection, text)
+            }
+            EqualityPolicy::Never => false,
+        });
+        let equals = claims_equal && self.claimed_equal == 0;
+        self.claimed_equal += usize::from(claims_equal);
+
+        let state = FilterState { prev_matches: self.matches, had_exact: self.had_exact };
+        let filter_result = match text_matcher {
+            Some(matcher) => filter_matched(matcher, option, text, state),
+            None => option.filter_by_text(text, state),
+        };
+        let (matches, relevance) = match filter_result {
+            FilterResult::Partial => (true, Relevance::Partial),
+            FilterResult::Scored(score) => {
+                self.scored = true;
+                (true, Relevance::Scored(score))
+            }
+            FilterResult::Exact => {
+                self.had_exact = true;
+                (true, Relevance::Exact)
+            }
+            FilterResult::None => (false, Relevance::Partial),
+        };
+        self.matches += usize::from(matches);
+
+        FilteredOption { matches, equals, relevance }
+    }
+
+    /// Matches the editable text of an `option` not matching `text`
+    /// with [`SubsequenceMatcher`], returning its score.
+    ///
+    /// Nothing is matched once any option has matched `text` in this pass,
+    /// so that fuzzy matching only costs while there is nothing else to display.
+    pub(crate) fn filter_fuzzy<V, Opt: ValueOption<V>>(
+        &mut self,
+        option: &Opt,
+        text: &str,
+    ) -> Option<f32> {
+        if self.matches > 0 || text.is_empty() {
+            return None;
+        }
+        let full = option.editable_text(text)?;
+        let text_match = SubsequenceMatcher.match_text(text, &full)?;
+        self.fuzzy_matches += 1;
+        Some(text_match.score)
+    }
+}
+
+/// A row of the popup: an option matching the text, or a separator between matching options.
+pub(crate) struct DisplayedOption<Opt> {
+    pub(crate) source_index: usize,
+    pub(crate) option:       Opt,
+    pub(crate) separator:    bool,
+    pub(crate) equals:       bool,
+    pub(crate) relevance:    Relevance,
+    /// Whether the option is listed in the "Recent" section at the top.
+    pub(crate) recent:       bool,
+}
+
+impl<Opt> DisplayedOption<Opt> {
+    pub(crate) fn new(source_index: usize, option: Opt, separator: bool) -> Self {
+        Self {
+            source_index,
+            option,
+            separator,
+            equals: false,
+            relevance: Relevance::Partial,
+            recent: false,
+        }
+    }
+}
+
+/// Whether a separator is displayed after `rows`.
+///
+/// Separators are only displayed between matching options,
+/// so consecutive separators collapse.
+pub(crate) fn separator_fits<Opt>(rows: &[DisplayedOption<Opt>]) -> bool {
+    rows.last().is_some_and(|last| !last.separator)
+}
+
+/// How the rows matching the text are ordered in the popup.
+pub(crate) struct Arrangement {
+    pub(crate) reverse_order: bool,
+    pub(crate) pin_selected:  bool,
+    /// Whether the rows are sorted by [`Relevance`].
+    pub(crate) sorted:        bool,
+}
+
+impl Arrangement {
+    /// Orders the rows collected in source order for display.
+    pub(crate) fn arrange<Opt>(&self, rows: &mut Vec<DisplayedOption<Opt>>) {
+        if rows.last().is_some_and(|last| last.separator) {
+            rows.pop();
+        }
+        if self.reverse_order {
+            rows.reverse();
+        }
+        let reordered = self.sorted
+            && !rows
+                .iter()
+                .filter(|displayed| !displayed.separator)
+                .is_sorted_by(|a, b| a.relevance.cmp_more_relevant(b.relevance).is_le());
+        if reordered {
+            // Separated groups are meaningless once the options are reordered.
+            rows.retain(|displayed| !displayed.separator);
+            // Stable, so equally relevant options stay in the displayed order.
+            rows.sort_by(|a, b| a.relevance.cmp_more_relevant(b.relevance));
+        }
+        if self.pin_selected
+            && let Some(selected) = rows.iter().position(|displayed| displayed.equals)
+        {
+            rows[..=selected].rotate_right(1);
+        }
+    }
+}
+
+/// Moves the options whose editable text is in `recent`, most recent first,
+/// to the top of `rows` and marks them as recent.
+pub(crate) fn move_recent_first<'a, Opt>(
+    rows: &mut Vec<DisplayedOption<Opt>>,
+    recent: impl IntoIterator<Item = &'a String>,
+    editable_text: impl Fn(&Opt) -> Option<String>,
+) {
+    let mut count = 0;
+    for entry in recent {
+        let position = rows[count..].iter().position(|displayed| {
+            !displayed.separator && editable_text(&displayed.option).as_ref() == Some(entry)
+        });
+        if let Some(position) = position {
+            rows[count + position].recent = true;
+            rows[count..=count + position].rotate_right(1);
+            count += 1;
+        }
+    }
+    // The section is already followed by a separator.
+    if count > 0 && rows.get(count).is_some_and(|displayed| displayed.separator) {
+        rows.remove(count);
+    }
+}
+
+/// The source indices of the rows the cursor can be placed on, in display order.
+///
+/// Separators and disabled options are skipped by the cursor.
+pub(crate) fn cursor_rows<Opt>(
+    rows: &[DisplayedOption<Opt>],
+    is_enabled: impl Fn(&Opt) -> bool,
+) -> Vec<usize> {
+    rows.iter()
+        .filter(|displayed| !displayed.separator && is_enabled(&displayed.option))
+        .map(|displayed| displayed.source_index)
+        .collect()
+}
+
+/// The positions in the [`cursor_rows`] of the first row of each group,
+/// as separated by the separator rows.
+pub(crate) fn group_starts<Opt>(
+    rows: &[DisplayedOption<Opt>],
+    is_enabled: impl Fn(&Opt) -> bool,
+) -> Vec<usize> {
+    let mut starts = Vec::new();
+    let mut count = 0;
+    let mut new_group = true;
+    for displayed in rows {
+        if displayed.separator {
+            new_group = true;
+        } else if is_enabled(&displayed.option) {
+            if new_group {
+                starts.push(count);
+                new_group = false;
+            }
+            count += 1;
+        }
+    }
+    starts
+}
+
+/// The source index of the cursor before it is moved in this frame:
+/// `cursor` if set, or the first row the cursor can be placed on.
+///
+/// Sorted rows are not in source order,
+/// so a cursor option filtered out moves to the most relevant row instead.
+pub(crate) fn initial_cursor(cursor: Option<usize>, cursor_rows: &[usize], sorted: bool) -> usize {
+    let first = cursor_rows.first().copied();
+    match cursor {
+        Some(cursor) if sorted && !cursor_rows.contains(&cursor) => first.unwrap_or(cursor),
+        Some(cursor) => cursor,
+        None => first.unwrap_or(0),
+    }
+}
+
+/// A keyboard motion of the cursor in the popup.
+#[derive(Clone, Copy)]
+pub(crate) enum Motion {
+    Home,
+    End,
+    Up,
+    Down,
+    /// Moves up by the given number of rows, stopping at the first row.
+    PageUp(usize),
+    /// Moves down by the given number of rows, stopping at the last row.
+    PageDown(usize),
+    /// Moves to the first row of the next group.
+    NextGroup,
+    /// Moves to the first row of the previous group.
+    PreviousGroup,
+}
+
+/// Whether the option at source index `a` is displayed before the one at `b`.
+pub(crate) fn displayed_before(a: usize, b: usize, reverse_order: bool) -> bool {
+    if reverse_order { a > b } else { a < b }
+}
+
+/// Returns the index in `displayed` of the cursor at source index `cursor`.
+///
+/// If the cursor option is not displayed,
+/// the cursor is displayed on the nearest option after it in source order,
+/// or clamped to the last row if no option is after it.
+/// Rows moved out of source order, like the [pinned](crate::EditableComboBox::pin_selected)
+/// and [recent](crate::EditableComboBox::remember_recent) rows, are found by their source index
+/// rather than by their position.
+pub(crate) fn displayed_cursor_index(
+    displayed: &[usize],
+    cursor: usize,
+    reverse_order: bool,
+) -> usize {
+    if let Some(index) = displayed.iter().position(|&source_index| source_index == cursor) {
+        return index;
+    }
+    let after = displayed
+        .iter()
+        .enumerate()
+        .filter(|&(_, &source_index)| displayed_before(cursor, source_index, reverse_order));
+    let nearest = after.reduce(|nearest, row| {
+        if displayed_before(*row.1, *nearest.1, reverse_order) { row } else { nearest }
+    });
+    nearest.map_or(displayed.len().saturating_sub(1), |(index, _)| index)
+}
+
+/// Moves the cursor at source index `cursor` among the `displayed` source indices,
+/// wrapping around at both ends if `wrap` is set.
+/// Page motions never wrap around.
+///
+/// `displayed` is in display order.
+/// A cursor option not displayed moves from the row it is
+/// [displayed on](displayed_cursor_index).
+/// `group_starts` are the positions in `displayed` where the groups start.
+pub(crate) fn move_cursor(
+    cursor: &mut usize,
+    displayed: &[usize],
+    group_starts: &[usize],
+    reverse_order: bool,
+    wrap: bool,
+    motion: Motion,
+) {
+    if displayed.is_empty() {
+        return;
+    }
+    let index = displayed_cursor_index(displayed, *cursor, reverse_order);
+    let target = match motion {
+        Motion::Home => displayed.first(),
+        Motion::End => displayed.last(),
+        Motion::Up => index
+            .checked_sub(1)
+            .and_then(|index| displayed.get(index))
+            .or_else(|| if wrap { displayed.last() } else { displayed.first() }),
+        Motion::Down => displayed
+            .get(index + 1)
+            .or_else(|| if wrap { displayed.first() } else { displayed.last() }),
+        Motion::PageUp(rows) => displayed.get(index.saturating_sub(rows)),
+        Motion::PageDown(rows) => displayed.get((index + rows).min(displayed.len() - 1)),
+        Motion::NextGroup => {
+            let next = group_starts.iter().find(|&&start| start > index);
+            next.or_else(|| group_starts.first().filter(|_| wrap))
+                .and_then(|&start| displayed.get(start))
+        }
+        Motion::PreviousGroup => {
+            let group = group_starts.partition_point(|&start| start <= index);
+            let previous = group.checked_sub(2).and_then(|group| group_starts.get(group));
+            previous
+                .or_else(|| group_starts.last().filter(|_| wrap))
+                .and_then(|&start| displayed.get(start))
+        }
+    };
+    if let Some(&target) = target {
+        *cursor = target;
+    }
+}
@@ -0,0 +1,403 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use alloc::{format, vec};
+use core::marker::PhantomData;
+use core::mem;
+
+use egui::text::LayoutJob;
+use egui::{Atom, Button, Event, IntoAtoms, TextBuffer, UiBuilder};
+
+use crate::value::IntoAtomsEither;
+use crate::{
+    EditableComboBox, FilterResult, FilterState, Ids, MatchHighlight, RowsState, Value,
+    ValueOption, load_text_buf, store_refocus, store_text_buf,
+};
+
+impl<B> EditableComboBox<'_, B>
+where
+    B: TextBuffer + Default + Clone + Send + Sync + 'static,
+{
+    /// Display the combo box for picking multiple values, like a tag editor.
+    ///
+    /// The selected values are displayed as chips before the editor,
+    /// each with a button to remove it.
+    /// Options equal to a selected value are hidden from the popup,
+    /// and selecting an option appends it to `values` and clears the text
+    /// without closing the popup, so that the user can continue adding values.
+    /// Backspace in an empty editor removes the last value.
+    ///
+    /// Copying from an empty editor copies all values joined by the
+    /// [`multi_separator`](Self::multi_separator).
+    /// Pasting a list with separators or line breaks adds each item
+    /// that matches the [`editable_text`](ValueOption::editable_text) of an option,
+    /// ignoring case.
+    /// Other items are parsed as values with [`allow_free_text`](Self::allow_free_text)
+    /// if they pass the [`validate`](Self::validate) hook,
+    /// and are otherwise left in the editor to be corrected.
+    ///
+    /// The [`select_all_footer`](Self::select_all_footer) adds all matching options at once.
+    /// The source indices passed to hooks such as [`on_select`](Self::on_select)
+    /// count all `options`, including the hidden ones.
+    ///
+    /// # Example
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// use egui_editable_combobox::EditableComboBox;
+    ///
+    /// let mut tags = vec![String::from("urgent")];
+    /// EditableComboBox::new("tags").show_multi(ui, &mut tags, ["urgent", "later", "blocked"]);
+    /// # });
+    /// ```
+    pub fn show_multi<V, Opt>(
+        mut self,
+        ui: &mut egui::Ui,
+        values: &mut Vec<V>,
+        options: impl IntoIterator<Item = Opt>,
+    ) -> egui::Response
+    where
+        V: Value,
+        Opt: ValueOption<V>,
+    {
+        self.load_defaults(ui.ctx());
+        self.interaction.close_on_select = false;
+        self.overridden.interaction = true;
+        self.multi = true;
+        let (id_salt, store) = (self.id_salt, self.state_store);
+        let (mut changed, select_all) = self.apply_footer_action(ui.ctx(), values);
+
+        ui.horizontal_wrapped(|ui| {
+            let mut removed = None;
+            for (index, value) in values.iter().enumerate() {
+                let chip = Button::new((value.to_editable(), " ×")).small();
+                let chip = ui.add_enabled(self.interactive, chip);
+                if chip.on_hover_text("Remove").clicked() {
+                    removed = Some(index);
+                }
+            }
+
+            let text_was_empty =
+                load_text_buf::<B>(ui.ctx(), store, id_salt, "").as_str().is_empty();
+            let editing = ui.memory(|mem| mem.has_focus(Ids::Editor.id(id_salt)));
+            if editing && text_was_empty && ui.input(|input| input.events.contains(&Event::Copy)) {
+                let texts: Vec<String> = values.iter().map(Value::to_editable).collect();
+                ui.ctx().copy_text(texts.join(&self.multi_separator));
+            }
+            let mut pasted =
+                if editing { take_pasted_list(ui, &self.multi_separator) } else { vec![] };
+            pasted.retain(|item| {
+                let item = item.as_deref().unwrap_or_default().to_lowercase();
+                !values.iter().any(|value| value.to_editable().to_lowercase() == item)
+            });
+            let mut pasted_values = Vec::new();
+            let allow_free_text = self.interaction.allow_free_text;
+            let invalid: Vec<String> = pasted
+                .iter()
+                .flatten()
+                .filter(|item| self.free_text_error("", item).is_some())
+                .cloned()
+                .collect();
+            let separator = mem::take(&mut self.multi_separator);
+
+            let mut picked = Picked(None);
+            let mut selected_all = Vec::new();
+            // Taken options are hidden rather than removed,
+            // so that the source indices passed to the hooks are those of `options`.
+            let options = options.into_iter().map(|option| {
+                Some(option)
+                    .filter(|option| !values.iter().any(|value| option.equals_value(value, "")))
+            });
+            let options = split_off(options, select_all.as_ref(), &mut selected_all)
+                .map(|option| {
+                    let option = option?;
+                    let Some(index) = pasted.iter().position(|item| {
+                        item.as_deref().is_some_and(|item| matches_item(&option, item))
+                    }) else {
+                        return Some(option);
+                    };
+                    let item = pasted[index].take().unwrap_or_default();
+                    pasted_values.push((index, option.into_value(&item)));
+                    None
+                })
+                .map(|option| PickOption { option, _value: PhantomData });
+            // The editor has an automatic id, which would change with the number of chips.
+            let scope = UiBuilder::new().id(Ids::MultiEditScope.id(id_salt));
+            let mut resp = ui
+                .scope_builder(scope, |ui| self.show_with_output(ui, &mut picked, options))
+                .inner
+                .response;
+
+            if let Some(value) = picked.0 {
+                values.push(value);
+                store_text_buf(ui.ctx(), store, id_salt, B::default());
+                changed = true;
+            }
+            changed |= !selected_all.is_empty();
+            values.append(&mut selected_all);
+            if !pasted.is_empty() {
+                // Keep the order of the pasted items.
+                pasted_values.sort_by_key(|&(index, _)| index);
+                let mut unmatched = Vec::new();
+                for item in pasted.into_iter().flatten() {
+                    match V::try_from_editable(&item)
+                        .filter(|_| allow_free_text && !invalid.contains(&item))
+                    {
+                        Some(value) => pasted_values.push((usize::MAX, value)),
+                        None => unmatched.push(item),
+                    }
+                }
+                changed |= !pasted_values.is_empty();
+                values.extend(pasted_values.into_iter().map(|(_, value)| value));
+                let mut text = B::default();
+                text.replace_with(&unmatched.join(&separator));
+                store_text_buf(ui.ctx(), store, id_salt, text);
+            }
+            if let Some(index) = removed {
+                values.remove(index);
+                changed = true;
+            } else if resp.has_focus()
+                && text_was_empty
+                && ui.input(|input| input.key_pressed(egui::Key::Backspace))
+            {
+                changed |= values.pop().is_some();
+            }
+
+            if changed {
+                resp.mark_changed();
+            }
+            resp
+        })
+        .inner
+    }
+
+    /// Applies the footer action clicked in the last frame,
+    /// before the options are consumed by the popup.
+    ///
+    /// Returns whether `values` were changed,
+    /// and the text and source indices of the options to select if selecting all matches.
+    fn apply_footer_action<V>(
+        &self,
+        ctx: &egui::Context,
+        values: &mut Vec<V>,
+    ) -> (bool, Option<(String, Vec<usize>)>) {
+        let (store, id_salt) = (self.state_store, self.id_salt);
+        match take_footer_action(ctx, id_salt) {
+            Some(FooterState::Clear) => {
+                let changed = !values.is_empty();
+                values.clear();
+                (changed, None)
+            }
+            Some(FooterState::SelectAll { text, source_indices })
+                if load_text_buf::<B>(ctx, store, id_salt, "").as_str() == text =>
+            {
+                // The remaining options are filtered from scratch.
+                store_text_buf(ctx, store, id_salt, B::default());
+                // The selected values are added after the chips are displayed.
+                ctx.request_repaint();
+                (false, Some((text, source_indices)))
+            }
+            _ => (false, None),
+        }
+    }
+
+    /// Displays the [select all footer](Self::select_all_footer) below the rows of the popup.
+    ///
+    /// `matches` are the source indices of all filtered options, not only the rows in view.
+    /// The options have already been consumed by the rows,
+    /// so they are selected by [`show_multi`](Self::show_multi) in the next frame.
+    /// Select all is disabled while the options are `pending`.
+    pub(crate) fn show_select_all_footer<Opt>(
+        &self,
+        ui: &mut egui::Ui,
+        rows: &RowsState<'_, Opt>,
+        matches: Vec<usize>,
+        pending: bool,
+    ) {
+        let Some(confirm_above) = self.select_all_footer else { return };
+        let ctx = ui.ctx().clone();
+        let confirming = matches!(
+            load_footer_state(&ctx, self.id_salt),
+            FooterState::Confirming { text } if text == rows.text
+        );
+        let count = matches.len();
+        let select =
+            || FooterState::SelectAll { text: rows.text.into(), source_indices: matches };
+
+        ui.separator();
+        let next = ui
+            .horizontal(|ui| {
+                if confirming {
+                    ui.label(format!("Select all {count} matches?"));
+                    if ui.button("Select all").clicked() {
+                        return Some(select());
+                    }
+                    ui.button("Cancel").clicked().then_some(FooterState::Idle)
+                } else {
+                    let button = Button::new(format!("Select all matches ({count})"));
+                    if ui.add_enabled(!pending && count > 0, button).clicked() {
+                        return Some(if count > confirm_above {
+                            FooterState::Confirming { text: rows.text.into() }
+                        } else {
+                            select()
+                        });
+                    }
+                    ui.button("Clear selection").clicked().then_some(FooterState::Clear)
+                }
+            })
+            .inner;
+
+        let Some(next) = next else { return };
+        store_footer_state(&ctx, self.id_salt, next);
+        // Clicking the footer takes focus from the editor, but the popup stays open.
+        ui.memory_mut(|mem| mem.request_focus(rows.main_edit_id));
+        store_refocus(&ctx, self.id_salt, true);
+        ctx.request_repaint();
+    }
+}
+
+/// Removes a pasted list from the input events,
+/// returning its items split at `separator` and line breaks.
+///
+/// Text without any separator is left to be pasted into the editor as usual.
+fn take_pasted_list(ui: &egui::Ui, separator: &str) -> Vec<Option<String>> {
+    let separator = separator.trim();
+    let has_separator =
+        |text: &str| text.contains('\n') || (!separator.is_empty() && text.contains(separator));
+    let Some(list) = ui.input_mut(|input| {
+        let index = input
+            .events
+            .iter()
+            .position(|event| matches!(event, Event::Paste(text) if has_separator(text)))?;
+        match input.events.remove(index) {
+            Event::Paste(text) => Some(text),
+            _ => None,
+        }
+    }) else {
+        return Vec::new();
+    };
+
+    list.lines()
+        .flat_map(
+            |line| if separator.is_empty() { vec![line] } else { line.split(separator).collect() },
+        )
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(|item| Some(String::from(item)))
+        .collect()
+}
+
+/// Moves the values of the options to `select_all`, if any, into `selected`,
+/// passing on the other options and `None` in place of the taken ones.
+///
+/// The options are selected by the text they match and their ascending source indices.
+fn split_off<'s, V, Opt: ValueOption<V> + 's>(
+    options: impl Iterator<Item = Option<Opt>> + 's,
+    select_all: Option<&'s (String, Vec<usize>)>,
+    selected: &'s mut Vec<V>,
+) -> impl Iterator<Item = Option<Opt>> + 's {
+    let (text, source_indices) =
+        select_all.map_or(("", &[][..]), |(text, indices)| (text.as_str(), indices.as_slice()));
+    options.enumerate().map(move |(source_index, option)| {
+        let option = option?;
+        if source_indices.binary_search(&source_index).is_err() {
+            return Some(option);
+        }
+        selected.push(option.into_value(text));
+        None
+    })
+}
+
+/// Whether the pasted `item` is the editable text of `option`, ignoring case.
+fn matches_item<V, Opt: ValueOption<V>>(option: &Opt, item: &str) -> bool {
+    !option.is_separator()
+        && option.editable_text(item).is_some_and(|text| text.to_lowercase() == item.to_lowercase())
+}
+
+/// The value picked from the popup in the current frame, if any.
+///
+/// Its editable text is that of the picked value,
+/// which is recorded in the history and announced to screen readers.
+struct Picked<V>(Option<V>);
+
+impl<V: Value> Value for Picked<V> {
+    fn to_editable(&self) -> String { self.0.as_ref().map(Value::to_editable).unwrap_or_default() }
+}
+
+/// Adapts an option to pick a value into [`Picked`].
+///
+/// Options already taken are `None` and never match.
+struct PickOption<V, Opt> {
+    option: Option<Opt>,
+    _value: PhantomData<fn() -> V>,
+}
+
+impl<V, Opt: ValueOption<V>> ValueOption<Picked<V>> for PickOption<V, Opt> {
+    fn filter_by_text(&self, text: &str, state: FilterState) -> FilterResult {
+        self.option.as_ref().map_or(FilterResult::None, |option| option.filter_by_text(text, state))
+    }
+
+    fn display(&self, text: &str) -> impl IntoAtoms<'_> {
+        match &self.option {
+            Some(option) => IntoAtomsEither::Left(option.display(text)),
+            None => IntoAtomsEither::Right(""),
+        }
+    }
+
+    fn display_highlighted(&self, text: &str, highlight: &MatchHighlight) -> Option<LayoutJob> {
+        self.option.as_ref()?.display_highlighted(text, highlight)
+    }
+
+    fn into_value(self, text: &str) -> Picked<V> {
+        Picked(self.option.map(|option| option.into_value(text)))
+    }
+
+    // Already picked values are hidden, so no option is marked as selected.
+    fn equals_value(&self, _value: &Picked<V>, _text: &str) -> bool { false }
+
+    fn editable_text(&self, text: &str) -> Option<String> {
+        self.option.as_ref()?.editable_text(text)
+    }
+
+    fn icon(&self) -> Option<Atom<'_>> { self.option.as_ref()?.icon() }
+
+    fn description(&self) -> Option<&str> { self.option.as_ref()?.description() }
+
+    fn is_separator(&self) -> bool { self.option.as_ref().is_some_and(ValueOption::is_separator) }
+
+    fn is_enabled(&self, text: &str) -> bool {
+        self.option.as_ref().is_some_and(|option| option.is_enabled(text))
+    }
+}
+
+/// The state of the [select all footer](EditableComboBox::select_all_footer).
+#[derive(Clone, Default)]
+pub(crate) enum FooterState {
+    #[default]
+    Idle,
+    /// Asking to confirm selecting all options matching `text`.
+    Confirming { text: String },
+    /// Selecting the options matching `text` at `source_indices`, in ascending order,
+    /// in the next frame.
+    SelectAll { text: String, source_indices: Vec<usize> },
+    /// Removing all values in the next frame.
+    Clear,
+}
+
+fn load_footer_state(ctx: &egui::Context, id_salt: egui::Id) -> FooterState {
+    ctx.memory(|mem| mem.data.get_temp::<FooterState>(Ids::MultiFooter.id(id_salt)))
+        .unwrap_or_default()
+}
+
+fn store_footer_state(ctx: &egui::Context, id_salt: egui::Id, state: FooterState) {
+    ctx.memory_mut(|mem| mem.data.insert_temp::<FooterState>(Ids::MultiFooter.id(id_salt), state));
+}
+
+/// Takes the footer action clicked in the last frame, if any.
+fn take_footer_action(ctx: &egui::Context, id_salt: egui::Id) -> Option<FooterState> {
+    match load_footer_state(ctx, id_salt) {
+        action @ (FooterState::SelectAll { .. } | FooterState::Clear) => {
+            store_footer_state(ctx, id_salt, FooterState::Idle);
+            Some(action)
+        }
+        FooterState::Idle | FooterState::Confirming { .. } => None,
+    }
+}
@@ -0,0 +1,270 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use alloc::{format, vec};
+use core::cell::Cell;
+use core::fmt::{self, Display};
+use core::ops::{Add, RangeInclusive};
+use core::str::FromStr;
+
+use egui::IntoAtoms;
+use egui::text::LayoutJob;
+
+use crate::{FilterResult, FilterState, MatchHighlight, Value, ValueOption};
+
+/// A wrapper implementing [`Value`] and [`ValueOption`] for numbers,
+/// formatted with `Display` and parsed with [`FromStr`] ignoring surrounding whitespace.
+///
+/// Use [`NumericOptions`] to suggest numbers from a list or a stepped range,
+/// and to accept any typed number clamped into a range.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct NumericValue<T>(pub T);
+
+impl<T: FromStr + Display> Value for NumericValue<T> {
+    fn to_editable(&self) -> String { self.0.to_string() }
+
+    fn try_from_editable(text: &str) -> Option<Self> { text.trim().parse().ok().map(NumericValue) }
+}
+
+impl<T: FromStr + Display + PartialEq> ValueOption<NumericValue<T>> for NumericValue<T> {
+    fn filter_by_text(&self, text: &str, _: FilterState) -> FilterResult {
+        FilterResult::from_case_insensitive_substring(self.0.to_string(), text.trim())
+    }
+
+    fn display(&self, _text: &str) -> impl IntoAtoms<'_> { self.0.to_string() }
+
+    fn display_highlighted(&self, text: &str, highlight: &MatchHighlight) -> Option<LayoutJob> {
+        Some(highlight.layout_substring(&self.0.to_string(), text.trim()))
+    }
+
+    fn into_value(self, _text: &str) -> NumericValue<T> { self }
+
+    fn equals_value(&self, value: &NumericValue<T>, _text: &str) -> bool { self.0 == value.0 }
+
+    fn editable_text(&self, _text: &str) -> Option<String> { Some(self.0.to_string()) }
+}
+
+/// The options of a [`NumericValue`]:
+/// suggested numbers followed by a row using the typed number.
+///
+/// # Example
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// use egui_editable_combobox::{EditableComboBox, NumericOptions, NumericValue};
+///
+/// let mut port = NumericValue(8080_u16);
+/// EditableComboBox::new("port").show(
+///     ui,
+///     &mut port,
+///     NumericOptions::new([22, 80, 443, 8080]).clamp(1..=u16::MAX),
+/// );
+/// # });
+/// ```
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct NumericOptions<T> {
+    suggestions: Vec<T>,
+    range:       Option<RangeInclusive<T>>,
+}
+
+impl<T: Copy + PartialOrd> NumericOptions<T> {
+    /// Suggests the given numbers in order.
+    pub fn new(suggestions: impl IntoIterator<Item = T>) -> Self {
+        Self { suggestions: suggestions.into_iter().collect(), range: None }
+    }
+
+    /// Suggests the numbers from the start of `range` to its end, `step` apart.
+    ///
+    /// Nothing is suggested if `step` does not increase the number.
+    pub fn stepped(range: RangeInclusive<T>, step: T) -> Self
+    where
+        T: Add<Output = T>,
+    {
+        let mut suggestions = vec![];
+        let mut number = *range.start();
+        while number <= *range.end() {
+            suggestions.push(number);
+            let next = number + step;
+            if next <= number {
+                break;
+            }
+            number = next;
+        }
+        Self::new(suggestions)
+    }
+
+    /// Clamp typed numbers into `range`.
+    ///
+    /// The suggestions are not affected.
+    pub fn clamp(mut self, range: RangeInclusive<T>) -> Self {
+        self.range = Some(range);
+        self
+    }
+}
+
+impl<T> IntoIterator for NumericOptions<T> {
+    type Item = NumericOption<T>;
+    type IntoIter = core::iter::Chain<
+        core::iter::Map<vec::IntoIter<T>, fn(T) -> NumericOption<T>>,
+        core::iter::Once<NumericOption<T>>,
+    >;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let suggested: fn(T) -> NumericOption<T> = NumericOption::Suggested;
+        self.suggestions.into_iter().map(suggested).chain(core::iter::once(NumericOption::Typed(
+            TypedNumber { range: self.range, number: Cell::new(None) },
+        )))
+    }
+}
+
+/// An option of [`NumericOptions`].
+pub enum NumericOption<T> {
+    /// One of the suggested numbers.
+    Suggested(T),
+    /// The typed number, clamped into the range if any.
+    ///
+    /// This option is hidden if the text is not a number
+    /// or a suggested number matches it exactly.
+    Typed(TypedNumber<T>),
+}
+
+impl<T: Copy> Clone for NumericOption<T> {
+    fn clone(&self) -> Self {
+        match self {
+            NumericOption::Suggested(number) => NumericOption::Suggested(*number),
+            NumericOption::Typed(typed) => NumericOption::Typed(typed.clone()),
+        }
+    }
+}
+
+impl<T: Copy + fmt::Debug> fmt::Debug for NumericOption<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NumericOption::Suggested(number) => f.debug_tuple("Suggested").field(number).finish(),
+            NumericOption::Typed(typed) => f.debug_tuple("Typed").field(typed).finish(),
+        }
+    }
+}
+
+/// The [typed](NumericOption::Typed) option of [`NumericOptions`].
+///
+/// It carries the number last parsed from the text,
+/// so that it is converted into that number even if it is selected with other text,
+/// e.g. when all matching options are added at once in
+/// [`show_multi`](crate::EditableComboBox::show_multi).
+pub struct TypedNumber<T> {
+    range:  Option<RangeInclusive<T>>,
+    number: Cell<Option<T>>,
+}
+
+impl<T: Copy> TypedNumber<T> {
+    /// The range typed numbers are clamped into, if any.
+    #[must_use]
+    pub fn range(&self) -> Option<&RangeInclusive<T>> { self.range.as_ref() }
+
+    /// The number last parsed from the text, clamped into the range,
+    /// or `None` if the text has not been a number yet.
+    #[must_use]
+    pub fn number(&self) -> Option<T> { self.number.get() }
+}
+
+impl<T: Copy> Clone for TypedNumber<T> {
+    fn clone(&self) -> Self { Self { range: self.range.clone(), number: self.number.clone() } }
+}
+
+impl<T: Copy + fmt::Debug> fmt::Debug for TypedNumber<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TypedNumber")
+            .field("range", &self.range)
+            .field("number", &self.number.get())
+            .finish()
+    }
+}
+
+impl<T: FromStr + PartialOrd + Copy> TypedNumber<T> {
+    /// Parses `text` into a number clamped into the range, and carries it if it is one.
+    fn parse(&self, text: &str) -> Option<T> {
+        let number = text.trim().parse::<T>().ok()?;
+        // NaN is not comparable and cannot be clamped.
+        number.partial_cmp(&number)?;
+        let number = self.clamp(number);
+        self.number.set(Some(number));
+        Some(number)
+    }
+
+    fn clamp(&self, number: T) -> T {
+        self.range.as_ref().map_or(number, |range| {
+            if number < *range.start() {
+                *range.start()
+            } else if number > *range.end() {
+                *range.end()
+            } else {
+                number
+            }
+        })
+    }
+}
+
+impl<T> ValueOption<NumericValue<T>> for NumericOption<T>
+where
+    T: FromStr + Display + PartialOrd + Copy + Default,
+{
+    fn filter_by_text(&self, text: &str, state: FilterState) -> FilterResult {
+        match self {
+            NumericOption::Suggested(number) => {
+                FilterResult::from_case_insensitive_substring(number.to_string(), text.trim())
+            }
+            NumericOption::Typed(typed) => {
+                if typed.parse(text).is_none() || state.had_exact {
+                    FilterResult::None
+                } else {
+                    FilterResult::Partial
+                }
+            }
+        }
+    }
+
+    fn display(&self, text: &str) -> impl IntoAtoms<'_> {
+        match self {
+            NumericOption::Suggested(number) => number.to_string(),
+            NumericOption::Typed(typed) => match typed.parse(text) {
+                Some(number) => format!("Use {number}"),
+                None => String::new(),
+            },
+        }
+    }
+
+    fn display_highlighted(&self, text: &str, highlight: &MatchHighlight) -> Option<LayoutJob> {
+        match self {
+            NumericOption::Suggested(number) => {
+                Some(highlight.layout_substring(&number.to_string(), text.trim()))
+            }
+            NumericOption::Typed(_) => None,
+        }
+    }
+
+    /// Converts the typed option into the number parsed from `text`,
+    /// or else the number it carries, or else zero clamped into the range.
+    fn into_value(self, text: &str) -> NumericValue<T> {
+        match self {
+            NumericOption::Suggested(number) => NumericValue(number),
+            NumericOption::Typed(typed) => {
+                let number = typed.parse(text).or_else(|| typed.number());
+                NumericValue(number.unwrap_or_else(|| typed.clamp(T::default())))
+            }
+        }
+    }
+
+    fn equals_value(&self, value: &NumericValue<T>, _text: &str) -> bool {
+        match self {
+            NumericOption::Suggested(number) => *number == value.0,
+            NumericOption::Typed(_) => false,
+        }
+    }
+
+    fn editable_text(&self, text: &str) -> Option<String> {
+        match self {
+            NumericOption::Suggested(number) => Some(number.to_string()),
+            NumericOption::Typed(typed) => typed.parse(text).map(|number| number.to_string()),
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use alloc::string::String;
+
+use egui::text::LayoutJob;
+use egui::{Atom, IntoAtoms};
+
+use crate::value::IntoAtomsEither;
+use crate::{FilterResult, FilterState, MatchHighlight, Value, ValueOption};
+
+/// An absent value is edited as empty text.
+///
+/// Committing empty text, e.g. with a [`clear_button`](crate::EditableComboBox::clear_button)
+/// reset to `""`, sets the value to `None`.
+impl<V: Value> Value for Option<V> {
+    fn to_editable(&self) -> String { self.as_ref().map(Value::to_editable).unwrap_or_default() }
+
+    fn try_from_editable(text: &str) -> Option<Self> {
+        if text.is_empty() { Some(None) } else { V::try_from_editable(text).map(Some) }
+    }
+}
+
+/// An option of an [`Option`] value,
+/// or the row selecting `None`, as produced by [`with_none`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Optional<'a, Opt> {
+    /// Selects `Some` value.
+    Some(Opt),
+    /// Selects `None`, displayed with the given label.
+    None(&'a str),
+}
+
+/// Prepends a row labelled `label` selecting `None` to the options of an [`Option`] value.
+///
+/// The `None` row matches empty text and text contained in its label.
+///
+/// # Example
+/// ```
+/// use egui_editable_combobox::{EditableComboBox, with_none};
+///
+/// # egui::__run_test_ui(|ui| {
+/// let mut assignee: Option<String> = None;
+/// EditableComboBox::new("assignee").empty_is_unset("Unassigned").show(
+///     ui,
+///     &mut assignee,
+///     with_none("(nobody)", ["Alice", "Bob", "Carol"]),
+/// );
+/// # });
+/// ```
+pub fn with_none<I: IntoIterator>(
+    label: &str,
+    options: I,
+) -> impl Iterator<Item = Optional<'_, I::Item>> {
+    core::iter::once(Optional::None(label)).chain(options.into_iter().map(Optional::Some))
+}
+
+impl<V, Opt: ValueOption<V>> ValueOption<Option<V>> for Optional<'_, Opt> {
+    fn filter_by_text(&self, text: &str, state: FilterState) -> FilterResult {
+        match self {
+            Optional::Some(option) => option.filter_by_text(text, state),
+            Optional::None(label) => FilterResult::from_case_insensitive_substring(label, text),
+        }
+    }
+
+    fn display(&self, text: &str) -> impl IntoAtoms<'_> {
+        match self {
+            Optional::Some(option) => IntoAtomsEither::Left(option.display(text)),
+            Optional::None(label) => IntoAtomsEither::Right(*label),
+        }
+    }
+
+    fn display_highlighted(&self, text: &str, highlight: &MatchHighlight) -> Option<LayoutJob> {
+        match self {
+            Optional::Some(option) => option.display_highlighted(text, highlight),
+            Optional::None(label) => Some(highlight.layout_substring(label, text)),
+        }
+    }
+
+    fn into_value(self, text: &str) -> Option<V> {
+        match self {
+            Optional::Some(option) => Some(option.into_value(text)),
+            Optional::None(_) => None,
+        }
+    }
+
+    fn equals_value(&self, value: &Option<V>, text: &str) -> bool {
+        match (self, value) {
+            (Optional::Some(option), Some(value)) => option.equals_value(value, text),
+            (Optional::None(_), None) => true,
+            _ => false,
+        }
+    }
+
+    fn editable_text(&self, text: &str) -> Option<String> {
+        match self {
+            Optional::Some(option) => option.editable_text(text),
+            Optional::None(_) => None,
+        }
+    }
+
+    fn icon(&self) -> Option<Atom<'_>> {
+        match self {
+            Optional::Some(option) => option.icon(),
+            Optional::None(_) => None,
+        }
+    }
+
+    fn description(&self) -> Option<&str> {
+        match self {
+            Optional::Some(option) => option.description(),
+            Optional::None(_) => None,
+        }
+    }
+
+    fn is_separator(&self) -> bool {
+        matches!(self, Optional::Some(option) if option.is_separator())
+    }
+
+    fn is_enabled(&self, text: &str) -> bool {
+        match self {
+            Optional::Some(option) => option.is_enabled(text),
+            Optional::None(_) => true,
+        }
+    }
+}
@@ -0,0 +1,96 @@
+/// The result of showing an [`EditableComboBox`](crate::EditableComboBox),
+/// returned by [`show_with_output`](crate::EditableComboBox::show_with_output).
+///
+/// # Example
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// use egui_editable_combobox::EditableComboBox;
+///
+/// let mut fruit = String::new();
+/// let output =
+///     EditableComboBox::new("fruit").show_with_output(ui, &mut fruit, ["apple", "banana"]);
+/// if output.popup_open {
+///     ui.label(format!("{} results", output.filtered_count));
+/// }
+/// # });
+/// ```
+#[derive(Debug)]
+pub struct ShowOutput {
+    /// The response of the text editor.
+    pub response:       egui::Response,
+    /// Whether the popup is open after this frame.
+    pub popup_open:     bool,
+    /// The number of options matching the text in the popup, excluding separators.
+    ///
+    /// This is zero if the popup is not displayed.
+    pub filtered_count: usize,
+    /// The source index of the option under the keyboard cursor,
+    /// or `None` if the popup is not displayed or no options match the text.
+    pub cursor:         Option<usize>,
+    /// How the value was changed in this frame, or `None` if it was not changed.
+    pub change:         Option<ValueChange>,
+    /// How an option [equal](crate::ValueOption::equals_value) to the current value
+    /// was selected again in this frame, or `None` if it was not.
+    ///
+    /// Selecting the current value again closes the popup like any selection,
+    /// but leaves the value unchanged, so [`change`](Self::change) is `None`
+    /// and the response is not marked as changed.
+    pub reselected:     Option<ValueChange>,
+    /// How the options matched the text in the popup,
+    /// e.g. to suggest fuzzy matching if nothing else matches.
+    ///
+    /// This is zero if the popup is not displayed.
+    pub match_stats:    MatchStats,
+}
+
+impl ShowOutput {
+    /// Whether the value was changed in this frame.
+    #[must_use]
+    pub fn changed(&self) -> bool { self.change.is_some() }
+}
+
+/// How the options matched the typed text in a frame, returned in [`ShowOutput::match_stats`].
+///
+/// Fuzzy matching runs in the same pass as the filter of the combo box,
+/// but only until any option matches, so it only costs while nothing else is displayed.
+/// Apps can check [`fuzzy_would_match`](Self::fuzzy_would_match) to offer fuzzy matching,
+/// or let the combo box list the fuzzy matches
+/// with [`fuzzy_fallback`](crate::EditableComboBox::fuzzy_fallback).
+///
+/// While [filtering with a budget](crate::EditableComboBox::filter_budget),
+/// the counts only cover the options filtered so far.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MatchStats {
+    /// The number of options matching the text, excluding separators and fuzzy matches.
+    pub matches:        usize,
+    /// The number of options whose [editable text](crate::ValueOption::editable_text)
+    /// contains the text as a subsequence, as matched by [`SubsequenceMatcher`](crate::SubsequenceMatcher).
+    ///
+    /// This is zero if any option matches the text otherwise.
+    pub fuzzy_matches:  usize,
+    /// Whether the popup lists the fuzzy matches because no option matches otherwise.
+    pub fuzzy_fallback: bool,
+}
+
+impl MatchStats {
+    /// Whether no option matches the text, but some would match it as a fuzzy subsequence.
+    #[must_use]
+    pub fn fuzzy_would_match(&self) -> bool { self.matches == 0 && self.fuzzy_matches > 0 }
+}
+
+/// How the user changed the value of an [`EditableComboBox`](crate::EditableComboBox).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueChange {
+    /// An option was clicked.
+    Clicked,
+    /// The option under the cursor was selected with Enter.
+    Entered,
+    /// The typed text was committed as the value
+    /// with [`allow_free_text`](crate::EditableComboBox::allow_free_text).
+    FreeText,
+    /// The value follows the typed text,
+    /// e.g. with [`CompletionScope::Segment`](crate::CompletionScope::Segment).
+    Typed,
+    /// The value was reset by the [clear button](crate::EditableComboBox::clear_button).
+    Cleared,
+}
@@ -0,0 +1,105 @@
+use core::task::Poll;
+
+use egui::TextBuffer;
+
+use crate::{EditableComboBox, Value, ValueOption};
+
+/// A source of options queried with the text typed by the user,
+/// e.g. a database query keyed on the typed prefix.
+///
+/// The options are polled without blocking the UI,
+/// so that the query can run in the background and the results can arrive in a later frame.
+///
+/// # Example
+/// ```
+/// use std::sync::mpsc;
+/// use std::task::Poll;
+///
+/// use egui_editable_combobox::{EditableComboBox, OptionProvider};
+///
+/// fn query_database(prefix: &str) -> Vec<String> {
+///     ["Alice", "Bob"]
+///         .into_iter()
+///         .filter(|name| name.starts_with(prefix))
+///         .map(String::from)
+///         .collect()
+/// }
+///
+/// #[derive(Default)]
+/// struct Names {
+///     query:   Option<String>,
+///     loading: Option<mpsc::Receiver<Vec<String>>>,
+///     results: Vec<String>,
+/// }
+///
+/// impl OptionProvider<String> for Names {
+///     type Opt = String;
+///
+///     fn poll_options<'a>(
+///         &'a mut self,
+///         text: &str,
+///     ) -> Poll<impl IntoIterator<Item = String> + use<'a>> {
+///         if self.query.as_deref() != Some(text) {
+///             // Start a new query in the background when the text changes.
+///             let (send, recv) = mpsc::channel();
+///             let prefix = text.to_owned();
+///             std::thread::spawn(move || send.send(query_database(&prefix)));
+///             self.query = Some(text.to_owned());
+///             self.loading = Some(recv);
+///         }
+///         if let Some(recv) = &self.loading {
+///             match recv.try_recv() {
+///                 Ok(results) => self.results = results,
+///                 Err(mpsc::TryRecvError::Empty) => return Poll::Pending,
+///                 Err(mpsc::TryRecvError::Disconnected) => self.results.clear(),
+///             }
+///             self.loading = None;
+///         }
+///         Poll::Ready(self.results.iter().cloned())
+///     }
+/// }
+///
+/// # egui::__run_test_ui(|ui| {
+/// let mut names = Names::default();
+/// let mut name = String::new();
+/// EditableComboBox::new("name").show_with_provider(ui, &mut name, &mut names);
+/// # });
+/// ```
+pub trait OptionProvider<V> {
+    /// The type of the provided options.
+    type Opt: ValueOption<V>;
+
+    /// Polls the options for the filter text `text`.
+    ///
+    /// This is called in every frame while the popup is displayed,
+    /// so it should start loading the options when `text` changes and return without blocking.
+    /// Returns [`Poll::Pending`] while the options for `text` are loading,
+    /// in which case the popup displays a "Loading…" row and is repainted to poll again.
+    ///
+    /// The returned options are still filtered by [`ValueOption::filter_by_text`].
+    fn poll_options<'a>(
+        &'a mut self,
+        text: &str,
+    ) -> Poll<impl IntoIterator<Item = Self::Opt> + use<'a, Self, V>>;
+}
+
+impl<B> EditableComboBox<'_, B>
+where
+    B: TextBuffer + Default + Clone + Send + Sync + 'static,
+{
+    /// Display the combo box with options from a [`OptionProvider`].
+    ///
+    /// The provider is only polled while the popup is displayed.
+    pub fn show_with_provider<V, P>(
+        self,
+        ui: &mut egui::Ui,
+        value: &mut V,
+        provider: &mut P,
+    ) -> egui::Response
+    where
+        V: Value,
+        P: OptionProvider<V>,
+    {
+        self.show_polled(ui, value, |text| provider.poll_options(text)).response
+    }
+}
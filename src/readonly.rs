@@ -0,0 +1,45 @@
+use egui::{InnerResponse, TextBuffer};
+
+use crate::{EditableComboBox, Value, ValueOption};
+
+impl<B> EditableComboBox<'_, B>
+where
+    B: TextBuffer + Default + Clone + Send + Sync + 'static,
+{
+    /// Display the combo box without binding it to a value.
+    ///
+    /// The same searchable popup is shown,
+    /// but `current` is never mutated and keeps being displayed after the user picks a value.
+    /// Instead, the picked value is returned in [`InnerResponse::inner`] for the frame it was picked,
+    /// e.g. for "search & insert" UIs where the result is consumed immediately.
+    ///
+    /// # Example
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// use egui_editable_combobox::EditableComboBox;
+    ///
+    /// let mut tags = vec![String::from("urgent")];
+    ///
+    /// let picked = EditableComboBox::new("add tag")
+    ///     .show_readonly(ui, &String::new(), ["urgent", "later", "blocked"])
+    ///     .inner;
+    /// if let Some(tag) = picked {
+    ///     tags.push(tag);
+    /// }
+    /// # });
+    /// ```
+    pub fn show_readonly<V, Opt>(
+        self,
+        ui: &mut egui::Ui,
+        current: &V,
+        options: impl IntoIterator<Item = Opt>,
+    ) -> InnerResponse<Option<V>>
+    where
+        V: Value + Clone,
+        Opt: ValueOption<V>,
+    {
+        let mut value = current.clone();
+        let output = self.show_with_output(ui, &mut value, options);
+        InnerResponse::new(output.changed().then_some(value), output.response)
+    }
+}
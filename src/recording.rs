@@ -0,0 +1,183 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// A log of the inputs and state transitions of a combo box,
+/// for reproducing bugs in focus and commit ordering.
+///
+/// Pass a `Recording` to [`EditableComboBox::record`](crate::EditableComboBox::record)
+/// to append a [`RecordedFrame`] every time the combo box is shown.
+/// With the `serde` feature, the recording can be serialized and attached to a bug report,
+/// and later [replayed](Recording::replay) in a test.
+///
+/// # Example
+/// ```
+/// use egui_editable_combobox::{EditableComboBox, RecordedFrame, Recording, Transition};
+///
+/// let click = |pressed| egui::Event::PointerButton {
+///     pos: egui::pos2(10.0, 5.0),
+///     button: egui::PointerButton::Primary,
+///     pressed,
+///     modifiers: egui::Modifiers::NONE,
+/// };
+/// let enter = egui::Event::Key {
+///     key:          egui::Key::Enter,
+///     physical_key: None,
+///     pressed:      true,
+///     repeat:       false,
+///     modifiers:    egui::Modifiers::NONE,
+/// };
+/// let frame = |time, events| RecordedFrame { time, events, ..Default::default() };
+///
+/// // A recording would normally be deserialized from a bug report.
+/// let report = Recording {
+///     frames: vec![
+///         frame(0.0, vec![]),
+///         frame(
+///             0.1,
+///             vec![egui::Event::PointerMoved(egui::pos2(10.0, 5.0)), click(true), click(false)],
+///         ),
+///         frame(0.2, vec![egui::Event::Text("Bet".into())]),
+///         frame(0.3, vec![enter]),
+///     ],
+///     ..Default::default()
+/// };
+///
+/// let ctx = egui::Context::default();
+/// let mut replayed = Recording::default();
+/// let mut value = String::new();
+/// report.replay(&ctx, |ui| {
+///     EditableComboBox::new("replayed").record(&mut replayed).show(
+///         ui,
+///         &mut value,
+///         ["Alpha", "Beta"],
+///     );
+/// });
+///
+/// assert_eq!(value, "Beta");
+/// assert!(replayed.transitions().any(|t| *t == Transition::Selected { source_index: 1 }));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Recording {
+    /// The recorded frames, oldest first.
+    pub frames:  Vec<RecordedFrame>,
+    /// The ID salt of the recorded combo box,
+    /// by which [`replay`](Recording::replay) finds the combo box to translate positions.
+    pub id_salt: Option<egui::Id>,
+}
+
+/// The inputs and state transitions of a combo box in a single frame.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RecordedFrame {
+    /// The input time of the frame in seconds.
+    pub time:        f64,
+    /// The modifier keys held in the frame.
+    pub modifiers:   egui::Modifiers,
+    /// The input events relevant to the combo box,
+    /// with positions relative to the top-left corner of the combo box.
+    pub events:      Vec<egui::Event>,
+    /// The state transitions of the combo box in the frame.
+    pub transitions: Vec<Transition>,
+}
+
+/// A state transition of a combo box.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Transition {
+    /// The text editor gained focus.
+    GainedFocus,
+    /// The text editor lost focus.
+    LostFocus,
+    /// The user edited the text.
+    TextEdited(String),
+    /// The user selected the option at `source_index` of the `options` iterator.
+    Selected {
+        /// The index of the selected option.
+        source_index: usize,
+    },
+}
+
+impl Recording {
+    /// Iterates over the transitions of all frames in order.
+    pub fn transitions(&self) -> impl Iterator<Item = &Transition> {
+        self.frames.iter().flat_map(|frame| &frame.transitions)
+    }
+
+    /// Replays the recorded inputs on `ctx`, running a frame with `run_ui` for each recorded frame.
+    ///
+    /// `run_ui` is shown in a [`CentralPanel`](egui::CentralPanel).
+    /// Positions are replayed relative to the top-left corner of the combo box
+    /// with the recorded [`id_salt`](Recording::id_salt) as laid out in the previous frame,
+    /// so the combo box can be anywhere in `run_ui`.
+    /// Until it has been laid out, or if the ID salt is not recorded,
+    /// positions are relative to the top-left corner of the panel.
+    pub fn replay(&self, ctx: &egui::Context, mut run_ui: impl FnMut(&mut egui::Ui)) {
+        ctx.data_mut(|data| match self.id_salt {
+            Some(id_salt) => data.insert_temp(replay_target_id(), id_salt),
+            None => data.remove::<egui::Id>(replay_target_id()),
+        });
+        for frame in &self.frames {
+            // The central panel starts at the origin without a margin in the first frame,
+            // so use the origin laid out in the previous frame if any.
+            let origin = ctx.memory(|mem| mem.data.get_temp(replay_origin_id()));
+            let origin = origin.unwrap_or(egui::Pos2::ZERO).to_vec2();
+
+            let input = egui::RawInput {
+                time: Some(frame.time),
+                modifiers: frame.modifiers,
+                events: frame.events.iter().map(|event| translate_event(event, origin)).collect(),
+                ..Default::default()
+            };
+            let _ = ctx.run(input, |ctx| {
+                egui::CentralPanel::default().show(ctx, |ui| {
+                    // Overwritten by the replayed combo box when it is shown.
+                    let origin = ui.cursor().min;
+                    ui.ctx().memory_mut(|mem| mem.data.insert_temp(replay_origin_id(), origin));
+                    run_ui(ui);
+                });
+            });
+        }
+        ctx.data_mut(|data| data.remove::<egui::Id>(replay_target_id()));
+    }
+}
+
+fn replay_origin_id() -> egui::Id { egui::Id::new("egui_editable_combobox::Recording::replay") }
+
+fn replay_target_id() -> egui::Id { replay_origin_id().with("target") }
+
+/// Stores `origin` as the origin of replayed positions
+/// if the combo box with `id_salt` is being [replayed](Recording::replay).
+pub(crate) fn store_replay_origin(ctx: &egui::Context, id_salt: egui::Id, origin: egui::Pos2) {
+    ctx.data_mut(|data| {
+        if data.get_temp::<egui::Id>(replay_target_id()) == Some(id_salt) {
+            data.insert_temp(replay_origin_id(), origin);
+        }
+    });
+}
+
+/// Records the inputs of the current frame relative to `origin`,
+/// returning `None` for events irrelevant to the combo box.
+pub(crate) fn record_event(event: &egui::Event, origin: egui::Pos2) -> Option<egui::Event> {
+    match event {
+        egui::Event::Key { .. }
+        | egui::Event::Text(_)
+        | egui::Event::Copy
+        | egui::Event::Cut
+        | egui::Event::Paste(_)
+        | egui::Event::MouseWheel { .. }
+        | egui::Event::PointerMoved(_)
+        | egui::Event::PointerButton { .. }
+        | egui::Event::PointerGone => Some(translate_event(event, -origin.to_vec2())),
+        _ => None,
+    }
+}
+
+fn translate_event(event: &egui::Event, delta: egui::Vec2) -> egui::Event {
+    let mut event = event.clone();
+    match &mut event {
+        egui::Event::PointerMoved(pos) | egui::Event::PointerButton { pos, .. } => *pos += delta,
+        _ => {}
+    }
+    event
+}
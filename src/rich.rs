@@ -0,0 +1,81 @@
+use alloc::string::String;
+
+use egui::text::LayoutJob;
+use egui::{Atom, IntoAtoms};
+
+use crate::{FilterResult, FilterState, MatchHighlight, ValueOption};
+
+/// Wraps an option to display an icon before it and a dimmed description under it.
+///
+/// The icon can be an [`egui::Image`] or any other [`Atom`], such as an emoji.
+/// The description is not matched when filtering;
+/// wrap the option in [`Keyworded`](crate::Keyworded) to also search it.
+///
+/// # Example
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// use egui_editable_combobox::{EditableComboBox, RichOption};
+///
+/// let users = [("Alice", "alice@example.com"), ("Bob", "bob@example.com")];
+/// let mut assignee = String::new();
+/// EditableComboBox::new("assignee").show(
+///     ui,
+///     &mut assignee,
+///     users.map(|(name, email)| RichOption::new(name).icon("👤").description(email)),
+/// );
+/// # });
+/// ```
+#[derive(Debug, Clone)]
+pub struct RichOption<'a, Opt> {
+    /// The wrapped option.
+    pub option:      Opt,
+    /// The icon displayed before the option.
+    pub icon:        Option<Atom<'a>>,
+    /// The secondary line displayed under the option.
+    pub description: Option<String>,
+}
+
+impl<'a, Opt> RichOption<'a, Opt> {
+    /// Wraps `option` without an icon or a description.
+    pub fn new(option: Opt) -> Self { Self { option, icon: None, description: None } }
+
+    /// Display `icon` before the option.
+    #[must_use]
+    pub fn icon(mut self, icon: impl Into<Atom<'a>>) -> Self {
+        self.icon = Some(icon.into());
+        self
+    }
+
+    /// Display `description` dimmed under the option.
+    #[must_use]
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+}
+
+impl<V, Opt: ValueOption<V>> ValueOption<V> for RichOption<'_, Opt> {
+    fn filter_by_text(&self, text: &str, state: FilterState) -> FilterResult {
+        self.option.filter_by_text(text, state)
+    }
+
+    fn display(&self, text: &str) -> impl IntoAtoms<'_> { self.option.display(text) }
+
+    fn display_highlighted(&self, text: &str, highlight: &MatchHighlight) -> Option<LayoutJob> {
+        self.option.display_highlighted(text, highlight)
+    }
+
+    fn into_value(self, text: &str) -> V { self.option.into_value(text) }
+
+    fn equals_value(&self, value: &V, text: &str) -> bool { self.option.equals_value(value, text) }
+
+    fn editable_text(&self, text: &str) -> Option<String> { self.option.editable_text(text) }
+
+    fn icon(&self) -> Option<Atom<'_>> { self.icon.clone() }
+
+    fn description(&self) -> Option<&str> { self.description.as_deref() }
+
+    fn is_separator(&self) -> bool { self.option.is_separator() }
+
+    fn is_enabled(&self, text: &str) -> bool { self.option.is_enabled(text) }
+}
@@ -0,0 +1,121 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::hash::Hash;
+
+use crate::{EditableComboBox, HISTORY_CAPACITY, Ids, StateStore, Value};
+
+/// A value committed by the user,
+/// as returned by [`EditableComboBox::selection_history`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SelectionRecord {
+    /// The committed value, converted with [`Value::to_editable`].
+    pub text:         String,
+    /// The source index of the selected option,
+    /// or `None` if the typed text was committed with
+    /// [`allow_free_text`](EditableComboBox::allow_free_text).
+    pub source_index: Option<usize>,
+    /// When the value was committed, in seconds as in [`egui::InputState::time`].
+    ///
+    /// Records restored by egui persistence keep the times of the previous run of the app.
+    pub time:         f64,
+}
+
+impl SelectionRecord {
+    /// Parses the committed value back with [`Value::try_from_editable`].
+    #[must_use]
+    pub fn value<V: Value>(&self) -> Option<V> { V::try_from_editable(&self.text) }
+}
+
+impl EditableComboBox<'_> {
+    /// The values recently committed in the combo box with the ID `id_salt`, oldest first.
+    ///
+    /// Every commit is recorded, even if the value was already committed before,
+    /// so that apps can implement undo or analytics on top of it.
+    /// The [`HistoryMode`](crate::HistoryMode) history
+    /// and the [recent values](Self::remember_recent) are read from these records.
+    /// Only the last 50 commits are kept, in persisted egui memory,
+    /// or in the [`StateStore`] of the combo box if set,
+    /// in which case this returns nothing.
+    ///
+    /// # Example
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// use egui_editable_combobox::EditableComboBox;
+    ///
+    /// let mut fruit = String::new();
+    /// EditableComboBox::new("fruit").show(ui, &mut fruit, ["apple", "banana"]);
+    ///
+    /// for record in EditableComboBox::selection_history(ui.ctx(), "fruit") {
+    ///     let fruit: Option<String> = record.value();
+    ///     ui.label(format!("{fruit:?} at {:.1}s", record.time));
+    /// }
+    /// # });
+    /// ```
+    pub fn selection_history(ctx: &egui::Context, id_salt: impl Hash) -> Vec<SelectionRecord> {
+        load_memory_history(ctx, egui::Id::new(id_salt))
+    }
+}
+
+/// A [`SelectionRecord`] as kept in persisted egui memory,
+/// as a tuple that is serializable whenever egui persistence is enabled.
+type StoredRecord = (String, Option<usize>, f64);
+
+/// Appends a commit of `value` to the selection history.
+pub(crate) fn record_selection<V: Value>(
+    ctx: &egui::Context,
+    store: Option<&dyn StateStore>,
+    id_salt: egui::Id,
+    value: &V,
+    source_index: Option<usize>,
+) {
+    let mut history = load_selection_history(ctx, store, id_salt);
+    if history.len() >= HISTORY_CAPACITY {
+        history.remove(0);
+    }
+    let time = ctx.input(|input| input.time);
+    history.push(SelectionRecord { text: value.to_editable(), source_index, time });
+    match store {
+        Some(store) => store.set_history(ctx, id_salt, history),
+        None => store_memory_history(ctx, id_salt, history),
+    }
+}
+
+pub(crate) fn load_selection_history(
+    ctx: &egui::Context,
+    store: Option<&dyn StateStore>,
+    id_salt: egui::Id,
+) -> Vec<SelectionRecord> {
+    match store {
+        Some(store) => store.history(ctx, id_salt),
+        None => load_memory_history(ctx, id_salt),
+    }
+}
+
+/// The distinct non-empty texts committed, most recent first.
+pub(crate) fn distinct_texts(history: &[SelectionRecord]) -> Vec<&String> {
+    let mut texts: Vec<&String> = Vec::new();
+    for record in history.iter().rev() {
+        if !record.text.is_empty() && !texts.contains(&&record.text) {
+            texts.push(&record.text);
+        }
+    }
+    texts
+}
+
+pub(crate) fn load_memory_history(ctx: &egui::Context, id_salt: egui::Id) -> Vec<SelectionRecord> {
+    let stored = ctx.memory_mut(|mem| {
+        mem.data.get_persisted::<Vec<StoredRecord>>(Ids::SelectionHistory.id(id_salt))
+    });
+    let records = stored.unwrap_or_default().into_iter();
+    records.map(|(text, source_index, time)| SelectionRecord { text, source_index, time }).collect()
+}
+
+pub(crate) fn store_memory_history(
+    ctx: &egui::Context,
+    id_salt: egui::Id,
+    history: Vec<SelectionRecord>,
+) {
+    let stored: Vec<StoredRecord> =
+        history.into_iter().map(|record| (record.text, record.source_index, record.time)).collect();
+    ctx.memory_mut(|mem| mem.data.insert_persisted(Ids::SelectionHistory.id(id_salt), stored));
+}
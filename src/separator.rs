@@ -0,0 +1,137 @@
+use alloc::string::String;
+use core::iter::Peekable;
+
+use egui::text::LayoutJob;
+use egui::{Atom, IntoAtoms};
+
+use crate::value::IntoAtomsEither;
+use crate::{FilterResult, FilterState, MatchHighlight, ValueOption};
+
+/// An option or a separator between groups of options,
+/// as produced by [`with_separators`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Separated<Opt> {
+    /// A selectable option.
+    Option(Opt),
+    /// A thin line between two groups of options.
+    Separator,
+}
+
+/// Inserts a [`Separator`](Separated::Separator) between each pair of adjacent options
+/// for which `between(prev, next)` returns true.
+///
+/// Separators are only displayed between matching options,
+/// so a group filtered out entirely does not leave consecutive separators behind.
+/// The source indices passed to callbacks such as
+/// [`on_select`](crate::EditableComboBox::on_select) count the separators.
+///
+/// # Example
+/// ```
+/// use egui_editable_combobox::{EditableComboBox, with_separators};
+///
+/// # egui::__run_test_ui(|ui| {
+/// let mut name = String::new();
+/// // Separate the names by their initial letter.
+/// let options = with_separators(["Alice", "Amy", "Bob", "Carol"], |prev, next| {
+///     prev.chars().next() != next.chars().next()
+/// });
+/// EditableComboBox::new("name").show(ui, &mut name, options);
+/// # });
+/// ```
+pub fn with_separators<I, F>(options: I, between: F) -> impl Iterator<Item = Separated<I::Item>>
+where
+    I: IntoIterator,
+    F: FnMut(&I::Item, &I::Item) -> bool,
+{
+    WithSeparators { options: options.into_iter().peekable(), between, separator_next: false }
+}
+
+struct WithSeparators<I: Iterator, F> {
+    options:        Peekable<I>,
+    between:        F,
+    separator_next: bool,
+}
+
+impl<I, F> Iterator for WithSeparators<I, F>
+where
+    I: Iterator,
+    F: FnMut(&I::Item, &I::Item) -> bool,
+{
+    type Item = Separated<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.separator_next {
+            self.separator_next = false;
+            return Some(Separated::Separator);
+        }
+        let option = self.options.next()?;
+        self.separator_next = self.options.peek().is_some_and(|next| (self.between)(&option, next));
+        Some(Separated::Option(option))
+    }
+}
+
+impl<V, Opt: ValueOption<V>> ValueOption<V> for Separated<Opt> {
+    fn filter_by_text(&self, text: &str, state: FilterState) -> FilterResult {
+        match self {
+            Separated::Option(option) => option.filter_by_text(text, state),
+            Separated::Separator => FilterResult::None,
+        }
+    }
+
+    fn display(&self, text: &str) -> impl IntoAtoms<'_> {
+        match self {
+            Separated::Option(option) => IntoAtomsEither::Left(option.display(text)),
+            Separated::Separator => IntoAtomsEither::Right(""),
+        }
+    }
+
+    fn display_highlighted(&self, text: &str, highlight: &MatchHighlight) -> Option<LayoutJob> {
+        match self {
+            Separated::Option(option) => option.display_highlighted(text, highlight),
+            Separated::Separator => None,
+        }
+    }
+
+    /// # Panics
+    /// Panics if called on a separator, which cannot be selected.
+    fn into_value(self, text: &str) -> V {
+        match self {
+            Separated::Option(option) => option.into_value(text),
+            Separated::Separator => unreachable!("separators cannot be selected"),
+        }
+    }
+
+    fn equals_value(&self, value: &V, text: &str) -> bool {
+        match self {
+            Separated::Option(option) => option.equals_value(value, text),
+            Separated::Separator => false,
+        }
+    }
+
+    fn editable_text(&self, text: &str) -> Option<String> {
+        match self {
+            Separated::Option(option) => option.editable_text(text),
+            Separated::Separator => None,
+        }
+    }
+
+    fn icon(&self) -> Option<Atom<'_>> {
+        match self {
+            Separated::Option(option) => option.icon(),
+            Separated::Separator => None,
+        }
+    }
+
+    fn description(&self) -> Option<&str> {
+        match self {
+            Separated::Option(option) => option.description(),
+            Separated::Separator => None,
+        }
+    }
+
+    fn is_separator(&self) -> bool { matches!(self, Separated::Separator) }
+
+    fn is_enabled(&self, text: &str) -> bool {
+        matches!(self, Separated::Option(option) if option.is_enabled(text))
+    }
+}
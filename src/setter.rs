@@ -0,0 +1,53 @@
+use egui::TextBuffer;
+
+use crate::{EditableComboBox, Value, ValueOption};
+
+impl<B> EditableComboBox<'_, B>
+where
+    B: TextBuffer + Default + Clone + Send + Sync + 'static,
+{
+    /// Display the combo box for a value that the UI cannot borrow mutably.
+    ///
+    /// `set` is called with the new value whenever the user changes it,
+    /// so that architectures dispatching changes as messages (e.g. Elm-style or redux-like)
+    /// can apply them to their state.
+    ///
+    /// # Example
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// use egui_editable_combobox::EditableComboBox;
+    ///
+    /// enum Message {
+    ///     SetFruit(String),
+    /// }
+    ///
+    /// let fruit = String::from("apple");
+    /// let mut messages = Vec::new();
+    ///
+    /// EditableComboBox::new("fruit").show_with_setter(
+    ///     ui,
+    ///     &fruit,
+    ///     |fruit| messages.push(Message::SetFruit(fruit)),
+    ///     ["apple", "banana"],
+    /// );
+    /// # });
+    /// ```
+    pub fn show_with_setter<V, Opt>(
+        self,
+        ui: &mut egui::Ui,
+        current: &V,
+        mut set: impl FnMut(V),
+        options: impl IntoIterator<Item = Opt>,
+    ) -> egui::Response
+    where
+        V: Value + Clone,
+        Opt: ValueOption<V>,
+    {
+        let mut value = current.clone();
+        let output = self.show_with_output(ui, &mut value, options);
+        if output.changed() {
+            set(value);
+        }
+        output.response
+    }
+}
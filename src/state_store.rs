@@ -0,0 +1,96 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::selection_history::{load_memory_history, store_memory_history};
+use crate::{Ids, SelectionRecord};
+
+/// Where a combo box keeps the state of the user editing it:
+/// the typed text, the cursor in the popup and the history of selected values.
+///
+/// The state is kept in egui memory unless a store is set with
+/// [`EditableComboBox::state_store`](crate::EditableComboBox::state_store),
+/// so that it can live with the edited document instead,
+/// e.g. to restore the typed text when switching between documents in a multi-document editor.
+/// Each method defaults to egui memory, so that a store only implements the state it keeps.
+///
+/// Stores are shared with the UI thread through `&self`,
+/// so they use interior mutability and must be `Send` and `Sync`.
+/// `id` is the ID of the combo box, unique among the combo boxes sharing the store.
+///
+/// # Example
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// use std::collections::HashMap;
+/// use std::sync::Mutex;
+///
+/// use egui_editable_combobox::{EditableComboBox, StateStore};
+///
+/// /// The typed texts of a document, restored when the document is reopened.
+/// #[derive(Default)]
+/// struct DocumentState {
+///     texts: Mutex<HashMap<egui::Id, String>>,
+/// }
+///
+/// impl StateStore for DocumentState {
+///     fn text(&self, _ctx: &egui::Context, id: egui::Id) -> Option<String> {
+///         self.texts.lock().unwrap().get(&id).cloned()
+///     }
+///
+///     fn set_text(&self, _ctx: &egui::Context, id: egui::Id, text: Option<String>) {
+///         let mut texts = self.texts.lock().unwrap();
+///         match text {
+///             Some(text) => texts.insert(id, text),
+///             None => texts.remove(&id),
+///         };
+///     }
+/// }
+///
+/// let document = DocumentState::default();
+/// let mut font = String::from("Serif");
+/// EditableComboBox::new("font").state_store(&document).show(
+///     ui,
+///     &mut font,
+///     ["Serif", "Sans", "Mono"],
+/// );
+/// # });
+/// ```
+pub trait StateStore: Send + Sync {
+    /// The text typed in the editor, or `None` to display the current value.
+    fn text(&self, ctx: &egui::Context, id: egui::Id) -> Option<String> {
+        ctx.memory(|mem| mem.data.get_temp(Ids::TextBuf.id(id)))
+    }
+
+    /// Stores the text typed in the editor, or discards it if `None`.
+    fn set_text(&self, ctx: &egui::Context, id: egui::Id, text: Option<String>) {
+        ctx.memory_mut(|mem| match text {
+            Some(text) => mem.data.insert_temp(Ids::TextBuf.id(id), text),
+            None => mem.data.remove::<String>(Ids::TextBuf.id(id)),
+        });
+    }
+
+    /// The source index of the option under the cursor in the popup,
+    /// or `None` to place the cursor on the current value.
+    fn cursor(&self, ctx: &egui::Context, id: egui::Id) -> Option<usize> {
+        ctx.memory(|mem| mem.data.get_temp(Ids::CursorPos.id(id)))
+    }
+
+    /// Stores the source index of the option under the cursor, or discards it if `None`.
+    fn set_cursor(&self, ctx: &egui::Context, id: egui::Id, source_index: Option<usize>) {
+        ctx.memory_mut(|mem| match source_index {
+            Some(source_index) => mem.data.insert_temp(Ids::CursorPos.id(id), source_index),
+            None => mem.data.remove::<usize>(Ids::CursorPos.id(id)),
+        });
+    }
+
+    /// The [selection history](crate::EditableComboBox::selection_history), oldest first,
+    /// from which the [`HistoryMode`](crate::HistoryMode) history
+    /// and the [recent values](crate::EditableComboBox::remember_recent) are read.
+    fn history(&self, ctx: &egui::Context, id: egui::Id) -> Vec<SelectionRecord> {
+        load_memory_history(ctx, id)
+    }
+
+    /// Stores the selection history, oldest first.
+    fn set_history(&self, ctx: &egui::Context, id: egui::Id, history: Vec<SelectionRecord>) {
+        store_memory_history(ctx, id, history);
+    }
+}
@@ -0,0 +1,81 @@
+use egui::{Color32, Stroke, Visuals};
+
+/// Visuals of the rows in the popup of an [`EditableComboBox`](crate::EditableComboBox).
+///
+/// The option equal to the current value, the keyboard cursor row and the hovered row
+/// are displayed differently, so that they can be told apart when they are different rows.
+/// Fields left as `None` follow the current egui theme.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PopupStyle {
+    /// Visuals of the option equal to the current value.
+    ///
+    /// Defaults to the fill of [`Visuals::selection`].
+    pub selected:    Option<RowVisuals>,
+    /// Visuals of the keyboard cursor row.
+    ///
+    /// Defaults to the fill of inactive widgets outlined with the stroke of [`Visuals::selection`].
+    pub cursor:      Option<RowVisuals>,
+    /// Visuals of the row under the pointer.
+    ///
+    /// Defaults to the visuals of hovered widgets.
+    pub hovered:     Option<RowVisuals>,
+    /// The color of the characters matching the typed text,
+    /// if [`highlight_matches`](crate::EditableComboBox::highlight_matches) is enabled.
+    ///
+    /// Defaults to the stroke color of [`Visuals::selection`].
+    pub highlight:   Option<Color32>,
+    /// The vertical spacing between the rows,
+    /// as described in [`EditableComboBox::row_spacing`](crate::EditableComboBox::row_spacing).
+    pub row_spacing: Option<f32>,
+    /// Whether the rows are displayed compactly,
+    /// as described in [`EditableComboBox::dense`](crate::EditableComboBox::dense).
+    pub dense:       bool,
+}
+
+/// The background of a row in the popup.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RowVisuals {
+    /// The background fill of the row.
+    pub fill:   Color32,
+    /// The outline of the row.
+    pub stroke: Stroke,
+}
+
+impl PopupStyle {
+    /// Fills the unset fields from `visuals`.
+    pub(crate) fn resolve(&self, visuals: &Visuals) -> ResolvedPopupStyle {
+        ResolvedPopupStyle {
+            selected: self
+                .selected
+                .unwrap_or(RowVisuals { fill: visuals.selection.bg_fill, stroke: Stroke::NONE }),
+            cursor:   self.cursor.unwrap_or(RowVisuals {
+                fill:   visuals.widgets.inactive.weak_bg_fill,
+                stroke: visuals.selection.stroke,
+            }),
+            hovered:  self.hovered.unwrap_or(RowVisuals {
+                fill:   visuals.widgets.hovered.weak_bg_fill,
+                stroke: visuals.widgets.hovered.bg_stroke,
+            }),
+        }
+    }
+}
+
+/// A [`PopupStyle`] with all fields set.
+pub(crate) struct ResolvedPopupStyle {
+    pub selected: RowVisuals,
+    pub cursor:   RowVisuals,
+    pub hovered:  RowVisuals,
+}
+
+impl ResolvedPopupStyle {
+    /// Applies the row visuals to the widget visuals used by a selectable button.
+    ///
+    /// The cursor stroke is not applied, because it is passed to the cursor row directly
+    /// to stay visible when the row is hovered or selected.
+    pub(crate) fn apply(&self, visuals: &mut Visuals) {
+        visuals.selection.bg_fill = self.selected.fill;
+        visuals.widgets.inactive.weak_bg_fill = self.cursor.fill;
+        visuals.widgets.hovered.weak_bg_fill = self.hovered.fill;
+        visuals.widgets.hovered.bg_stroke = self.hovered.stroke;
+    }
+}
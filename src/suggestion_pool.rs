@@ -0,0 +1,71 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::hash::Hash;
+
+/// The maximum number of values kept in a [`SuggestionPool`].
+const POOL_CAPACITY: usize = 100;
+
+/// Suggestions shared by the combo boxes with the same tag across the app,
+/// e.g. every "author" field learning from the authors entered in the others.
+///
+/// Combo boxes contribute the values committed in them with
+/// [`EditableComboBox::suggestion_pool`](crate::EditableComboBox::suggestion_pool),
+/// and any of them may list the [`suggestions`](Self::suggestions) as options.
+/// The pool is kept in egui memory and persisted if egui persistence is enabled.
+///
+/// # Example
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// use egui_editable_combobox::{EditableComboBox, SuggestionPool};
+///
+/// let authors = SuggestionPool::new("author");
+/// let (mut book_author, mut article_author) = (String::new(), String::new());
+/// for (id, author) in [("book", &mut book_author), ("article", &mut article_author)] {
+///     EditableComboBox::new(id).allow_free_text(true).suggestion_pool(authors).show(
+///         ui,
+///         author,
+///         authors.suggestions(ui.ctx()),
+///     );
+/// }
+/// # });
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SuggestionPool {
+    id: egui::Id,
+}
+
+impl SuggestionPool {
+    /// The pool shared by the combo boxes with the given semantic tag.
+    #[must_use]
+    pub fn new(tag: impl Hash) -> Self {
+        Self { id: egui::Id::new("egui-editable-combobox suggestion pool").with(tag) }
+    }
+
+    /// The values in the pool, most recently committed first.
+    ///
+    /// Only the last 100 distinct values are kept.
+    #[must_use]
+    pub fn suggestions(self, ctx: &egui::Context) -> Vec<String> {
+        ctx.memory_mut(|mem| mem.data.get_persisted::<Vec<String>>(self.id)).unwrap_or_default()
+    }
+
+    /// Adds `text` to the front of the pool, e.g. for values imported from a document.
+    ///
+    /// Empty text is ignored.
+    pub fn add(self, ctx: &egui::Context, text: impl Into<String>) {
+        let text = text.into();
+        if text.is_empty() {
+            return;
+        }
+        let mut suggestions = self.suggestions(ctx);
+        suggestions.retain(|old| *old != text);
+        suggestions.insert(0, text);
+        suggestions.truncate(POOL_CAPACITY);
+        ctx.memory_mut(|mem| mem.data.insert_persisted(self.id, suggestions));
+    }
+
+    /// Removes all values from the pool.
+    pub fn clear(self, ctx: &egui::Context) {
+        ctx.memory_mut(|mem| mem.data.remove::<Vec<String>>(self.id));
+    }
+}
@@ -0,0 +1,52 @@
+use egui::{Popup, TextBuffer};
+
+use crate::{EditableComboBox, Value, ValueOption};
+
+impl<B> EditableComboBox<'_, B>
+where
+    B: TextBuffer + Default + Clone + Send + Sync + 'static,
+{
+    /// Display the combo box with a read-only summary of the selected value beneath it.
+    ///
+    /// `summary` is called with the current value to render rich details about the selection,
+    /// e.g. the avatar and email of the selected user.
+    /// The summary is hidden while the user is editing the text or browsing the popup.
+    ///
+    /// # Example
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// use egui_editable_combobox::EditableComboBox;
+    ///
+    /// let mut user = String::from("alice");
+    ///
+    /// EditableComboBox::new("user").show_with_summary(ui, &mut user, ["alice", "bob"], |ui, user| {
+    ///     ui.weak(format!("{user}@example.com"));
+    /// });
+    /// # });
+    /// ```
+    pub fn show_with_summary<V, Opt>(
+        self,
+        ui: &mut egui::Ui,
+        value: &mut V,
+        options: impl IntoIterator<Item = Opt>,
+        summary: impl FnOnce(&mut egui::Ui, &V),
+    ) -> egui::Response
+    where
+        V: Value,
+        Opt: ValueOption<V>,
+    {
+        let popup_id = self.popup_id();
+
+        ui.vertical(|ui| {
+            let resp = self.show(ui, value, options);
+
+            let editing = resp.has_focus() || Popup::is_id_open(ui.ctx(), popup_id);
+            if !editing {
+                summary(ui, value);
+            }
+
+            resp
+        })
+        .inner
+    }
+}
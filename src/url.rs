@@ -0,0 +1,210 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use core::hash::Hash;
+use core::str::FromStr;
+
+use egui::IntoAtoms;
+use egui::text::LayoutJob;
+
+use crate::{EditableComboBox, FilterResult, FilterState, MatchHighlight, Value, ValueOption};
+
+/// A URL with a scheme and a host, validated when it is parsed from text.
+///
+/// Text without a scheme is parsed as an `https` URL, like in the address bar of a browser.
+/// Only the general shape of the URL is validated:
+/// the scheme must start with a letter followed by letters, digits, `+`, `-` or `.`,
+/// the host must not be empty, and the URL must not contain whitespace.
+///
+/// # Example
+/// ```
+/// use egui_editable_combobox::UrlValue;
+///
+/// let url: UrlValue = "example.com/docs".parse().unwrap();
+/// assert_eq!(url.as_str(), "https://example.com/docs");
+/// assert_eq!(url.host(), "example.com");
+/// assert!("https:///docs".parse::<UrlValue>().is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct UrlValue(String);
+
+impl UrlValue {
+    /// The whole URL, including the scheme.
+    #[must_use]
+    pub fn as_str(&self) -> &str { &self.0 }
+
+    /// The scheme of the URL, e.g. `https`.
+    #[must_use]
+    pub fn scheme(&self) -> &str { self.0.split_once("://").map_or("", |(scheme, _)| scheme) }
+
+    /// The host of the URL, including the port if any.
+    #[must_use]
+    pub fn host(&self) -> &str {
+        let rest = self.0.split_once("://").map_or("", |(_, rest)| rest);
+        rest.split(['/', '?', '#']).next().unwrap_or_default()
+    }
+}
+
+impl FromStr for UrlValue {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, String> {
+        let text = text.trim();
+        let url = if text.contains("://") { String::from(text) } else { format!("https://{text}") };
+        if url.chars().any(char::is_whitespace) {
+            return Err(String::from("URLs cannot contain spaces"));
+        }
+        let url = Self(url);
+        let mut scheme = url.scheme().chars();
+        let valid_scheme = scheme.next().is_some_and(|c| c.is_ascii_alphabetic())
+            && scheme.all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c));
+        if !valid_scheme {
+            return Err(format!("invalid scheme \"{}\"", url.scheme()));
+        }
+        if url.host().is_empty() {
+            return Err(String::from("missing host"));
+        }
+        Ok(url)
+    }
+}
+
+impl fmt::Display for UrlValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { f.write_str(&self.0) }
+}
+
+impl Value for UrlValue {
+    fn to_editable(&self) -> String { self.0.clone() }
+
+    fn try_from_editable(text: &str) -> Option<Self> { text.parse().ok() }
+}
+
+/// A preset combo box for entering a URL, like the address bar of a browser.
+///
+/// The URLs entered before are remembered across sessions if egui persistence is enabled,
+/// listed in the popup, most recent first,
+/// and suggested while typing with the rest of the best match displayed as ghost text.
+/// The editor is outlined as an error while the typed URL is invalid.
+///
+/// # Example
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// use egui_editable_combobox::{UrlPicker, UrlValue};
+///
+/// let mut url: Option<UrlValue> = None;
+/// UrlPicker::new("address").show(ui, &mut url);
+/// # });
+/// ```
+#[must_use = "You should call .show()"]
+pub struct UrlPicker {
+    id_salt:     egui::Id,
+    scheme:      Option<String>,
+    history_len: usize,
+}
+
+impl UrlPicker {
+    /// Create a new `UrlPicker` with the given ID.
+    pub fn new(id_salt: impl Hash) -> Self {
+        Self { id_salt: egui::Id::new(id_salt), scheme: None, history_len: 20 }
+    }
+
+    /// Only accept URLs with the given scheme, e.g. `"https"`,
+    /// which is displayed as a [locked prefix](EditableComboBox::locked_prefix) of the editor.
+    pub fn locked_scheme(mut self, scheme: impl Into<String>) -> Self {
+        self.scheme = Some(scheme.into());
+        self
+    }
+
+    /// Set how many URLs are remembered. Defaults to 20.
+    pub fn history_len(mut self, history_len: usize) -> Self {
+        self.history_len = history_len;
+        self
+    }
+
+    /// The URLs remembered by the picker with the ID `id_salt`, most recent first.
+    #[must_use]
+    pub fn history(ctx: &egui::Context, id_salt: impl Hash) -> Vec<String> {
+        load_history(ctx, egui::Id::new(id_salt))
+    }
+
+    /// Display the picker in the given UI.
+    pub fn show(self, ui: &mut egui::Ui, url: &mut Option<UrlValue>) -> egui::Response {
+        let Self { id_salt, scheme, history_len } = self;
+        let prefix = scheme.map(|scheme| format!("{scheme}://"));
+        let history = load_history(ui.ctx(), id_salt);
+
+        let mut combo = EditableComboBox::new(id_salt)
+            .allow_free_text(true)
+            .ghost_text(true)
+            .validate(|text| {
+                let url = text.parse::<UrlValue>()?;
+                match &prefix {
+                    Some(prefix) if !url.as_str().starts_with(prefix.as_str()) => {
+                        Err(format!("only {prefix} URLs are allowed"))
+                    }
+                    _ => Ok(()),
+                }
+            });
+        if let Some(prefix) = &prefix {
+            combo = combo.locked_prefix(prefix.clone());
+        }
+        let options = history.iter().map(|url| UrlOption(url));
+        let response = combo.show(ui, url, options);
+
+        if response.changed()
+            && let Some(url) = url
+        {
+            let mut history = history;
+            history.retain(|entry| entry != url.as_str());
+            history.insert(0, url.to_string());
+            history.truncate(history_len);
+            ui.ctx().memory_mut(|mem| mem.data.insert_persisted(history_id(id_salt), history));
+        }
+        response
+    }
+}
+
+fn history_id(id_salt: egui::Id) -> egui::Id { id_salt.with("url history") }
+
+fn load_history(ctx: &egui::Context, id_salt: egui::Id) -> Vec<String> {
+    ctx.memory_mut(|mem| mem.data.get_persisted::<Vec<String>>(history_id(id_salt)))
+        .unwrap_or_default()
+}
+
+/// A remembered URL, matching text typed with or without its scheme.
+struct UrlOption<'a>(&'a str);
+
+impl UrlOption<'_> {
+    /// The URL without its scheme if the typed `text` has no scheme.
+    fn matched_part(&self, text: &str) -> &str {
+        if text.contains("://") {
+            self.0
+        } else {
+            self.0.split_once("://").map_or(self.0, |(_, rest)| rest)
+        }
+    }
+}
+
+impl ValueOption<Option<UrlValue>> for UrlOption<'_> {
+    fn filter_by_text(&self, text: &str, _: FilterState) -> FilterResult {
+        FilterResult::from_case_insensitive_substring(self.matched_part(text), text.trim())
+    }
+
+    fn display(&self, _text: &str) -> impl IntoAtoms<'_> { self.0 }
+
+    fn display_highlighted(&self, text: &str, highlight: &MatchHighlight) -> Option<LayoutJob> {
+        Some(highlight.layout_substring(self.0, text.trim()))
+    }
+
+    fn into_value(self, _text: &str) -> Option<UrlValue> { self.0.parse().ok() }
+
+    fn equals_value(&self, value: &Option<UrlValue>, _text: &str) -> bool {
+        value.as_ref().is_some_and(|url| url.as_str() == self.0)
+    }
+
+    // The typed text is completed without the scheme if it has none,
+    // so that the rest of the URL is displayed as ghost text.
+    fn editable_text(&self, text: &str) -> Option<String> {
+        Some(String::from(if text.is_empty() { self.0 } else { self.matched_part(text) }))
+    }
+}
@@ -0,0 +1,97 @@
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+type Check = Box<dyn Fn(&str) -> Result<(), String> + Send + Sync>;
+
+/// Validates the text of a custom value entered with
+/// [`CustomOption::Validated`](crate::CustomOption::Validated).
+///
+/// The checks are composed with the builder methods and run in order.
+/// While the text is invalid, the custom row displays the reason of the first failing check
+/// and cannot be selected.
+///
+/// # Example
+/// ```
+/// use std::sync::Arc;
+///
+/// use egui_editable_combobox::{ComboModel, CustomOption, CustomValue, Validator};
+///
+/// let validator = Arc::new(Validator::new().max_chars(8).check(|text| {
+///     if text.chars().all(char::is_alphanumeric) {
+///         Ok(())
+///     } else {
+///         Err("letters and digits only".into())
+///     }
+/// }));
+/// let options = [CustomOption::Value("main"), CustomOption::Validated(validator)];
+/// let mut model = ComboModel::<CustomValue<String>, _>::new(options);
+///
+/// model.input_text("feature/x");
+/// assert!(model.commit().is_none());
+/// model.input_text("dev");
+/// assert!(matches!(model.commit(), Some(CustomValue::Custom(text)) if text == "dev"));
+/// ```
+#[derive(Default)]
+pub struct Validator {
+    checks: Vec<Check>,
+}
+
+impl Validator {
+    /// Creates a validator accepting any text.
+    #[must_use]
+    pub fn new() -> Self { Self::default() }
+
+    /// Rejects text shorter than `min` characters.
+    #[must_use]
+    pub fn min_chars(self, min: usize) -> Self {
+        self.check(move |text| {
+            if text.chars().count() >= min {
+                Ok(())
+            } else {
+                Err(format!("at least {min} characters"))
+            }
+        })
+    }
+
+    /// Rejects text longer than `max` characters.
+    #[must_use]
+    pub fn max_chars(self, max: usize) -> Self {
+        self.check(move |text| {
+            if text.chars().count() <= max {
+                Ok(())
+            } else {
+                Err(format!("at most {max} characters"))
+            }
+        })
+    }
+
+    /// Rejects text not matching `regex`, with `reason` displayed on the custom row.
+    ///
+    /// The regex matches anywhere in the text unless it is anchored with `^` and `$`.
+    #[cfg(feature = "regex")]
+    #[must_use]
+    pub fn regex(self, regex: regex::Regex, reason: impl Into<String>) -> Self {
+        let reason = reason.into();
+        self.check(move |text| if regex.is_match(text) { Ok(()) } else { Err(reason.clone()) })
+    }
+
+    /// Rejects text for which `check` returns the reason as an error.
+    #[must_use]
+    pub fn check(
+        mut self,
+        check: impl Fn(&str) -> Result<(), String> + Send + Sync + 'static,
+    ) -> Self {
+        self.checks.push(Box::new(check));
+        self
+    }
+
+    /// Runs the checks on `text`, returning the reason of the first failing check.
+    ///
+    /// # Errors
+    /// Returns the reason if `text` is invalid.
+    pub fn validate(&self, text: &str) -> Result<(), String> {
+        self.checks.iter().try_for_each(|check| check(text))
+    }
+}
@@ -1,7 +1,14 @@
-use std::fmt::Display;
-use std::str::FromStr;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::sync::Arc;
+use core::fmt::Display;
+use core::str::FromStr;
 
-use egui::IntoAtoms;
+use egui::text::LayoutJob;
+use egui::{Atom, Atoms, IntoAtoms, RichText};
+
+use crate::flatten::flatten_control_chars;
+use crate::{MatchHighlight, MatchMode, Matcher, Validator};
 
 /// The selected value of an [`EditableComboBox`](crate::EditableComboBox).
 pub trait Value {
@@ -10,10 +17,26 @@ pub trait Value {
     /// This conversion is used to populate the text editor
     /// when the user is not editing and the value is changed externally.
     fn to_editable(&self) -> String;
+
+    /// Parses the text edited by the user back into a value.
+    ///
+    /// This is used when the text itself is committed as the value
+    /// instead of a selected option,
+    /// e.g. in [`CompletionScope::Segment`](crate::CompletionScope::Segment).
+    /// Returns `None` if the text does not represent a valid value.
+    #[must_use]
+    fn try_from_editable(_text: &str) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        None
+    }
 }
 
 impl Value for String {
     fn to_editable(&self) -> String { self.clone() }
+
+    fn try_from_editable(text: &str) -> Option<Self> { Some(text.to_string()) }
 }
 
 /// An option provided when displaying the list of selectable values.
@@ -30,38 +53,129 @@ pub trait ValueOption<V> {
     /// Displays this option in the dropdown list.
     fn display(&self, text: &str) -> impl IntoAtoms<'_>;
 
+    /// Displays this option with the characters matching `text` highlighted,
+    /// or returns `None` to display it with [`display`](Self::display) instead.
+    ///
+    /// [`MatchHighlight::layout`] lays out the text with the given matches highlighted.
+    fn display_highlighted(&self, _text: &str, _highlight: &MatchHighlight) -> Option<LayoutJob> {
+        None
+    }
+
     /// Converts this option into the value.
     fn into_value(self, text: &str) -> V;
 
     /// Tests if this option can be converted into the same value as `value`.
     fn equals_value(&self, value: &V, text: &str) -> bool;
+
+    /// The text that the user would type to select this option.
+    ///
+    /// This is used to prefill the editor when the option is renamed.
+    /// Returns `None` if the option has no editable text representation.
+    fn editable_text(&self, _text: &str) -> Option<String> { None }
+
+    /// An icon displayed before this option, such as an [`egui::Image`] or an emoji.
+    ///
+    /// Unlike an icon returned from [`display`](Self::display),
+    /// this icon is also displayed when the matches are highlighted.
+    fn icon(&self) -> Option<Atom<'_>> { None }
+
+    /// A secondary line of text displayed dimmed under this option,
+    /// e.g. the email address of a user.
+    ///
+    /// The description is only displayed and not matched when filtering.
+    fn description(&self) -> Option<&str> { None }
+
+    /// Whether this option is a separator between groups of options,
+    /// such as those inserted by [`with_separators`](crate::with_separators).
+    ///
+    /// Separators are displayed as thin lines between matching options,
+    /// are skipped by the cursor and cannot be selected.
+    fn is_separator(&self) -> bool { false }
+
+    /// Whether this option can be selected with the user text `text`.
+    ///
+    /// Disabled options are displayed grayed out and skipped by the cursor,
+    /// e.g. a custom value failing validation.
+    fn is_enabled(&self, _text: &str) -> bool { true }
 }
 
 /// Whether the user text fully or partially matched this option.
+///
+/// More kinds of matches may be added, so matching on a result needs a wildcard arm.
+#[non_exhaustive]
 pub enum FilterResult {
     /// The option fully matches the user text.
     Exact,
     /// The option partially matches the user text.
     Partial,
+    /// The option partially matches the user text with a relevance score.
+    ///
+    /// If any option returns a score, the displayed options are sorted by relevance:
+    /// exact matches first, then scored matches with higher scores first,
+    /// then the other partial matches, each group in the original order.
+    Scored(f32),
     /// The option does not match the user text.
     None,
 }
 
 impl FilterResult {
     /// Filters `full` by allowing `input` to be a case-insensitive substring.
+    ///
+    /// Newlines and other control characters in `full` are matched as spaces.
     pub fn from_case_insensitive_substring(
         full: impl AsRef<str>,
         input: impl AsRef<str>,
     ) -> FilterResult {
-        if full.as_ref() == input.as_ref() {
+        let full = flatten_control_chars(full.as_ref());
+        let (full, input) = (full.as_ref(), input.as_ref());
+        if full == input {
             FilterResult::Exact
+        } else if full.is_ascii() && input.is_ascii() {
+            // Avoid allocating lowercase copies in the common case.
+            let matched = input.is_empty()
+                || full
+                    .as_bytes()
+                    .windows(input.len())
+                    .any(|window| window.eq_ignore_ascii_case(input.as_bytes()));
+            if matched { FilterResult::Partial } else { FilterResult::None }
         } else {
-            let full = full.as_ref().to_lowercase();
-            let input = input.as_ref().to_lowercase();
+            let full = full.to_lowercase();
+            let input = input.to_lowercase();
 
             if full.contains(&input) { FilterResult::Partial } else { FilterResult::None }
         }
     }
+
+    /// Filters `full` by allowing `input` to be a case-insensitive subsequence,
+    /// e.g. "esa" matches "Eurasia".
+    /// Newlines and other control characters in `full` are matched as spaces.
+    ///
+    /// See [`fuzzy_score`](crate::fuzzy_score) for ranking the matches.
+    pub fn from_fuzzy(full: impl AsRef<str>, input: impl AsRef<str>) -> FilterResult {
+        if flatten_control_chars(full.as_ref()) == input.as_ref() {
+            FilterResult::Exact
+        } else if crate::fuzzy_score(full.as_ref(), input.as_ref()).is_some() {
+            FilterResult::Partial
+        } else {
+            FilterResult::None
+        }
+    }
+
+    /// Filters `full` by matching `input` in the given [`MatchMode`].
+    pub fn from_match_mode(
+        full: impl AsRef<str>,
+        input: impl AsRef<str>,
+        mode: MatchMode,
+    ) -> FilterResult {
+        let (full, input) = (full.as_ref(), input.as_ref());
+        if full == input {
+            FilterResult::Exact
+        } else if mode.match_text(input, full).is_some() {
+            FilterResult::Partial
+        } else {
+            FilterResult::None
+        }
+    }
 }
 
 /// State provided to [`ValueOption::filter_by_text`],
@@ -80,9 +194,15 @@ impl ValueOption<String> for String {
 
     fn display(&self, _text: &str) -> impl IntoAtoms<'_> { self.as_str() }
 
+    fn display_highlighted(&self, text: &str, highlight: &MatchHighlight) -> Option<LayoutJob> {
+        Some(highlight.layout_substring(self, text))
+    }
+
     fn into_value(self, _text: &str) -> String { self }
 
     fn equals_value(&self, value: &String, _text: &str) -> bool { self == value }
+
+    fn editable_text(&self, _text: &str) -> Option<String> { Some(self.clone()) }
 }
 
 impl ValueOption<String> for &str {
@@ -92,9 +212,15 @@ impl ValueOption<String> for &str {
 
     fn display(&self, _text: &str) -> impl IntoAtoms<'_> { *self }
 
+    fn display_highlighted(&self, text: &str, highlight: &MatchHighlight) -> Option<LayoutJob> {
+        Some(highlight.layout_substring(self, text))
+    }
+
     fn into_value(self, _text: &str) -> String { self.to_string() }
 
     fn equals_value(&self, value: &String, _text: &str) -> bool { self == value }
+
+    fn editable_text(&self, _text: &str) -> Option<String> { Some(self.to_string()) }
 }
 
 /// A wrapper implementing [`Value`] and [`ValueOption`]
@@ -108,6 +234,8 @@ pub struct ParseDisplayValue<T>(pub T);
 
 impl<T: FromStr + Display> Value for ParseDisplayValue<T> {
     fn to_editable(&self) -> String { self.0.to_string() }
+
+    fn try_from_editable(text: &str) -> Option<Self> { text.parse().ok().map(ParseDisplayValue) }
 }
 
 impl<T: FromStr + Display + PartialEq> ValueOption<ParseDisplayValue<T>> for ParseDisplayValue<T> {
@@ -117,9 +245,73 @@ impl<T: FromStr + Display + PartialEq> ValueOption<ParseDisplayValue<T>> for Par
 
     fn display(&self, _text: &str) -> impl IntoAtoms<'_> { self.0.to_string() }
 
+    fn display_highlighted(&self, text: &str, highlight: &MatchHighlight) -> Option<LayoutJob> {
+        Some(highlight.layout_substring(&self.0.to_string(), text))
+    }
+
     fn into_value(self, _text: &str) -> ParseDisplayValue<T> { self }
 
     fn equals_value(&self, value: &ParseDisplayValue<T>, _text: &str) -> bool { self.0 == value.0 }
+
+    fn editable_text(&self, _text: &str) -> Option<String> { Some(self.0.to_string()) }
+}
+
+/// A wrapper implementing [`Value`] and [`ValueOption`]
+/// by delegating to [`FromStr`] and [`AsRef<str>`].
+///
+/// This works like [`ParseDisplayValue`],
+/// but the options are filtered and displayed by borrowing their text
+/// instead of formatting a new string for each option in every frame,
+/// which matters for lists of thousands of options.
+/// The trait bounds are tailored to [strum](https://docs.rs/strum) enums
+/// deriving `AsRefStr` and `EnumString`.
+///
+/// # Example
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// use egui_editable_combobox::{AsRefStrValue, EditableComboBox};
+/// use strum::IntoEnumIterator;
+///
+/// #[derive(Clone, PartialEq, strum::EnumIter, strum::AsRefStr, strum::EnumString)]
+/// enum Continent {
+///     Africa,
+///     Antarctica,
+///     Eurasia,
+/// }
+///
+/// let mut continent = AsRefStrValue(Continent::Antarctica);
+///
+/// EditableComboBox::new("continent").show(
+///     ui,
+///     &mut continent,
+///     Continent::iter().map(AsRefStrValue),
+/// );
+/// # });
+/// ```
+pub struct AsRefStrValue<T>(pub T);
+
+impl<T: FromStr + AsRef<str>> Value for AsRefStrValue<T> {
+    fn to_editable(&self) -> String { self.0.as_ref().to_string() }
+
+    fn try_from_editable(text: &str) -> Option<Self> { text.parse().ok().map(AsRefStrValue) }
+}
+
+impl<T: FromStr + AsRef<str> + PartialEq> ValueOption<AsRefStrValue<T>> for AsRefStrValue<T> {
+    fn filter_by_text(&self, text: &str, _: FilterState) -> FilterResult {
+        FilterResult::from_case_insensitive_substring(self.0.as_ref(), text)
+    }
+
+    fn display(&self, _text: &str) -> impl IntoAtoms<'_> { self.0.as_ref() }
+
+    fn display_highlighted(&self, text: &str, highlight: &MatchHighlight) -> Option<LayoutJob> {
+        Some(highlight.layout_substring(self.0.as_ref(), text))
+    }
+
+    fn into_value(self, _text: &str) -> AsRefStrValue<T> { self }
+
+    fn equals_value(&self, value: &AsRefStrValue<T>, _text: &str) -> bool { self.0 == value.0 }
+
+    fn editable_text(&self, _text: &str) -> Option<String> { Some(self.0.as_ref().to_string()) }
 }
 
 /// The selected value for [`CustomOption`].
@@ -141,11 +333,19 @@ impl<V: Value> Value for CustomValue<V> {
             CustomValue::Custom(s) => s.clone(),
         }
     }
+
+    fn try_from_editable(text: &str) -> Option<Self> {
+        Some(
+            V::try_from_editable(text)
+                .map_or_else(|| CustomValue::Custom(text.to_string()), CustomValue::Value),
+        )
+    }
 }
 
 /// Wraps a [`Value`] to add a "custom" option.
 ///
 /// See [`EditableComboBox`](crate::EditableComboBox) for example usage.
+#[derive(Clone)]
 pub enum CustomOption<V> {
     /// Provides an existing value option.
     Value(V),
@@ -154,9 +354,14 @@ pub enum CustomOption<V> {
     /// This option should be provided after all [`Value`](CustomOption::Value) options
     /// so that it correctly hides when a previous value was matched exactly.
     Custom,
+    /// Allows entering a custom value that passes the [`Validator`].
+    ///
+    /// While the text is invalid, the row displays the reason and cannot be selected.
+    /// Like [`Custom`](CustomOption::Custom), this option should be provided last.
+    Validated(Arc<Validator>),
 }
 
-enum IntoAtomsEither<A, B> {
+pub(crate) enum IntoAtomsEither<A, B> {
     Left(A),
     Right(B),
 }
@@ -178,7 +383,7 @@ impl<V, Opt: ValueOption<V>> ValueOption<CustomValue<V>> for CustomOption<Opt> {
     fn filter_by_text(&self, text: &str, state: FilterState) -> FilterResult {
         match self {
             CustomOption::Value(v) => v.filter_by_text(text, state),
-            CustomOption::Custom => {
+            CustomOption::Custom | CustomOption::Validated(_) => {
                 if state.had_exact {
                     FilterResult::None
                 } else if state.prev_matches > 0 {
@@ -193,22 +398,141 @@ impl<V, Opt: ValueOption<V>> ValueOption<CustomValue<V>> for CustomOption<Opt> {
     fn display(&self, text: &str) -> impl IntoAtoms<'_> {
         match self {
             CustomOption::Value(v) => IntoAtomsEither::Left(v.display(text)),
-            CustomOption::Custom => IntoAtomsEither::Right(("Custom: ", text)),
+            CustomOption::Custom => IntoAtomsEither::Right(Atoms::new(("Custom: ", text))),
+            CustomOption::Validated(validator) => {
+                let mut atoms = Atoms::new(("Custom: ", text));
+                if let Err(reason) = validator.validate(text) {
+                    atoms.push_right(RichText::new(format!(" ({reason})")).weak());
+                }
+                IntoAtomsEither::Right(atoms)
+            }
+        }
+    }
+
+    fn display_highlighted(&self, text: &str, highlight: &MatchHighlight) -> Option<LayoutJob> {
+        match self {
+            CustomOption::Value(v) => v.display_highlighted(text, highlight),
+            CustomOption::Custom | CustomOption::Validated(_) => None,
         }
     }
 
     fn into_value(self, text: &str) -> CustomValue<V> {
         match self {
             CustomOption::Value(v) => CustomValue::Value(v.into_value(text)),
-            CustomOption::Custom => CustomValue::Custom(text.to_string()),
+            CustomOption::Custom | CustomOption::Validated(_) => {
+                CustomValue::Custom(text.to_string())
+            }
         }
     }
 
     fn equals_value(&self, value: &CustomValue<V>, text: &str) -> bool {
         match (self, value) {
             (CustomOption::Value(this), CustomValue::Value(that)) => this.equals_value(that, text),
-            (CustomOption::Custom, CustomValue::Custom(custom)) => text == custom,
+            (CustomOption::Custom | CustomOption::Validated(_), CustomValue::Custom(custom)) => {
+                text == custom
+            }
             _ => false,
         }
     }
+
+    fn editable_text(&self, text: &str) -> Option<String> {
+        match self {
+            CustomOption::Value(v) => v.editable_text(text),
+            CustomOption::Custom | CustomOption::Validated(_) => None,
+        }
+    }
+
+    fn icon(&self) -> Option<Atom<'_>> {
+        match self {
+            CustomOption::Value(v) => v.icon(),
+            CustomOption::Custom | CustomOption::Validated(_) => None,
+        }
+    }
+
+    fn description(&self) -> Option<&str> {
+        match self {
+            CustomOption::Value(v) => v.description(),
+            CustomOption::Custom | CustomOption::Validated(_) => None,
+        }
+    }
+
+    fn is_separator(&self) -> bool { matches!(self, CustomOption::Value(v) if v.is_separator()) }
+
+    fn is_enabled(&self, text: &str) -> bool {
+        match self {
+            CustomOption::Value(v) => v.is_enabled(text),
+            CustomOption::Custom => true,
+            CustomOption::Validated(validator) => validator.validate(text).is_ok(),
+        }
+    }
+}
+
+/// Wraps an option to also match it by hidden search keywords.
+///
+/// The keywords are only used for filtering and never displayed,
+/// e.g. to find products by SKU codes and tags while listing their names.
+/// The option matches if it matches the text by itself,
+/// or if the text is a case-insensitive substring of `keywords`.
+///
+/// # Example
+/// ```
+/// use egui_editable_combobox::{ComboModel, Keyworded};
+///
+/// let mut model = ComboModel::<String, _>::new([
+///     Keyworded::new("Espresso", "SKU-1001 coffee hot"),
+///     Keyworded::new("Lemonade", "SKU-2002 cold"),
+/// ]);
+/// model.input_text("cold");
+/// assert_eq!(model.filtered(), [1]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Keyworded<Opt> {
+    /// The wrapped option.
+    pub option:   Opt,
+    /// The text searched in addition to the option itself.
+    pub keywords: String,
+}
+
+impl<Opt> Keyworded<Opt> {
+    /// Wraps `option` with the search `keywords`.
+    pub fn new(option: Opt, keywords: impl Into<String>) -> Self {
+        Self { option, keywords: keywords.into() }
+    }
+}
+
+impl<V, Opt: ValueOption<V>> ValueOption<V> for Keyworded<Opt> {
+    fn filter_by_text(&self, text: &str, state: FilterState) -> FilterResult {
+        match self.option.filter_by_text(text, state) {
+            FilterResult::None => {
+                match FilterResult::from_case_insensitive_substring(&self.keywords, text) {
+                    // Matching the keywords is never an exact match of the option.
+                    FilterResult::None => FilterResult::None,
+                    FilterResult::Exact | FilterResult::Partial | FilterResult::Scored(_) => {
+                        FilterResult::Partial
+                    }
+                }
+            }
+            result => result,
+        }
+    }
+
+    fn display(&self, text: &str) -> impl IntoAtoms<'_> { self.option.display(text) }
+
+    fn display_highlighted(&self, text: &str, highlight: &MatchHighlight) -> Option<LayoutJob> {
+        self.option.display_highlighted(text, highlight)
+    }
+
+    fn into_value(self, text: &str) -> V { self.option.into_value(text) }
+
+    fn equals_value(&self, value: &V, text: &str) -> bool { self.option.equals_value(value, text) }
+
+    fn editable_text(&self, text: &str) -> Option<String> { self.option.editable_text(text) }
+
+    fn icon(&self) -> Option<Atom<'_>> { self.option.icon() }
+
+    fn description(&self) -> Option<&str> { self.option.description() }
+
+    fn is_separator(&self) -> bool { self.option.is_separator() }
+
+    fn is_enabled(&self, text: &str) -> bool { self.option.is_enabled(text) }
 }
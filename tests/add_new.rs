@@ -0,0 +1,91 @@
+//! Checks that `show_with_add_new` selects the value created by the callback
+//! after the "+" button is clicked.
+
+mod common;
+
+use common::{click, run_frame, text};
+use egui_editable_combobox::{AddNew, EditableComboBox};
+
+struct Profiles {
+    ctx:      egui::Context,
+    selected: String,
+    /// The name typed into the creation form.
+    new_name: String,
+    /// Whether the creation form is submitted in the frame.
+    create:   bool,
+    /// The number of frames the creation form was shown in.
+    shown:    usize,
+}
+
+impl Profiles {
+    fn new() -> Self {
+        Self {
+            ctx:      egui::Context::default(),
+            selected: String::from("Default"),
+            new_name: String::new(),
+            create:   false,
+            shown:    0,
+        }
+    }
+
+    /// Runs a frame, returning whether the value changed and the texts painted with their centers.
+    fn run(&mut self, events: Vec<egui::Event>) -> (bool, Vec<(String, egui::Pos2)>) {
+        let mut changed = false;
+        let output = run_frame(&self.ctx.clone(), events, |ui| {
+            let resp = EditableComboBox::new("profile").show_with_add_new(
+                ui,
+                &mut self.selected,
+                ["Default"],
+                |ui| {
+                    self.shown += 1;
+                    ui.add(egui::TextEdit::singleline(&mut self.new_name).hint_text("Name"));
+                    if self.create {
+                        AddNew::Created(std::mem::take(&mut self.new_name))
+                    } else {
+                        AddNew::Pending
+                    }
+                },
+            );
+            changed = resp.changed();
+        });
+        let texts = output
+            .shapes
+            .into_iter()
+            .filter_map(|clipped| match clipped.shape {
+                egui::Shape::Text(text) => Some((
+                    text.galley.text().to_owned(),
+                    text.pos + text.galley.rect.center().to_vec2(),
+                )),
+                _ => None,
+            })
+            .collect();
+        (changed, texts)
+    }
+
+    /// Clicks the center of the painted `label`.
+    fn click_text(&mut self, label: &str) -> bool {
+        let (_, texts) = self.run(vec![]);
+        let (_, center) = texts.iter().find(|(text, _)| text == label).expect("text shown");
+        self.run(click(*center)).0
+    }
+}
+
+#[test]
+fn created_value_is_selected() {
+    let mut profiles = Profiles::new();
+    profiles.run(vec![]);
+    profiles.click_text("+");
+    profiles.click_text("Name");
+    profiles.run(text("Work"));
+    assert_eq!(profiles.selected, "Default", "the value is kept while pending");
+
+    profiles.create = true;
+    let (changed, _) = profiles.run(vec![]);
+    assert!(changed);
+    assert_eq!(profiles.selected, "Work");
+
+    let shown = profiles.shown;
+    let (_, texts) = profiles.run(vec![]);
+    assert_eq!(profiles.shown, shown, "the form is closed once created");
+    assert!(texts.iter().any(|(text, _)| text == "Work"), "{texts:?}");
+}
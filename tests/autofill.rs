@@ -0,0 +1,57 @@
+//! Checks that the autofill candidates supplied for a hint are merged after the options.
+
+mod common;
+
+use common::{click, enter, key_with, run_frame, text};
+use egui_editable_combobox::{AutofillHint, EditableComboBox, EmailValue, ParseDisplayValue};
+
+type Address = ParseDisplayValue<EmailValue>;
+
+fn address(text: &str) -> Address { ParseDisplayValue(text.parse().expect("valid address")) }
+
+fn select_all() -> Vec<egui::Event> { key_with(egui::Key::A, egui::Modifiers::COMMAND) }
+
+/// Runs a frame of a recipient combo box,
+/// returning its rect and the number of options displayed.
+fn run(
+    ctx: &egui::Context,
+    recipient: &mut Address,
+    events: Vec<egui::Event>,
+) -> (egui::Rect, usize) {
+    let mut shown = (egui::Rect::NOTHING, 0);
+    run_frame(ctx, events, |ui| {
+        let output = EditableComboBox::new("recipient")
+            .autofill(AutofillHint::Email)
+            .show_with_output(ui, recipient, [address("team@example.com")]);
+        shown = (output.response.rect, output.filtered_count);
+    });
+    shown
+}
+
+#[test]
+fn candidates_are_merged() {
+    let ctx = egui::Context::default();
+    AutofillHint::Email
+        .set_candidates(&ctx, ["grace@example.com", "team@example.com", "not an address"]);
+    let mut recipient = address("ada@example.com");
+    let (rect, _) = run(&ctx, &mut recipient, vec![]);
+    run(&ctx, &mut recipient, click(rect.center()));
+    run(&ctx, &mut recipient, select_all());
+    // The duplicate and the invalid candidate are skipped.
+    run(&ctx, &mut recipient, text("example"));
+    assert_eq!(run(&ctx, &mut recipient, vec![]).1, 2);
+
+    run(&ctx, &mut recipient, select_all());
+    run(&ctx, &mut recipient, text("gra"));
+    assert_eq!(run(&ctx, &mut recipient, vec![]).1, 1);
+    run(&ctx, &mut recipient, enter());
+    assert_eq!(recipient.0.as_str(), "grace@example.com");
+}
+
+#[test]
+fn candidates_need_hint() {
+    let ctx = egui::Context::default();
+    AutofillHint::Name.set_candidates(&ctx, ["Grace Hopper"]);
+    assert_eq!(AutofillHint::Name.candidates(&ctx), ["Grace Hopper"]);
+    assert!(AutofillHint::Email.candidates(&ctx).is_empty());
+}
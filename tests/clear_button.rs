@@ -0,0 +1,68 @@
+//! Checks that the clear button resets the value without opening the popup.
+
+mod common;
+
+use common::{click, run_frame};
+use egui_editable_combobox::{EditableComboBox, ShowOutput, ValueChange};
+
+fn run(ctx: &egui::Context, value: &mut String, events: Vec<egui::Event>) -> ShowOutput {
+    let mut output = None;
+    run_frame(ctx, events, |ui| {
+        output = Some(EditableComboBox::new("clear").clear_button("").show_with_output(
+            ui,
+            value,
+            ["Alice", "Bob", "Carol"],
+        ));
+    });
+    output.expect("combo box shown")
+}
+
+/// A point on the clear button at the trailing edge of the editor.
+fn clear_button_pos(rect: egui::Rect) -> egui::Pos2 { rect.right_center() - egui::vec2(8.0, 0.0) }
+
+#[test]
+fn clear_button_resets_value() {
+    let ctx = egui::Context::default();
+    let mut value = String::from("Bob");
+    run(&ctx, &mut value, vec![]);
+    let rect = run(&ctx, &mut value, vec![]).response.rect;
+
+    let output = run(&ctx, &mut value, click(clear_button_pos(rect)));
+    assert_eq!(value, "");
+    assert_eq!(output.change, Some(ValueChange::Cleared));
+    assert!(output.response.changed());
+
+    let output = run(&ctx, &mut value, vec![]);
+    assert!(!output.popup_open, "clearing does not open the popup");
+    assert!(!output.response.has_focus());
+}
+
+#[test]
+fn clear_button_discards_typed_text() {
+    let ctx = egui::Context::default();
+    let mut value = String::from("Bob");
+    run(&ctx, &mut value, vec![]);
+    let rect = run(&ctx, &mut value, vec![]).response.rect;
+    run(&ctx, &mut value, click(rect.center()));
+    run(&ctx, &mut value, vec![egui::Event::Text("Car".into())]);
+
+    run(&ctx, &mut value, click(clear_button_pos(rect)));
+    run(&ctx, &mut value, vec![]);
+    let output = run(&ctx, &mut value, vec![]);
+    assert_eq!(value, "");
+    assert!(!output.popup_open);
+}
+
+#[test]
+fn clear_button_hidden_for_default_value() {
+    let ctx = egui::Context::default();
+    let mut value = String::new();
+    run(&ctx, &mut value, vec![]);
+    let rect = run(&ctx, &mut value, vec![]).response.rect;
+
+    // Without a clear button, the click lands on the editor and opens the popup.
+    run(&ctx, &mut value, click(clear_button_pos(rect)));
+    let output = run(&ctx, &mut value, vec![]);
+    assert!(output.popup_open);
+    assert_eq!(output.change, None);
+}
@@ -0,0 +1,225 @@
+//! Checks that selections vetoed by `on_before_commit`,
+//! or completing into an invalid value, leave the value and the hooks untouched
+//! and keep the popup open.
+
+mod common;
+
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::str::FromStr;
+
+use common::{click, enter, key_with, painted_texts, run_frame, text};
+use egui_editable_combobox::{CommitGuard, CompletionScope, EditableComboBox, ParseDisplayValue};
+
+/// The hooks called in the frames run so far.
+#[derive(Default)]
+struct Hooks {
+    guarded:   Vec<String>,
+    selected:  Vec<usize>,
+    feedbacks: usize,
+}
+
+/// Shows a combo box of roles, where only admins may pick `admin`.
+struct RoleForm {
+    ctx:        egui::Context,
+    role:       String,
+    hooks:      RefCell<Hooks>,
+    rect:       egui::Rect,
+    popup_open: bool,
+}
+
+impl RoleForm {
+    fn new() -> Self {
+        Self {
+            ctx:        egui::Context::default(),
+            role:       String::from("viewer"),
+            hooks:      RefCell::default(),
+            rect:       egui::Rect::NOTHING,
+            popup_open: false,
+        }
+    }
+
+    /// Runs a frame, returning the painted texts.
+    fn run(&mut self, events: Vec<egui::Event>) -> Vec<String> {
+        let hooks = &self.hooks;
+        let output = run_frame(&self.ctx.clone(), events, |ui| {
+            let output = EditableComboBox::new("role")
+                .on_before_commit(|_, candidate| {
+                    let candidate = candidate.to_editable();
+                    hooks.borrow_mut().guarded.push(candidate.clone());
+                    if candidate == "admin" {
+                        CommitGuard::Deny(String::from("Only admins can grant admin"))
+                    } else {
+                        CommitGuard::Allow
+                    }
+                })
+                .on_select(|index| hooks.borrow_mut().selected.push(index))
+                .on_commit_feedback(|_| hooks.borrow_mut().feedbacks += 1)
+                .show_with_output(ui, &mut self.role, ["viewer", "editor", "admin"]);
+            self.rect = output.response.rect;
+            self.popup_open = output.popup_open;
+        });
+        painted_texts(output)
+    }
+
+    fn focus(&mut self) {
+        self.run(vec![]);
+        self.run(vec![]);
+        self.run(click(self.rect.center()));
+        self.run(vec![]);
+    }
+}
+
+#[test]
+fn denied_selection_keeps_value_and_popup() {
+    let mut form = RoleForm::new();
+    form.focus();
+    form.run(text("adm"));
+    form.run(enter());
+    let texts = form.run(vec![]);
+
+    assert_eq!(form.role, "viewer");
+    assert!(form.popup_open);
+    assert!(texts.contains(&String::from("Only admins can grant admin")));
+    let hooks = form.hooks.borrow();
+    assert_eq!(hooks.guarded, ["admin"]);
+    assert!(hooks.selected.is_empty());
+    assert_eq!(hooks.feedbacks, 0);
+}
+
+#[test]
+fn allowed_selection_commits_after_denial() {
+    let mut form = RoleForm::new();
+    form.focus();
+    form.run(text("adm"));
+    form.run(enter());
+    form.run(vec![]);
+
+    // Editing the text hides the reason.
+    form.run(key_with(egui::Key::A, egui::Modifiers::COMMAND));
+    form.run(text("edi"));
+    let texts = form.run(vec![]);
+    assert!(!texts.contains(&String::from("Only admins can grant admin")));
+    form.run(enter());
+    form.run(vec![]);
+
+    assert_eq!(form.role, "editor");
+    let hooks = form.hooks.borrow();
+    assert_eq!(hooks.guarded, ["admin", "editor"]);
+    assert_eq!(hooks.selected, [1]);
+    assert_eq!(hooks.feedbacks, 1);
+}
+
+/// Distinct colors separated by commas.
+#[derive(Debug, Clone, PartialEq)]
+struct Colors(Vec<String>);
+
+impl FromStr for Colors {
+    type Err = ();
+
+    fn from_str(text: &str) -> Result<Self, ()> {
+        let mut colors: Vec<String> = Vec::new();
+        for color in text.split(',').map(str::trim) {
+            if colors.iter().any(|other| other == color) {
+                return Err(());
+            }
+            colors.push(color.to_owned());
+        }
+        Ok(Self(colors))
+    }
+}
+
+impl fmt::Display for Colors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { f.write_str(&self.0.join(", ")) }
+}
+
+fn colors(text: &str) -> ParseDisplayValue<Colors> { ParseDisplayValue(text.parse().unwrap()) }
+
+#[test]
+fn rejected_segment_completion_fires_no_hooks() {
+    let ctx = egui::Context::default();
+    let mut value = colors("red");
+    let selected = RefCell::new(Vec::new());
+    let feedbacks = RefCell::new(0);
+    let shown = Cell::new((egui::Rect::NOTHING, false));
+    let run = |value: &mut ParseDisplayValue<Colors>, events| {
+        run_frame(&ctx, events, |ui| {
+            let output = EditableComboBox::new("colors")
+                .completion_scope(CompletionScope::Segment { delimiter: ',' })
+                .announce_commit(|colors| format!("Colors set to {colors}"))
+                .on_select(|index| selected.borrow_mut().push(index))
+                .on_commit_feedback(|_| *feedbacks.borrow_mut() += 1)
+                .show_with_output(ui, value, [colors("red"), colors("green")]);
+            shown.set((output.response.rect, output.popup_open));
+        })
+    };
+
+    run(&mut value, vec![]);
+    run(&mut value, vec![]);
+    run(&mut value, click(shown.get().0.center()));
+    run(&mut value, vec![]);
+    run(&mut value, text(", re"));
+    run(&mut value, vec![]);
+    assert_eq!(value.0, colors("red, re").0);
+    let output = run(&mut value, enter());
+    run(&mut value, vec![]);
+
+    // Completing the last segment would repeat red.
+    assert_eq!(value.0, colors("red, re").0);
+    assert!(shown.get().1, "the popup stays open");
+    assert!(selected.borrow().is_empty());
+    assert_eq!(*feedbacks.borrow(), 0);
+    assert!(
+        !output
+            .platform_output
+            .events
+            .iter()
+            .any(|event| matches!(event, egui::output::OutputEvent::ValueChanged(_)))
+    );
+}
+
+#[test]
+fn guard_vetoes_free_text() {
+    let ctx = egui::Context::default();
+    let mut role = String::from("viewer");
+    let guarded = RefCell::new(Vec::new());
+    let feedbacks = Cell::new(0);
+    let rect = Cell::new(egui::Rect::NOTHING);
+    let run = |role: &mut String, events| {
+        run_frame(&ctx, events, |ui| {
+            let output = EditableComboBox::new("role")
+                .allow_free_text(true)
+                .on_before_commit(|index, candidate| {
+                    let candidate = candidate.to_editable();
+                    guarded.borrow_mut().push((index, candidate.clone()));
+                    if candidate == "root" {
+                        CommitGuard::Deny(String::from("Nobody can grant root"))
+                    } else {
+                        CommitGuard::Allow
+                    }
+                })
+                .on_commit_feedback(|_| feedbacks.set(feedbacks.get() + 1))
+                .show_with_output(ui, role, ["viewer", "editor", "admin"]);
+            rect.set(output.response.rect);
+        })
+    };
+
+    run(&mut role, vec![]);
+    run(&mut role, vec![]);
+    run(&mut role, click(rect.get().center()));
+    run(&mut role, key_with(egui::Key::A, egui::Modifiers::COMMAND));
+    run(&mut role, text("root"));
+    run(&mut role, enter());
+    let texts = painted_texts(run(&mut role, vec![]));
+    assert_eq!(role, "viewer");
+    assert!(texts.contains(&String::from("Nobody can grant root")));
+    assert_eq!(feedbacks.get(), 0);
+
+    run(&mut role, key_with(egui::Key::A, egui::Modifiers::COMMAND));
+    run(&mut role, text("guest"));
+    run(&mut role, enter());
+    run(&mut role, vec![]);
+    assert_eq!(role, "guest");
+    assert_eq!(feedbacks.get(), 1);
+    assert_eq!(*guarded.borrow(), [(None, String::from("root")), (None, String::from("guest"))]);
+}
@@ -0,0 +1,94 @@
+//! Input events and frame helpers shared by the integration tests.
+
+#![allow(dead_code, reason = "each test uses a different subset of the helpers")]
+
+/// A primary click at `pos`, leaving the pointer there.
+pub fn click(pos: egui::Pos2) -> Vec<egui::Event> {
+    [true, false]
+        .into_iter()
+        .map(|pressed| egui::Event::PointerButton {
+            pos,
+            button: egui::PointerButton::Primary,
+            pressed,
+            modifiers: egui::Modifiers::NONE,
+        })
+        .chain([egui::Event::PointerMoved(pos)])
+        .collect()
+}
+
+/// A press and release of `key` without modifiers.
+pub fn key(key: egui::Key) -> Vec<egui::Event> { key_with(key, egui::Modifiers::NONE) }
+
+/// A press and release of `key` with `modifiers` held.
+pub fn key_with(key: egui::Key, modifiers: egui::Modifiers) -> Vec<egui::Event> {
+    [true, false]
+        .into_iter()
+        .map(|pressed| egui::Event::Key {
+            key,
+            physical_key: None,
+            pressed,
+            repeat: false,
+            modifiers,
+        })
+        .collect()
+}
+
+/// A press and release of Enter.
+pub fn enter() -> Vec<egui::Event> { key(egui::Key::Enter) }
+
+/// Typing `text`.
+pub fn text(text: &str) -> Vec<egui::Event> { vec![egui::Event::Text(text.into())] }
+
+/// Runs a frame with `events`, showing `add_contents` in a central panel.
+pub fn run_frame(
+    ctx: &egui::Context,
+    events: Vec<egui::Event>,
+    add_contents: impl FnMut(&mut egui::Ui),
+) -> egui::FullOutput {
+    run_input(ctx, egui::RawInput { events, ..Default::default() }, add_contents)
+}
+
+/// Runs a frame with `events` at `time` in seconds,
+/// showing `add_contents` in a central panel.
+pub fn run_frame_at(
+    ctx: &egui::Context,
+    time: f64,
+    events: Vec<egui::Event>,
+    add_contents: impl FnMut(&mut egui::Ui),
+) -> egui::FullOutput {
+    run_input(ctx, egui::RawInput { events, time: Some(time), ..Default::default() }, add_contents)
+}
+
+/// Runs a frame with `events` on a screen of `size`,
+/// e.g. shrunk by a virtual keyboard, showing `add_contents` in a central panel.
+pub fn run_frame_sized(
+    ctx: &egui::Context,
+    size: egui::Vec2,
+    events: Vec<egui::Event>,
+    add_contents: impl FnMut(&mut egui::Ui),
+) -> egui::FullOutput {
+    let screen_rect = Some(egui::Rect::from_min_size(egui::Pos2::ZERO, size));
+    run_input(ctx, egui::RawInput { events, screen_rect, ..Default::default() }, add_contents)
+}
+
+fn run_input(
+    ctx: &egui::Context,
+    input: egui::RawInput,
+    mut add_contents: impl FnMut(&mut egui::Ui),
+) -> egui::FullOutput {
+    ctx.run(input, |ctx| {
+        egui::CentralPanel::default().show(ctx, |ui| add_contents(ui));
+    })
+}
+
+/// The texts painted in a frame.
+pub fn painted_texts(output: egui::FullOutput) -> Vec<String> {
+    output
+        .shapes
+        .into_iter()
+        .filter_map(|clipped| match clipped.shape {
+            egui::Shape::Text(text) => Some(text.galley.text().to_owned()),
+            _ => None,
+        })
+        .collect()
+}
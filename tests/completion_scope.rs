@@ -0,0 +1,93 @@
+//! Checks that only the segment under the caret, or the text before the caret,
+//! filters the options and is replaced when an option is selected.
+
+mod common;
+
+use common::{click, enter, key, painted_texts, run_frame, text};
+use egui_editable_combobox::{CompletionScope, EditableComboBox};
+
+/// Shows a recipients field completing within `scope`.
+struct Field {
+    ctx:        egui::Context,
+    scope:      CompletionScope,
+    value:      String,
+    rect:       egui::Rect,
+    filtered:   usize,
+    popup_open: bool,
+}
+
+impl Field {
+    fn new(scope: CompletionScope) -> Self {
+        Self {
+            ctx: egui::Context::default(),
+            scope,
+            value: String::new(),
+            rect: egui::Rect::NOTHING,
+            filtered: 0,
+            popup_open: false,
+        }
+    }
+
+    /// Runs a frame, returning the painted texts.
+    fn run(&mut self, events: Vec<egui::Event>) -> Vec<String> {
+        let output = run_frame(&self.ctx.clone(), events, |ui| {
+            let output = EditableComboBox::new("recipients")
+                .completion_scope(self.scope.clone())
+                .show_with_output(ui, &mut self.value, ["alice", "bob", "carol"]);
+            self.rect = output.response.rect;
+            self.filtered = output.filtered_count;
+            self.popup_open = output.popup_open;
+        });
+        painted_texts(output)
+    }
+
+    fn focus(&mut self) {
+        self.run(vec![]);
+        self.run(vec![]);
+        self.run(click(self.rect.center()));
+        self.run(vec![]);
+    }
+}
+
+#[test]
+fn segment_under_caret_filters_and_completes() {
+    let mut field = Field::new(CompletionScope::Segment { delimiter: ',' });
+    field.focus();
+    field.run(text("alice, ca"));
+    field.run(vec![]);
+    assert_eq!(field.filtered, 1, "only the last segment filters");
+    field.run(enter());
+    let texts = field.run(vec![]);
+    assert!(texts.contains(&String::from("alice, carol")));
+    assert_eq!(field.value, "alice, carol");
+    assert!(field.popup_open, "the editor stays focused for the next segment");
+}
+
+#[test]
+fn segment_before_others_completes_in_place() {
+    let mut field = Field::new(CompletionScope::Segment { delimiter: ',' });
+    field.focus();
+    field.run(text("al, bob"));
+    field.run(key(egui::Key::Home));
+    field.run(key(egui::Key::ArrowRight));
+    field.run(key(egui::Key::ArrowRight));
+    field.run(vec![]);
+    assert_eq!(field.filtered, 1);
+    field.run(enter());
+    field.run(vec![]);
+    assert_eq!(field.value, "alice, bob");
+}
+
+#[test]
+fn text_before_caret_filters_and_completes() {
+    let mut field = Field::new(CompletionScope::BeforeCaret);
+    field.focus();
+    field.run(text("b!"));
+    field.run(key(egui::Key::ArrowLeft));
+    field.run(vec![]);
+    assert_eq!(field.filtered, 1, "the text after the caret does not filter");
+    field.run(enter());
+    let texts = field.run(vec![]);
+    assert!(texts.contains(&String::from("bob!")), "the text after the caret is kept");
+    assert_eq!(field.value, "bob!");
+}
@@ -0,0 +1,219 @@
+//! Property and example tests for the filtering and cursor logic shared by the popup,
+//! driven through the headless [`ComboModel`].
+
+use egui::{IntoAtoms, Key};
+use egui_editable_combobox::{
+    ComboModel, CustomOption, CustomValue, FilterResult, FilterState, Separated, ValueOption,
+};
+use proptest::prelude::*;
+
+/// A string option that may be disabled.
+#[derive(Debug, Clone, PartialEq)]
+struct Toggled {
+    label:   String,
+    enabled: bool,
+}
+
+impl ValueOption<String> for Toggled {
+    fn filter_by_text(&self, text: &str, _: FilterState) -> FilterResult {
+        FilterResult::from_case_insensitive_substring(&self.label, text)
+    }
+
+    fn display(&self, _: &str) -> impl IntoAtoms<'_> { self.label.as_str() }
+
+    fn into_value(self, _: &str) -> String { self.label }
+
+    fn equals_value(&self, value: &String, _: &str) -> bool { self.label == *value }
+
+    fn is_enabled(&self, _: &str) -> bool { self.enabled }
+}
+
+/// Options that may be disabled, each optionally followed by a separator.
+fn toggled_options() -> impl Strategy<Value = Vec<Separated<Toggled>>> {
+    prop::collection::vec(("[a-c]{0,3}", any::<bool>(), any::<bool>()), 0..12).prop_map(|rows| {
+        rows.into_iter()
+            .flat_map(|(label, enabled, separator)| {
+                let option = Separated::Option(Toggled { label, enabled });
+                [Some(option), separator.then_some(Separated::Separator)]
+            })
+            .flatten()
+            .collect()
+    })
+}
+
+fn motion_key() -> impl Strategy<Value = Key> {
+    prop::sample::select(vec![
+        Key::ArrowUp,
+        Key::ArrowDown,
+        Key::Home,
+        Key::End,
+        Key::PageUp,
+        Key::PageDown,
+        Key::Backspace,
+    ])
+}
+
+fn model(options: &[String], reverse_order: bool) -> ComboModel<String, String> {
+    ComboModel::new(options.iter().cloned()).reverse_order(reverse_order).page_rows(3)
+}
+
+proptest! {
+    #[test]
+    fn cursor_lands_on_displayed_option(
+        options in prop::collection::vec("[a-c]{0,3}", 0..12),
+        text in "[a-c]{0,2}",
+        keys in prop::collection::vec(motion_key(), 0..20),
+        reverse_order: bool,
+    ) {
+        let mut model = model(&options, reverse_order);
+        model.input_text(&text);
+        for key in keys {
+            model.key(key);
+            match model.cursor() {
+                Some(cursor) => prop_assert!(model.filtered().contains(&cursor)),
+                None => prop_assert!(model.filtered().is_empty()),
+            }
+        }
+    }
+
+    #[test]
+    fn cursor_skips_separators_and_disabled_options(
+        options in toggled_options(),
+        text in "[a-c]{0,2}",
+        keys in prop::collection::vec(motion_key(), 0..20),
+        reverse_order: bool,
+    ) {
+        let mut model = ComboModel::new(options.iter().cloned()).reverse_order(reverse_order);
+        model.input_text(&text);
+        let enabled = |source_index: usize| {
+            matches!(&options[source_index], Separated::Option(option) if option.enabled)
+        };
+        for key in keys {
+            model.key(key);
+            let any_enabled = model.filtered().iter().any(|&source_index| enabled(source_index));
+            match model.cursor() {
+                Some(cursor) => {
+                    prop_assert!(model.filtered().contains(&cursor));
+                    prop_assert!(enabled(cursor));
+                }
+                None => prop_assert!(!any_enabled),
+            }
+            let separator = |&source_index: &usize| options[source_index] == Separated::Separator;
+            prop_assert!(!model.filtered().iter().any(separator));
+        }
+    }
+
+    #[test]
+    fn up_then_down_is_identity(
+        options in prop::collection::vec("[a-c]{0,3}", 1..12),
+        text in "[a-c]{0,2}",
+        downs in 0usize..12,
+        reverse_order: bool,
+    ) {
+        let mut model = model(&options, reverse_order);
+        model.input_text(&text);
+        for _ in 0..downs {
+            model.key(Key::ArrowDown);
+        }
+
+        let before = model.cursor();
+        if before.is_some() && before != model.filtered().first().copied() {
+            model.key(Key::ArrowUp);
+            model.key(Key::ArrowDown);
+            prop_assert_eq!(model.cursor(), before);
+        }
+    }
+
+    #[test]
+    fn page_down_moves_like_repeated_down_without_wrapping(
+        options in prop::collection::vec("[a-c]{0,3}", 1..12),
+        text in "[a-c]{0,2}",
+        downs in 0usize..12,
+        reverse_order: bool,
+    ) {
+        let mut paged = model(&options, reverse_order).wrap_navigation(false);
+        let mut stepped = model(&options, reverse_order).wrap_navigation(false);
+        paged.input_text(&text);
+        stepped.input_text(&text);
+        for _ in 0..downs {
+            paged.key(Key::ArrowDown);
+            stepped.key(Key::ArrowDown);
+        }
+
+        paged.key(Key::PageDown);
+        for _ in 0..3 {
+            stepped.key(Key::ArrowDown);
+        }
+        prop_assert_eq!(paged.cursor(), stepped.cursor());
+    }
+
+    #[test]
+    fn filtering_arbitrary_unicode_does_not_panic(
+        options in prop::collection::vec(any::<String>(), 0..8),
+        texts in prop::collection::vec(any::<String>(), 1..4),
+    ) {
+        let mut model = ComboModel::<CustomValue<String>, _>::new(
+            options.into_iter().map(CustomOption::Value).chain([CustomOption::Custom]),
+        );
+        for text in texts {
+            model.input_text(&text);
+            prop_assert!(!model.filtered().is_empty(), "the custom option matches any text");
+        }
+    }
+
+    #[test]
+    fn ascii_substring_matches_lowercase_contains(
+        full in "[a-cA-C ]{0,6}",
+        input in "[a-cA-C ]{0,3}",
+    ) {
+        let expected = full.to_lowercase().contains(&input.to_lowercase());
+        let matched = !matches!(
+            FilterResult::from_case_insensitive_substring(&full, &input),
+            FilterResult::None,
+        );
+        prop_assert_eq!(matched, expected);
+    }
+}
+
+#[test]
+fn arranges_rows_like_the_popup() {
+    let options = ["Africa", "America", "Antarctica", "Asia"];
+    let mut model =
+        ComboModel::<String, _>::new(options).pin_selected(true).remember_recent(2).min_chars(1);
+    assert_eq!(model.filtered(), [] as [usize; 0], "nothing is typed yet");
+
+    model.open(&String::from("Asia"));
+    model.input_text("am");
+    assert_eq!(model.filtered(), [3, 1], "the current value is pinned first");
+    assert_eq!(model.cursor(), Some(3));
+    model.key(Key::ArrowDown);
+    assert_eq!(model.key(Key::Enter).as_deref(), Some("America"));
+
+    let mut model = ComboModel::<String, _>::new(options).remember_recent(2);
+    model.input_text("tar");
+    model.key(Key::Enter);
+    model.input_text("");
+    assert_eq!(model.filtered(), [2, 0, 1, 3], "the recent value is listed first");
+}
+
+#[test]
+fn hidden_cursor_moves_from_its_displayed_row() {
+    let mut model = ComboModel::<String, _>::new(["x", "y", "ab", "ac"]);
+    model.open(&String::from("y"));
+    model.input_text("a");
+    assert_eq!(model.cursor(), Some(2), "displayed on the next option");
+    model.key(Key::ArrowDown);
+    assert_eq!(model.cursor(), Some(3));
+}
+
+#[test]
+fn hidden_cursor_skips_pinned_row() {
+    let options = ["Africa", "America", "Antarctica", "Asia"];
+    let mut model = ComboModel::<String, _>::new(options).pin_selected(true);
+    model.open(&String::from("Asia"));
+    model.key(Key::ArrowDown);
+    assert_eq!(model.cursor(), Some(0), "below the pinned Asia");
+    model.input_text("am");
+    assert_eq!(model.filtered(), [3, 1]);
+    assert_eq!(model.cursor(), Some(1), "Africa is followed by America, not the pinned Asia");
+}
@@ -0,0 +1,92 @@
+//! Checks that the defaults registered on the context apply to every combo box
+//! unless overridden on the builder.
+
+mod common;
+
+use common::{click, key, run_frame};
+use egui_editable_combobox::{ComboBoxDefaults, EditableComboBox, InteractionConfig};
+
+/// Focuses a combo box of three letters, presses Up and returns the cursor.
+fn cursor_after_up(
+    defaults: Option<InteractionConfig>,
+    interaction: Option<InteractionConfig>,
+) -> Option<usize> {
+    cursor_after_up_with(defaults, |combo_box| match interaction.clone() {
+        Some(interaction) => combo_box.interaction(interaction),
+        None => combo_box,
+    })
+}
+
+/// Like [`cursor_after_up`], with the builder settings applied by `configure`.
+fn cursor_after_up_with(
+    defaults: Option<InteractionConfig>,
+    configure: impl Fn(EditableComboBox<'static>) -> EditableComboBox<'static>,
+) -> Option<usize> {
+    let ctx = egui::Context::default();
+    if let Some(interaction) = defaults {
+        EditableComboBox::set_defaults(
+            &ctx,
+            ComboBoxDefaults { interaction, ..Default::default() },
+        );
+    }
+    let mut value = String::new();
+    let mut rect = egui::Rect::NOTHING;
+    let mut cursor = None;
+    let mut run = |events| {
+        run_frame(&ctx, events, |ui| {
+            let output = configure(EditableComboBox::new("letter")).show_with_output(
+                ui,
+                &mut value,
+                ["a", "b", "c"],
+            );
+            rect = output.response.rect;
+            cursor = output.cursor;
+        });
+        (rect, cursor)
+    };
+
+    run(vec![]);
+    let (rect, _) = run(vec![]);
+    run(click(rect.center()));
+    run(vec![]);
+    run(key(egui::Key::ArrowUp));
+    run(vec![]).1
+}
+
+fn no_wrap() -> InteractionConfig {
+    InteractionConfig { wrap_navigation: false, ..Default::default() }
+}
+
+#[test]
+fn defaults_apply_without_builder_settings() {
+    assert_eq!(cursor_after_up(None, None), Some(2), "Up wraps around by default");
+    assert_eq!(cursor_after_up(Some(no_wrap()), None), Some(0));
+}
+
+#[test]
+fn builder_overrides_defaults() {
+    assert_eq!(cursor_after_up(Some(no_wrap()), Some(InteractionConfig::default())), Some(2));
+}
+
+fn reversed() -> InteractionConfig {
+    InteractionConfig { reverse_order: true, ..Default::default() }
+}
+
+#[test]
+fn defaults_set_builder_flags() {
+    assert_eq!(
+        cursor_after_up_with(Some(reversed()), |combo_box| combo_box.ghost_text(false)),
+        Some(0),
+        "Up wraps to the last displayed row, which is the first option when reversed",
+    );
+}
+
+#[test]
+fn builder_flag_overrides_only_itself() {
+    let defaults = InteractionConfig { reverse_order: true, ..no_wrap() };
+    assert_eq!(
+        cursor_after_up_with(Some(defaults), |combo_box| combo_box.reverse_order(false)),
+        Some(0),
+        "the builder order applies while wrapping still follows the defaults",
+    );
+}
@@ -0,0 +1,50 @@
+//! Checks the `Value` and `ValueOption` implementations generated by `ComboValue`.
+#![cfg(feature = "derive")]
+
+use egui_editable_combobox::{ComboModel, ComboValue, Value};
+
+#[derive(Debug, PartialEq, ComboValue)]
+enum Continent {
+    Africa,
+    #[combo(label = "North America")]
+    NorthAmerica,
+    #[combo(label = "South America")]
+    SouthAmerica,
+}
+
+#[derive(Debug, ComboValue)]
+struct Port(u16);
+
+#[test]
+fn enum_labels() {
+    assert_eq!(Continent::NorthAmerica.to_editable(), "North America");
+    assert_eq!(Continent::try_from_editable("South America"), Some(Continent::SouthAmerica));
+    assert_eq!(Continent::try_from_editable("SouthAmerica"), None);
+    assert_eq!(
+        Continent::VARIANTS.map(|continent| continent.label()),
+        ["Africa", "North America", "South America"]
+    );
+}
+
+#[test]
+fn enum_options_filter_by_label() {
+    let mut model = ComboModel::new(&Continent::VARIANTS);
+    model.open(&Continent::SouthAmerica);
+    assert_eq!(model.cursor(), Some(2));
+
+    model.input_text("america");
+    assert_eq!(model.filtered(), [1, 2]);
+    // The cursor stays on the current value while it matches.
+    assert_eq!(model.commit(), Some(Continent::SouthAmerica));
+}
+
+#[test]
+fn newtype_parses_inner_value() {
+    assert_eq!(Port(8080).to_editable(), "8080");
+    assert_eq!(Port::try_from_editable("443").map(|port| port.0), Some(443));
+    assert!(Port::try_from_editable("http").is_none());
+
+    let mut model = ComboModel::new([Port(80), Port(443), Port(8080)]);
+    model.input_text("80");
+    assert_eq!(model.filtered(), [0, 2]);
+}
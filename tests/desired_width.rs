@@ -0,0 +1,78 @@
+//! Checks that `desired_width` and `width_full` size the whole widget,
+//! including the locked prefix and the dropdown arrow, and the popup below it.
+
+mod common;
+
+use common::click;
+use egui_editable_combobox::EditableComboBox;
+
+const SCREEN: egui::Rect = egui::Rect::from_min_max(egui::Pos2::ZERO, egui::pos2(1000.0, 600.0));
+
+#[derive(Clone, Copy)]
+struct Shown {
+    /// The rect of the whole widget.
+    widget: egui::Rect,
+    /// The rect of the editor alone.
+    editor: egui::Rect,
+    /// The rect available to the widget.
+    panel:  egui::Rect,
+    popup:  Option<egui::Rect>,
+}
+
+/// Shows a combo box configured by `build` and opens its popup.
+fn show(build: fn(EditableComboBox<'_>) -> EditableComboBox<'_>) -> Shown {
+    let ctx = egui::Context::default();
+    let mut value = String::from("team/Alice");
+    let mut run = |events| {
+        let mut shown = Shown {
+            widget: egui::Rect::NOTHING,
+            editor: egui::Rect::NOTHING,
+            panel:  egui::Rect::NOTHING,
+            popup:  None,
+        };
+        let _ = ctx.run(
+            egui::RawInput { events, screen_rect: Some(SCREEN), ..Default::default() },
+            |ctx| {
+                egui::CentralPanel::default().show(ctx, |ui| {
+                    shown.panel = ui.max_rect();
+                    let combo = build(EditableComboBox::new("sized"))
+                        .locked_prefix("team/")
+                        .dropdown_arrow(true);
+                    let popup_id = combo.popup_id();
+                    let response =
+                        ui.scope(|ui| combo.show(ui, &mut value, ["team/Alice", "team/Bob"]));
+                    shown.widget = response.response.rect;
+                    shown.editor = response.inner.rect;
+                    shown.popup = ctx.memory(|mem| mem.area_rect(popup_id));
+                });
+            },
+        );
+        shown
+    };
+    run(vec![]);
+    let editor = run(vec![]).editor;
+    run(click(editor.center()));
+    run(vec![]);
+    run(vec![])
+}
+
+#[test]
+fn desired_width_includes_prefix_and_arrow() {
+    let shown = show(|combo| combo.desired_width(300.0));
+    assert!((shown.widget.width() - 300.0).abs() < 1.0, "widget {:?}", shown.widget);
+    assert!(shown.editor.left() > shown.widget.left(), "the prefix is before the editor");
+
+    let popup = shown.popup.expect("popup open");
+    assert!(popup.width() >= shown.widget.right() - shown.editor.left());
+    assert!(popup.width() < 400.0, "popup {popup:?}");
+}
+
+#[test]
+fn width_full_fills_available_width() {
+    let shown = show(|combo| combo.width_full());
+    assert!((shown.widget.width() - shown.panel.width()).abs() < 1.0, "widget {:?}", shown.widget);
+
+    let popup = shown.popup.expect("popup open");
+    assert!(popup.width() >= shown.widget.right() - shown.editor.left(), "popup {popup:?}");
+    assert!(SCREEN.contains_rect(popup), "popup {popup:?}");
+}
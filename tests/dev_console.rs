@@ -0,0 +1,78 @@
+//! Drives the developer console example through its keyboard-only flows.
+
+mod common;
+
+use common::{key, run_frame, text};
+#[path = "../examples/dev_console.rs"]
+#[expect(dead_code)] // `main` only runs the example
+mod dev_console;
+
+use dev_console::Console;
+
+struct Session {
+    ctx:     egui::Context,
+    console: Console,
+}
+
+impl Session {
+    fn new() -> Self {
+        let mut session = Self { ctx: egui::Context::default(), console: Console::default() };
+        session.idle();
+        session
+    }
+
+    fn frame(&mut self, events: Vec<egui::Event>) {
+        run_frame(&self.ctx.clone(), events, |ui| {
+            self.console.ui(ui);
+        });
+    }
+
+    fn idle(&mut self) {
+        self.frame(vec![]);
+        self.frame(vec![]);
+    }
+
+    fn send(&mut self, events: Vec<egui::Event>) {
+        self.frame(events);
+        self.idle();
+    }
+}
+
+#[test]
+fn fuzzy_command_then_argument_executes() {
+    let mut session = Session::new();
+    session.send(text("tp"));
+    session.send(key(egui::Key::Enter));
+    assert_eq!(session.console.command, "teleport");
+
+    session.send(text("for"));
+    session.send(key(egui::Key::Enter));
+    assert_eq!(session.console.log, ["> teleport forest"]);
+    assert_eq!(session.console.argument, "");
+}
+
+#[test]
+fn free_text_argument_executes() {
+    let mut session = Session::new();
+    session.send(text("give"));
+    session.send(key(egui::Key::Enter));
+    session.send(text("100 gold"));
+    session.send(key(egui::Key::Enter));
+    assert_eq!(session.console.log, ["> give 100 gold"]);
+}
+
+#[test]
+fn up_recalls_previous_command() {
+    let mut session = Session::new();
+    session.send(text("spawn"));
+    session.send(key(egui::Key::Enter));
+    session.send(text("dragon"));
+    session.send(key(egui::Key::Enter));
+
+    // Focus is back on the command name, which recalls "spawn" from the history.
+    session.send(key(egui::Key::ArrowUp));
+    session.send(key(egui::Key::Enter));
+    session.send(text("gob"));
+    session.send(key(egui::Key::Enter));
+    assert_eq!(session.console.log, ["> spawn dragon", "> spawn goblin"]);
+}
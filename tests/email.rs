@@ -0,0 +1,88 @@
+//! Checks that the email preset suggests domains after the `@`,
+//! completes the address under the caret in a list of recipients,
+//! and rejects invalid addresses.
+
+mod common;
+
+use common::{click, enter, run_frame, text};
+use egui_editable_combobox::{EmailPicker, EmailValue};
+
+fn address(text: &str) -> EmailValue { text.parse().expect("valid address") }
+
+/// Shows a picker with `show` in each frame, returning the rect of the editor.
+struct Form<F> {
+    ctx:  egui::Context,
+    show: F,
+    rect: egui::Rect,
+}
+
+impl<F: FnMut(&mut egui::Ui) -> egui::Response> Form<F> {
+    fn new(show: F) -> Self {
+        Self { ctx: egui::Context::default(), show, rect: egui::Rect::NOTHING }
+    }
+
+    fn run(&mut self, events: Vec<egui::Event>) {
+        run_frame(&self.ctx.clone(), events, |ui| {
+            self.rect = (self.show)(ui).rect;
+        });
+    }
+
+    fn focus(&mut self) {
+        self.run(vec![]);
+        self.run(vec![]);
+        self.run(click(self.rect.center()));
+        self.run(vec![]);
+    }
+}
+
+#[test]
+fn parses_addresses() {
+    let ada = address("ada.lovelace+notes@mail.example.com");
+    assert_eq!(ada.local_part(), "ada.lovelace+notes");
+    assert_eq!(ada.domain(), "mail.example.com");
+    for invalid in ["ada", "@example.com", "ada lovelace@example.com", "ada@example", "ada@-x.com"]
+    {
+        assert!(invalid.parse::<EmailValue>().is_err(), "{invalid}");
+    }
+}
+
+#[test]
+fn suggests_domain_after_at() {
+    let mut from = None;
+    let mut form = Form::new(|ui| {
+        EmailPicker::new("from").domains(["example.com", "example.org"]).show(ui, &mut from)
+    });
+    form.focus();
+    form.run(text("ada@example.o"));
+    form.run(vec![]);
+    form.run(enter());
+    drop(form);
+    assert_eq!(from, Some(address("ada@example.org")));
+}
+
+#[test]
+fn invalid_address_is_not_committed() {
+    let mut from = None;
+    let mut form = Form::new(|ui| EmailPicker::new("from").show(ui, &mut from));
+    form.focus();
+    form.run(text("ada@localhost"));
+    form.run(enter());
+    form.run(vec![]);
+    drop(form);
+    assert_eq!(from, None);
+}
+
+#[test]
+fn completes_recipient_under_caret() {
+    let mut to = vec![address("ada@example.com")];
+    let mut form = Form::new(|ui| {
+        EmailPicker::new("to").domains(["example.com", "example.org"]).show_list(ui, &mut to)
+    });
+    form.focus();
+    form.run(text(", bob@example.o"));
+    form.run(vec![]);
+    form.run(enter());
+    form.run(vec![]);
+    drop(form);
+    assert_eq!(to, [address("ada@example.com"), address("bob@example.org")]);
+}
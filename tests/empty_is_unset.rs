@@ -0,0 +1,55 @@
+//! Checks that `empty_is_unset` shows the placeholder for an empty value
+//! and marks no option equal to it as selected.
+
+mod common;
+
+use common::{click, enter, painted_texts, run_frame};
+use egui_editable_combobox::{EditableComboBox, ShowOutput};
+
+/// Shows the combo box bound to `person`, returning its output and the texts painted.
+fn run(
+    ctx: &egui::Context,
+    unset: bool,
+    person: &mut String,
+    events: Vec<egui::Event>,
+) -> (ShowOutput, Vec<String>) {
+    let mut output = None;
+    let full_output = run_frame(ctx, events, |ui| {
+        let mut combo = EditableComboBox::new("person");
+        if unset {
+            combo = combo.empty_is_unset("Pick someone");
+        }
+        output = Some(combo.show_with_output(ui, person, ["Alice", ""]));
+    });
+    (output.expect("combo box shown"), painted_texts(full_output))
+}
+
+/// Focuses the editor of an empty value and presses Enter,
+/// returning the texts painted before focusing, the output of Enter and the value.
+fn enter_unfocused(unset: bool) -> (Vec<String>, ShowOutput, String) {
+    let ctx = egui::Context::default();
+    let mut person = String::new();
+    run(&ctx, unset, &mut person, vec![]);
+    let (output, texts) = run(&ctx, unset, &mut person, vec![]);
+    run(&ctx, unset, &mut person, click(output.response.rect.center()));
+    run(&ctx, unset, &mut person, vec![]);
+    let (output, _) = run(&ctx, unset, &mut person, enter());
+    (texts, output, person)
+}
+
+#[test]
+fn empty_value_is_selected_by_default() {
+    let (texts, output, person) = enter_unfocused(false);
+    assert!(!texts.iter().any(|text| text == "Pick someone"));
+    assert_eq!(output.change, None);
+    assert!(output.reselected.is_some(), "the empty option is marked as selected");
+    assert_eq!(person, "");
+}
+
+#[test]
+fn unset_value_shows_placeholder_and_selects_nothing() {
+    let (texts, output, person) = enter_unfocused(true);
+    assert!(texts.iter().any(|text| text == "Pick someone"), "{texts:?}");
+    assert!(output.change.is_some(), "the first option is committed as a new value");
+    assert_eq!(person, "Alice");
+}
@@ -0,0 +1,129 @@
+//! Checks that the option marked as the current value and the re-selection of it
+//! follow the `EqualityPolicy`, for options equal to the value ignoring case.
+
+mod common;
+
+use common::{click, enter, run_frame};
+use egui::{Color32, IntoAtoms};
+use egui_editable_combobox::{
+    EditableComboBox, EqualityPolicy, FilterResult, FilterState, PopupStyle, RowVisuals,
+    ShowOutput, ValueChange, ValueOption,
+};
+
+/// The fill marking the option equal to the current value.
+const SELECTED_FILL: Color32 = Color32::from_rgb(255, 0, 255);
+
+/// Whether `fill` is [`SELECTED_FILL`], which is faded while the rows fade in.
+fn is_selected_fill(fill: Color32) -> bool { fill.g() == 0 && fill.r() > 0 && fill.r() == fill.b() }
+
+/// An option equal to any value with the same letters ignoring case.
+struct Fruit(&'static str);
+
+impl ValueOption<String> for Fruit {
+    fn filter_by_text(&self, text: &str, _: FilterState) -> FilterResult {
+        FilterResult::from_case_insensitive_substring(self.0, text)
+    }
+
+    fn display(&self, _: &str) -> impl IntoAtoms<'_> { self.0 }
+
+    fn into_value(self, _: &str) -> String { self.0.to_owned() }
+
+    fn equals_value(&self, value: &String, _: &str) -> bool { self.0.eq_ignore_ascii_case(value) }
+}
+
+struct Frame {
+    output:   ShowOutput,
+    /// The texts painted in the frame with their centers.
+    texts:    Vec<(String, egui::Pos2)>,
+    /// The texts painted on the fill marking the current value.
+    selected: Vec<String>,
+}
+
+struct Fruits {
+    ctx:      egui::Context,
+    value:    String,
+    equality: EqualityPolicy,
+}
+
+impl Fruits {
+    fn new(value: &str, equality: EqualityPolicy) -> Self {
+        Self { ctx: egui::Context::default(), value: value.to_owned(), equality }
+    }
+
+    fn run(&mut self, events: Vec<egui::Event>) -> Frame {
+        let mut output = None;
+        let style = PopupStyle {
+            selected: Some(RowVisuals { fill: SELECTED_FILL, stroke: egui::Stroke::NONE }),
+            ..Default::default()
+        };
+        let full_output = run_frame(&self.ctx.clone(), events, |ui| {
+            output = Some(
+                EditableComboBox::new("fruit")
+                    .equality(self.equality)
+                    .popup_style(style.clone())
+                    .show_with_output(
+                        ui,
+                        &mut self.value,
+                        [Fruit("Apple"), Fruit("APPLE"), Fruit("Banana")],
+                    ),
+            );
+        });
+
+        let mut fills = Vec::new();
+        let mut texts = Vec::new();
+        for clipped in full_output.shapes {
+            match clipped.shape {
+                egui::Shape::Rect(rect) if is_selected_fill(rect.fill) => fills.push(rect.rect),
+                egui::Shape::Text(text) => {
+                    let center = text.pos + text.galley.rect.center().to_vec2();
+                    texts.push((text.galley.text().to_owned(), center));
+                }
+                _ => {}
+            }
+        }
+        let selected = texts
+            .iter()
+            .filter(|(_, center)| fills.iter().any(|fill| fill.contains(*center)))
+            .map(|(text, _)| text.clone())
+            .collect();
+        Frame { output: output.expect("combo box shown"), texts, selected }
+    }
+
+    /// Focuses the editor, which opens the popup.
+    fn open(&mut self) -> Frame {
+        self.run(vec![]);
+        let rect = self.run(vec![]).output.response.rect;
+        self.run(click(rect.center()));
+        self.run(vec![])
+    }
+}
+
+#[test]
+fn never_policy_marks_and_reselects_nothing() {
+    let mut fruits = Fruits::new("apple", EqualityPolicy::EveryOption);
+    assert_eq!(fruits.open().selected, ["Apple"]);
+    let output = fruits.run(enter()).output;
+    assert_eq!(output.reselected, Some(ValueChange::Entered));
+    assert_eq!(fruits.value, "apple", "re-selecting keeps the value");
+
+    let mut fruits = Fruits::new("apple", EqualityPolicy::Never);
+    assert!(fruits.open().selected.is_empty());
+    let output = fruits.run(enter()).output;
+    assert_eq!(output.change, Some(ValueChange::Entered));
+    assert_eq!(fruits.value, "Apple", "the first option is committed as a new value");
+}
+
+#[test]
+fn only_first_equal_option_is_marked() {
+    for equality in [EqualityPolicy::EveryOption, EqualityPolicy::FirstEqual] {
+        let mut fruits = Fruits::new("apple", equality);
+        let frame = fruits.open();
+        assert_eq!(frame.selected, ["Apple"], "{equality:?}");
+
+        let (_, second) = frame.texts.iter().find(|(text, _)| text == "APPLE").expect("row shown");
+        let output = fruits.run(click(*second)).output;
+        assert_eq!(output.change, Some(ValueChange::Clicked), "{equality:?}");
+        assert_eq!(output.reselected, None);
+        assert_eq!(fruits.value, "APPLE");
+    }
+}
@@ -0,0 +1,72 @@
+//! Checks that a filter budget spreads filtering over frames,
+//! showing the options matched so far with a "Still filtering…" row.
+
+mod common;
+
+use common::{click, painted_texts, run_frame, text};
+use egui_editable_combobox::EditableComboBox;
+
+const STILL_FILTERING: &str = "Still filtering…";
+
+/// Shows a combo box of many numbers, filtering three per frame.
+struct Numbers {
+    ctx:      egui::Context,
+    value:    String,
+    rect:     egui::Rect,
+    filtered: usize,
+}
+
+impl Numbers {
+    fn new() -> Self {
+        Self {
+            ctx:      egui::Context::default(),
+            value:    String::new(),
+            rect:     egui::Rect::NOTHING,
+            filtered: 0,
+        }
+    }
+
+    /// Runs a frame, returning whether the popup is still filtering.
+    fn run(&mut self, events: Vec<egui::Event>) -> bool {
+        let output = run_frame(&self.ctx.clone(), events, |ui| {
+            let output = EditableComboBox::new("number").filter_budget(3).show_with_output(
+                ui,
+                &mut self.value,
+                (0..10).map(|i| format!("n{i}")),
+            );
+            self.rect = output.response.rect;
+            self.filtered = output.filtered_count;
+        });
+        painted_texts(output).iter().any(|text| text == STILL_FILTERING)
+    }
+
+    fn focus(&mut self) {
+        self.run(vec![]);
+        self.run(vec![]);
+        self.run(click(self.rect.center()));
+    }
+}
+
+#[test]
+fn filters_within_budget_per_frame() {
+    let mut numbers = Numbers::new();
+    numbers.focus();
+    assert_eq!(numbers.filtered, 3);
+    assert!(numbers.run(vec![]));
+    assert_eq!(numbers.filtered, 6);
+    assert!(numbers.run(vec![]));
+    assert_eq!(numbers.filtered, 9);
+    assert!(!numbers.run(vec![]));
+    assert_eq!(numbers.filtered, 10);
+}
+
+#[test]
+fn edited_text_restarts_filtering() {
+    let mut numbers = Numbers::new();
+    numbers.focus();
+    while numbers.run(vec![]) {}
+
+    assert!(numbers.run(text("1")), "the new text is filtered from the first option");
+    while numbers.run(vec![]) {}
+    assert_eq!(numbers.filtered, 1);
+}
@@ -0,0 +1,61 @@
+//! Checks that the font size preset previews the size under the cursor
+//! and clamps typed sizes, even into an inverted range.
+
+mod common;
+
+use common::{click, enter, key, key_with, run_frame, text};
+use egui_editable_combobox::FontSizePicker;
+
+/// Runs a frame of a picker, returning its rect and the size of the painted preview.
+fn run(
+    ctx: &egui::Context,
+    size: &mut f32,
+    range: std::ops::RangeInclusive<f32>,
+    events: Vec<egui::Event>,
+) -> (egui::Rect, Option<f32>) {
+    let mut rect = egui::Rect::NOTHING;
+    let output = run_frame(ctx, events, |ui| {
+        rect = FontSizePicker::new("size").range(range.clone()).show(ui, size).rect;
+    });
+    let preview = output.shapes.iter().find_map(|clipped| match &clipped.shape {
+        egui::Shape::Text(shape) if shape.galley.text() == "Aa" => {
+            Some(shape.galley.job.sections[0].format.font_id.size)
+        }
+        _ => None,
+    });
+    (rect, preview)
+}
+
+fn select_all() -> Vec<egui::Event> { key_with(egui::Key::A, egui::Modifiers::COMMAND) }
+
+#[test]
+fn preview_follows_cursor() {
+    let ctx = egui::Context::default();
+    let mut size = 72.0;
+    let (rect, preview) = run(&ctx, &mut size, 1.0..=200.0, vec![]);
+    assert_eq!(preview, Some(72.0));
+
+    run(&ctx, &mut size, 1.0..=200.0, click(rect.center()));
+    run(&ctx, &mut size, 1.0..=200.0, select_all());
+    run(&ctx, &mut size, 1.0..=200.0, text("7"));
+    let (_, preview) = run(&ctx, &mut size, 1.0..=200.0, vec![]);
+    assert_eq!(preview, Some(72.0));
+    // The row using the typed size.
+    let (_, preview) = run(&ctx, &mut size, 1.0..=200.0, key(egui::Key::ArrowDown));
+    assert_eq!((size, preview), (72.0, Some(7.0)));
+    run(&ctx, &mut size, 1.0..=200.0, enter());
+    assert_eq!(size, 7.0);
+}
+
+#[test]
+fn inverted_range_clamps_typed_size() {
+    let ctx = egui::Context::default();
+    let mut size = 14.0;
+    let (rect, _) = run(&ctx, &mut size, 72.0..=8.0, vec![]);
+    run(&ctx, &mut size, 72.0..=8.0, click(rect.center()));
+    run(&ctx, &mut size, 72.0..=8.0, select_all());
+    run(&ctx, &mut size, 72.0..=8.0, text("500"));
+    run(&ctx, &mut size, 72.0..=8.0, vec![]);
+    run(&ctx, &mut size, 72.0..=8.0, enter());
+    assert_eq!(size, 72.0);
+}
@@ -0,0 +1,102 @@
+//! Checks that match statistics report when only fuzzy matching would find options,
+//! and that the fuzzy fallback lists them.
+
+mod common;
+
+use common::{click, enter, painted_texts, run_frame, text};
+use egui_editable_combobox::{EditableComboBox, MatchStats, ShowOutput};
+
+const OPTIONS: [&str; 3] = ["banana", "mango", "cherry"];
+
+struct Fruit {
+    ctx:            egui::Context,
+    value:          String,
+    fuzzy_fallback: bool,
+    filter_budget:  Option<usize>,
+    rect:           egui::Rect,
+}
+
+impl Fruit {
+    fn new(fuzzy_fallback: bool) -> Self {
+        Self {
+            ctx: egui::Context::default(),
+            value: String::new(),
+            fuzzy_fallback,
+            filter_budget: None,
+            rect: egui::Rect::NOTHING,
+        }
+    }
+
+    fn run_output(&mut self, events: Vec<egui::Event>) -> (ShowOutput, egui::FullOutput) {
+        let mut output = None;
+        let full_output = run_frame(&self.ctx.clone(), events, |ui| {
+            let mut combo_box = EditableComboBox::new("fruit").fuzzy_fallback(self.fuzzy_fallback);
+            if let Some(budget) = self.filter_budget {
+                combo_box = combo_box.filter_budget(budget);
+            }
+            output = Some(combo_box.show_with_output(ui, &mut self.value, OPTIONS));
+        });
+        let output = output.expect("combo box shown");
+        self.rect = output.response.rect;
+        (output, full_output)
+    }
+
+    fn run(&mut self, events: Vec<egui::Event>) -> ShowOutput { self.run_output(events).0 }
+
+    fn focus(&mut self) {
+        self.run(vec![]);
+        self.run(vec![]);
+        self.run(click(self.rect.center()));
+        self.run(vec![]);
+    }
+}
+
+#[test]
+fn substring_matches_skip_fuzzy_matching() {
+    let mut fruit = Fruit::new(true);
+    fruit.focus();
+    let output = fruit.run(text("an"));
+    assert_eq!(output.match_stats, MatchStats { matches: 2, ..MatchStats::default() });
+    assert!(!output.match_stats.fuzzy_would_match());
+}
+
+#[test]
+fn reports_fuzzy_matches_without_fallback() {
+    let mut fruit = Fruit::new(false);
+    fruit.focus();
+    let output = fruit.run(text("bna"));
+    assert_eq!(output.filtered_count, 0);
+    assert_eq!(
+        output.match_stats,
+        MatchStats { matches: 0, fuzzy_matches: 1, fuzzy_fallback: false }
+    );
+    assert!(output.match_stats.fuzzy_would_match());
+}
+
+#[test]
+fn fallback_lists_fuzzy_matches() {
+    let mut fruit = Fruit::new(true);
+    fruit.focus();
+    let output = fruit.run(text("bna"));
+    assert_eq!(output.filtered_count, 1);
+    assert!(output.match_stats.fuzzy_fallback);
+
+    fruit.run(vec![]);
+    let (_, full_output) = fruit.run_output(vec![]);
+    assert!(painted_texts(full_output).iter().any(|text| text == "Showing fuzzy matches"));
+
+    fruit.run(enter());
+    assert_eq!(fruit.value, "banana");
+}
+
+#[test]
+fn fallback_waits_for_budgeted_filtering() {
+    let mut fruit = Fruit::new(true);
+    fruit.filter_budget = Some(1);
+    fruit.focus();
+    let counts: Vec<_> = [text("bna"), vec![], vec![]]
+        .into_iter()
+        .map(|events| fruit.run(events).filtered_count)
+        .collect();
+    assert_eq!(counts, [0, 0, 1]);
+}
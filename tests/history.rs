@@ -0,0 +1,113 @@
+//! Checks that Up and Down step through the committed values like readline
+//! with `HistoryMode::Readline`, instead of moving the cursor in the popup.
+
+mod common;
+
+use common::{click, enter, key, run_frame, text};
+use egui_editable_combobox::{EditableComboBox, HistoryMode, InteractionConfig};
+
+/// Shows a font picker remembering the committed fonts.
+struct Fonts {
+    ctx:      egui::Context,
+    history:  HistoryMode,
+    value:    String,
+    rect:     egui::Rect,
+    filtered: usize,
+    cursor:   Option<usize>,
+}
+
+impl Fonts {
+    fn new(history: HistoryMode) -> Self {
+        Self {
+            ctx: egui::Context::default(),
+            history,
+            value: String::new(),
+            rect: egui::Rect::NOTHING,
+            filtered: 0,
+            cursor: None,
+        }
+    }
+
+    fn run(&mut self, events: Vec<egui::Event>) {
+        run_frame(&self.ctx.clone(), events, |ui| {
+            let output = EditableComboBox::new("font")
+                .interaction(InteractionConfig { history: self.history, ..Default::default() })
+                .show_with_output(ui, &mut self.value, ["Serif", "Sans", "Mono"]);
+            self.rect = output.response.rect;
+            self.filtered = output.filtered_count;
+            self.cursor = output.cursor;
+        });
+    }
+
+    fn focus(&mut self) {
+        self.run(vec![]);
+        self.run(vec![]);
+        self.run(click(self.rect.center()));
+        self.run(vec![]);
+    }
+
+    /// Focuses the editor and commits the option matching `font`.
+    fn pick(&mut self, font: &str) {
+        self.focus();
+        self.run(text(font));
+        self.run(vec![]);
+        self.run(enter());
+        self.run(vec![]);
+        assert_eq!(self.value, font);
+    }
+
+    fn press(&mut self, key_pressed: egui::Key) {
+        self.run(key(key_pressed));
+        self.run(vec![]);
+    }
+}
+
+#[test]
+fn up_and_down_step_through_history() {
+    let mut fonts = Fonts::new(HistoryMode::Readline);
+    fonts.pick("Sans");
+    fonts.pick("Mono");
+    fonts.focus();
+    assert_eq!(fonts.filtered, 3, "the text is empty");
+
+    fonts.press(egui::Key::ArrowUp);
+    fonts.press(egui::Key::ArrowUp);
+    assert_eq!(fonts.filtered, 1, "the text is the older value");
+    fonts.press(egui::Key::ArrowDown);
+    fonts.press(egui::Key::ArrowUp);
+    fonts.run(enter());
+    fonts.run(vec![]);
+    assert_eq!(fonts.value, "Sans");
+}
+
+#[test]
+fn stepping_past_newest_value_clears_text() {
+    let mut fonts = Fonts::new(HistoryMode::Readline);
+    fonts.pick("Sans");
+    fonts.focus();
+    fonts.press(egui::Key::ArrowUp);
+    assert_eq!(fonts.filtered, 1);
+    fonts.press(egui::Key::ArrowDown);
+    assert_eq!(fonts.filtered, 3);
+}
+
+#[test]
+fn edited_text_moves_cursor_instead() {
+    let mut fonts = Fonts::new(HistoryMode::Readline);
+    fonts.pick("Sans");
+    fonts.focus();
+    fonts.run(text("s"));
+    fonts.press(egui::Key::ArrowUp);
+    assert_eq!(fonts.filtered, 2, "the typed text is kept");
+    assert_eq!(fonts.cursor, Some(0), "the cursor moves up from the current value");
+}
+
+#[test]
+fn off_moves_cursor() {
+    let mut fonts = Fonts::new(HistoryMode::Off);
+    fonts.pick("Sans");
+    fonts.focus();
+    fonts.press(egui::Key::ArrowUp);
+    assert_eq!(fonts.filtered, 3);
+    assert_eq!(fonts.cursor, Some(0), "the cursor moves up from the current value");
+}
@@ -0,0 +1,51 @@
+//! Checks that clicking anywhere on the combo box focuses the editor and opens the popup.
+
+mod common;
+
+use common::{click, run_frame};
+use egui_editable_combobox::EditableComboBox;
+
+/// Runs a frame showing the combo box,
+/// and returns the rect of the editor and the ID of the popup.
+fn run(
+    ctx: &egui::Context,
+    prefix: &str,
+    value: &mut String,
+    events: Vec<egui::Event>,
+) -> (egui::Rect, egui::Id) {
+    let mut shown = None;
+    run_frame(ctx, events, |ui| {
+        let combo = EditableComboBox::new("hit testing").locked_prefix(prefix);
+        let popup_id = combo.popup_id();
+        let rect = combo.show(ui, value, ["pre/alpha", "pre/beta"]).rect;
+        shown = Some((rect, popup_id));
+    });
+    shown.expect("combo box shown")
+}
+
+/// Clicks the combo box at the position returned by `target` from the rect of the editor,
+/// and returns whether the popup is open afterwards.
+fn opens_when_clicked(prefix: &str, target: impl Fn(egui::Rect) -> egui::Pos2) -> bool {
+    let ctx = egui::Context::default();
+    let mut value = String::from("pre/alpha");
+    run(&ctx, prefix, &mut value, vec![]);
+    let (rect, _) = run(&ctx, prefix, &mut value, vec![]);
+    run(&ctx, prefix, &mut value, click(target(rect)));
+    run(&ctx, prefix, &mut value, vec![]);
+    let (_, popup_id) = run(&ctx, prefix, &mut value, vec![]);
+    egui::Popup::is_id_open(&ctx, popup_id)
+}
+
+#[test]
+fn click_on_editor_edges_opens_popup() {
+    let inset = egui::vec2(0.5, 0.5);
+    assert!(opens_when_clicked("", |rect| rect.left_top() + inset));
+    assert!(opens_when_clicked("", |rect| rect.right_bottom() - inset));
+    assert!(opens_when_clicked("", |rect| rect.left_center() + inset));
+}
+
+#[test]
+fn click_on_locked_prefix_opens_popup() {
+    // The prefix is displayed to the left of the editor.
+    assert!(opens_when_clicked("pre/", |rect| rect.left_center() - egui::vec2(10.0, 0.0)));
+}
@@ -0,0 +1,107 @@
+//! Checks that hovering a row only moves the cursor once the pointer rests on it,
+//! and that a resting pointer does not steal the cursor moved by the keyboard.
+
+mod common;
+
+use common::{click, key, run_frame_at};
+use egui_editable_combobox::EditableComboBox;
+
+/// Shows a combo box of three fruits, advancing the clock by a frame each run.
+struct Fruits {
+    ctx:    egui::Context,
+    value:  String,
+    time:   f64,
+    rect:   egui::Rect,
+    cursor: Option<usize>,
+}
+
+impl Fruits {
+    fn new() -> Self {
+        Self {
+            ctx:    egui::Context::default(),
+            value:  String::new(),
+            time:   0.0,
+            rect:   egui::Rect::NOTHING,
+            cursor: None,
+        }
+    }
+
+    fn run(&mut self, events: Vec<egui::Event>) {
+        self.time += 1.0 / 60.0;
+        run_frame_at(&self.ctx.clone(), self.time, events, |ui| {
+            let output = EditableComboBox::new("fruit").show_with_output(
+                ui,
+                &mut self.value,
+                ["apple", "banana", "cherry"],
+            );
+            self.rect = output.response.rect;
+            self.cursor = output.cursor;
+        });
+    }
+
+    /// Runs frames without input for `secs` seconds.
+    fn wait(&mut self, secs: f64) {
+        let until = self.time + secs;
+        while self.time < until {
+            self.run(vec![]);
+        }
+    }
+
+    fn focus(&mut self) {
+        self.run(vec![]);
+        self.run(vec![]);
+        self.run(click(self.rect.center()));
+        self.run(vec![]);
+    }
+
+    /// The position of the row at `index` in the popup.
+    fn row(&self, index: usize) -> egui::Pos2 {
+        let spacing = self.ctx.style().spacing.clone();
+        let step = spacing.interact_size.y + spacing.item_spacing.y;
+        #[expect(clippy::cast_precision_loss)] // small index
+        let offset = step * index as f32;
+        self.rect.center_bottom() + egui::vec2(0.0, 12.0 + offset)
+    }
+
+    fn hover(&mut self, index: usize) {
+        self.run(vec![egui::Event::PointerMoved(self.row(index))]);
+    }
+}
+
+#[test]
+fn cursor_moves_after_resting_on_row() {
+    let mut fruits = Fruits::new();
+    fruits.focus();
+    assert_eq!(fruits.cursor, Some(0));
+
+    fruits.hover(1);
+    assert_eq!(fruits.cursor, Some(0), "the pointer has not rested on the row yet");
+    fruits.wait(0.1);
+    assert_eq!(fruits.cursor, Some(1));
+}
+
+#[test]
+fn transient_hover_does_not_move_cursor() {
+    let mut fruits = Fruits::new();
+    fruits.focus();
+
+    // The stylus sweeps across the second row on its way to the third.
+    fruits.hover(1);
+    fruits.hover(2);
+    assert_eq!(fruits.cursor, Some(0));
+    fruits.wait(0.1);
+    assert_eq!(fruits.cursor, Some(2));
+}
+
+#[test]
+fn resting_pointer_keeps_keyboard_cursor() {
+    let mut fruits = Fruits::new();
+    fruits.focus();
+    fruits.hover(1);
+    fruits.wait(0.1);
+    assert_eq!(fruits.cursor, Some(1));
+
+    fruits.run(key(egui::Key::ArrowDown));
+    fruits.wait(0.1);
+    assert_eq!(fruits.cursor, Some(2), "the pointer did not move since the key press");
+}
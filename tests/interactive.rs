@@ -0,0 +1,93 @@
+//! Checks that a combo box that is not interactive displays its value
+//! without opening the popup or editing the text,
+//! and that disabling it while editing closes the popup.
+
+mod common;
+
+use common::{click, painted_texts, run_frame};
+use egui_editable_combobox::EditableComboBox;
+
+struct Frame {
+    has_focus:  bool,
+    popup_open: bool,
+    texts:      Vec<String>,
+}
+
+struct Form {
+    ctx:         egui::Context,
+    value:       String,
+    interactive: bool,
+    /// Whether the combo box is added in a disabled `Ui` instead.
+    enabled_ui:  bool,
+    rect:        egui::Rect,
+}
+
+impl Form {
+    fn new() -> Self {
+        Self {
+            ctx:         egui::Context::default(),
+            value:       String::from("Bob"),
+            interactive: true,
+            enabled_ui:  true,
+            rect:        egui::Rect::NOTHING,
+        }
+    }
+
+    fn run(&mut self, events: Vec<egui::Event>) -> Frame {
+        let mut frame = Frame { has_focus: false, popup_open: false, texts: vec![] };
+        let output = run_frame(&self.ctx.clone(), events, |ui| {
+            ui.add_enabled_ui(self.enabled_ui, |ui| {
+                let output = EditableComboBox::new("person")
+                    .interactive(self.interactive)
+                    .show_with_output(ui, &mut self.value, ["Alice", "Bob", "Carol"]);
+                self.rect = output.response.rect;
+                frame.has_focus = output.response.has_focus();
+                frame.popup_open = output.popup_open;
+            });
+        });
+        frame.texts = painted_texts(output);
+        frame
+    }
+
+    fn click_editor(&mut self) -> Frame {
+        self.run(vec![]);
+        self.run(vec![]);
+        self.run(click(self.rect.center()));
+        self.run(vec![])
+    }
+}
+
+#[test]
+fn click_does_not_open_popup() {
+    let mut form = Form::new();
+    form.interactive = false;
+    let frame = form.click_editor();
+    assert!(!frame.has_focus && !frame.popup_open);
+    assert!(frame.texts.contains(&String::from("Bob")));
+}
+
+#[test]
+fn disabled_ui_does_not_open_popup() {
+    let mut form = Form::new();
+    form.enabled_ui = false;
+    let frame = form.click_editor();
+    assert!(!frame.has_focus && !frame.popup_open);
+}
+
+#[test]
+fn disabling_while_editing_closes_popup() {
+    let mut form = Form::new();
+    assert!(form.click_editor().popup_open);
+    form.run(vec![egui::Event::Text(String::from("Car"))]);
+
+    form.enabled_ui = false;
+    form.run(vec![]);
+    let frame = form.run(vec![]);
+    assert!(!frame.has_focus && !frame.popup_open);
+    assert!(frame.texts.contains(&String::from("Bob")), "the value is displayed again");
+
+    form.enabled_ui = true;
+    let frame = form.run(vec![]);
+    assert!(!frame.has_focus && !frame.popup_open);
+    assert_eq!(form.value, "Bob");
+}
@@ -0,0 +1,36 @@
+//! Checks that `is_editing` reports whether the user is typing in a combo box,
+//! so that global shortcuts can be suppressed meanwhile.
+
+mod common;
+
+use common::{click, key, run_frame};
+use egui_editable_combobox::EditableComboBox;
+
+/// Runs a frame showing two combo boxes,
+/// returning the rect of the first one and whether each is being edited.
+fn run(ctx: &egui::Context, events: Vec<egui::Event>) -> (egui::Rect, [bool; 2]) {
+    let mut rect = egui::Rect::NOTHING;
+    run_frame(ctx, events, |ui| {
+        let mut fruit = String::from("apple");
+        rect = EditableComboBox::new("fruit").show(ui, &mut fruit, ["apple", "banana"]).rect;
+        let mut color = String::from("red");
+        EditableComboBox::new("color").show(ui, &mut color, ["red", "green"]);
+    });
+    let editing = ["fruit", "color"].map(|id_salt| EditableComboBox::is_editing(ctx, id_salt));
+    (rect, editing)
+}
+
+#[test]
+fn editing_while_focused() {
+    let ctx = egui::Context::default();
+    run(&ctx, vec![]);
+    let (rect, editing) = run(&ctx, vec![]);
+    assert_eq!(editing, [false, false]);
+
+    run(&ctx, click(rect.center()));
+    assert_eq!(run(&ctx, vec![]).1, [true, false]);
+
+    // Escape closes the popup and surrenders focus.
+    run(&ctx, key(egui::Key::Escape));
+    assert_eq!(run(&ctx, vec![]).1, [false, false]);
+}
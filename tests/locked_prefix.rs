@@ -0,0 +1,83 @@
+//! Checks that a locked prefix is displayed but cannot be deleted,
+//! and that options are filtered and committed with the prefix prepended.
+
+mod common;
+
+use common::{click, enter, key, painted_texts, run_frame, text};
+use egui_editable_combobox::EditableComboBox;
+
+/// Shows a picker of identifiers in the `team/` namespace.
+struct Picker {
+    ctx:      egui::Context,
+    value:    String,
+    rect:     egui::Rect,
+    filtered: usize,
+}
+
+impl Picker {
+    fn new() -> Self {
+        Self {
+            ctx:      egui::Context::default(),
+            value:    String::from("team/alpha"),
+            rect:     egui::Rect::NOTHING,
+            filtered: 0,
+        }
+    }
+
+    /// Runs a frame, returning the painted texts.
+    fn run(&mut self, events: Vec<egui::Event>) -> Vec<String> {
+        let output = run_frame(&self.ctx.clone(), events, |ui| {
+            let output = EditableComboBox::new("identifier")
+                .locked_prefix("team/")
+                .show_with_output(ui, &mut self.value, ["team/alpha", "team/beta", "team/gamma"]);
+            self.rect = output.response.rect;
+            self.filtered = output.filtered_count;
+        });
+        painted_texts(output)
+    }
+
+    fn focus(&mut self) {
+        self.run(vec![]);
+        self.run(vec![]);
+        self.run(click(self.rect.center()));
+        self.run(vec![]);
+    }
+}
+
+#[test]
+fn editor_only_holds_text_after_prefix() {
+    let mut picker = Picker::new();
+    let texts = picker.run(vec![]);
+    assert!(texts.contains(&String::from("team/")));
+    assert!(texts.contains(&String::from("alpha")));
+    assert!(!texts.contains(&String::from("team/alpha")));
+}
+
+#[test]
+fn backspace_stops_at_prefix() {
+    let mut picker = Picker::new();
+    picker.focus();
+    picker.run(text("b"));
+    for _ in 0..3 {
+        picker.run(key(egui::Key::Backspace));
+    }
+    let texts = picker.run(vec![]);
+    assert!(texts.contains(&String::from("team/")), "the prefix is not deleted");
+    assert_eq!(picker.filtered, 3);
+}
+
+#[test]
+fn filters_and_commits_with_prefix() {
+    let mut picker = Picker::new();
+    picker.focus();
+    // All options contain an "a", but only one contains "team/a".
+    picker.run(text("a"));
+    picker.run(vec![]);
+    assert_eq!(picker.filtered, 1);
+
+    picker.run(key(egui::Key::Backspace));
+    picker.run(text("b"));
+    picker.run(enter());
+    picker.run(vec![]);
+    assert_eq!(picker.value, "team/beta");
+}
@@ -0,0 +1,50 @@
+//! Checks that each match mode filters the options as documented.
+
+mod common;
+
+use common::{click, run_frame};
+use egui_editable_combobox::{EditableComboBox, FilterResult, MatchMode};
+
+const FUNCTIONS: [&str; 4] = ["print", "println", "sprintf", "Printer"];
+
+/// Runs a frame of a combo box with the given mode,
+/// returning its rect and the number of options displayed.
+fn run(ctx: &egui::Context, mode: MatchMode, events: Vec<egui::Event>) -> (egui::Rect, usize) {
+    let mut value = String::new();
+    let mut shown = (egui::Rect::NOTHING, 0);
+    run_frame(ctx, events, |ui| {
+        let output = EditableComboBox::new("function")
+            .match_mode(mode)
+            .show_with_output(ui, &mut value, FUNCTIONS);
+        shown = (output.response.rect, output.filtered_count);
+    });
+    shown
+}
+
+/// Types `text` into a combo box with the given mode,
+/// returning the number of options displayed.
+fn filtered_count(mode: MatchMode, text: &str) -> usize {
+    let ctx = egui::Context::default();
+    run(&ctx, mode, vec![]);
+    let (rect, _) = run(&ctx, mode, vec![]);
+    run(&ctx, mode, click(rect.center()));
+    run(&ctx, mode, vec![egui::Event::Text(text.into())]);
+    run(&ctx, mode, vec![]).1
+}
+
+#[test]
+fn modes_filter_options() {
+    assert_eq!(filtered_count(MatchMode::CaseInsensitiveSubstring, "print"), 4);
+    assert_eq!(filtered_count(MatchMode::CaseSensitiveSubstring, "print"), 3);
+    assert_eq!(filtered_count(MatchMode::PrefixOnly, "print"), 3);
+    assert_eq!(filtered_count(MatchMode::Exact, "PRINT"), 1);
+    assert_eq!(filtered_count(MatchMode::Exact, ""), 4);
+}
+
+#[test]
+fn filter_result_respects_mode() {
+    let filter = |full, input, mode| FilterResult::from_match_mode(full, input, mode);
+    assert!(matches!(filter("sprintf", "print", MatchMode::PrefixOnly), FilterResult::None));
+    assert!(matches!(filter("Printer", "print", MatchMode::PrefixOnly), FilterResult::Partial));
+    assert!(matches!(filter("print", "print", MatchMode::Exact), FilterResult::Exact));
+}
@@ -0,0 +1,86 @@
+//! Checks that `max_popup_height` limits the height of the popup,
+//! which scrolls to keep the cursor row in view.
+
+mod common;
+
+use common::{click, key, run_frame_sized};
+use egui_editable_combobox::EditableComboBox;
+
+const SCREEN: egui::Vec2 = egui::vec2(400.0, 800.0);
+
+struct Popup {
+    /// The rect of the popup area.
+    rect:  egui::Rect,
+    /// The texts painted in the frame with their centers.
+    texts: Vec<(String, egui::Pos2)>,
+}
+
+impl Popup {
+    fn center_of(&self, text: &str) -> Option<egui::Pos2> {
+        self.texts.iter().find(|(painted, _)| painted == text).map(|&(_, center)| center)
+    }
+}
+
+/// Opens a popup of 50 options limited to `max_height`,
+/// then runs a frame with each of `events` followed by an idle frame.
+fn open(max_height: f32, events: Vec<Vec<egui::Event>>) -> Popup {
+    let ctx = egui::Context::default();
+    let mut value = String::new();
+    let options: Vec<String> = (0..50).map(|i| format!("option {i}")).collect();
+    let mut run = |events| {
+        let mut rect = egui::Rect::NOTHING;
+        let output = run_frame_sized(&ctx, SCREEN, events, |ui| {
+            rect = EditableComboBox::new("long list")
+                .max_popup_height(max_height)
+                .show(ui, &mut value, options.iter().map(String::as_str))
+                .rect;
+        });
+        (rect, output)
+    };
+
+    run(vec![]);
+    let (rect, _) = run(vec![]);
+    run(click(rect.center()));
+    let mut output = run(vec![]).1;
+    for events in events {
+        run(events);
+        output = run(vec![]).1;
+    }
+
+    let texts = output
+        .shapes
+        .into_iter()
+        .filter_map(|clipped| match clipped.shape {
+            egui::Shape::Text(text) => Some((
+                text.galley.text().to_owned(),
+                text.pos + text.galley.rect.center().to_vec2(),
+            )),
+            _ => None,
+        })
+        .collect();
+    let popup_id = EditableComboBox::new("long list").popup_id();
+    let rect = ctx.memory(|mem| mem.area_rect(popup_id)).expect("popup open");
+    Popup { rect, texts }
+}
+
+#[test]
+fn popup_height_is_limited() {
+    let margin = egui::Frame::popup(&egui::Style::default()).total_margin().sum().y;
+    let short = open(100.0, vec![]).rect.height();
+    let tall = open(300.0, vec![]).rect.height();
+    assert!(short <= 100.0 + margin, "{short} exceeds the limit");
+    assert!(tall <= 300.0 + margin, "{tall} exceeds the limit");
+    assert!(tall > 250.0, "{tall} should fill the limit with 50 options");
+}
+
+#[test]
+fn cursor_row_scrolls_into_view() {
+    let popup = open(100.0, vec![]);
+    let first = popup.center_of("option 0").expect("first row shown");
+    assert!(popup.rect.contains(first));
+    assert_eq!(popup.center_of("option 49"), None, "the last row is scrolled out of view");
+
+    let popup = open(100.0, vec![key(egui::Key::End)]);
+    let last = popup.center_of("option 49").expect("last row shown");
+    assert!(popup.rect.contains(last), "{last:?} is outside {:?}", popup.rect);
+}
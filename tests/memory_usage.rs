@@ -0,0 +1,53 @@
+//! Checks that `memory_usage` reports the state a combo box keeps in egui memory.
+
+mod common;
+
+use common::{click, enter, run_frame};
+use egui_editable_combobox::{EditableComboBox, MemoryUsage};
+
+fn run(ctx: &egui::Context, value: &mut String, events: Vec<egui::Event>) -> egui::Rect {
+    let mut rect = egui::Rect::NOTHING;
+    run_frame(ctx, events, |ui| {
+        rect = EditableComboBox::new("budgeted")
+            .filter_budget(2)
+            .remember_recent(5)
+            .show(ui, value, ["apple", "apricot", "banana", "cherry", "grape"])
+            .rect;
+    });
+    rect
+}
+
+#[test]
+fn unknown_combo_box_uses_no_memory() {
+    let ctx = egui::Context::default();
+    assert_eq!(EditableComboBox::memory_usage(&ctx, "unknown"), MemoryUsage::default());
+}
+
+#[test]
+fn reports_cached_state() {
+    let ctx = egui::Context::default();
+    let mut value = String::new();
+    run(&ctx, &mut value, vec![]);
+    let rect = run(&ctx, &mut value, vec![]);
+    let idle = EditableComboBox::memory_usage(&ctx, "budgeted");
+    assert!(idle.entries > 0, "the text buffer is kept");
+
+    run(&ctx, &mut value, click(rect.center()));
+    run(&ctx, &mut value, vec![egui::Event::Text("ap".into())]);
+    run(&ctx, &mut value, vec![]);
+    let filtering = EditableComboBox::memory_usage(&ctx, "budgeted");
+    assert!(filtering.entries > idle.entries);
+    assert_eq!(filtering.cached_filter_results, 2, "apple and apricot match");
+    assert!(filtering.measured_rows > 0);
+
+    run(&ctx, &mut value, enter());
+    run(&ctx, &mut value, vec![]);
+    assert_eq!(value, "apple");
+    let committed = EditableComboBox::memory_usage(&ctx, "budgeted");
+    // The recent values are read from the selection history.
+    assert_eq!(committed.history_entries, 1);
+    assert_eq!(committed.cached_filter_results, 0, "the cache is dropped when the popup closes");
+
+    let total: MemoryUsage = [committed, idle].into_iter().sum();
+    assert_eq!(total.entries, committed.entries + idle.entries);
+}
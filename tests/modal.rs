@@ -0,0 +1,105 @@
+//! Checks that the combo box works inside an `egui::Modal`:
+//! the popup is interactive above the dimmed background,
+//! Escape closes the popup before the modal,
+//! and focus stays in the editor when the popup closes.
+
+mod common;
+
+use common::{click, key};
+use egui_editable_combobox::EditableComboBox;
+
+struct Frame {
+    /// The rect of the editor, or `None` if the modal has been closed.
+    rect:       Option<egui::Rect>,
+    has_focus:  bool,
+    popup_open: bool,
+}
+
+struct Dialog {
+    ctx:   egui::Context,
+    value: String,
+    open:  bool,
+}
+
+impl Dialog {
+    fn new() -> Self {
+        Self { ctx: egui::Context::default(), value: String::from("Bob"), open: true }
+    }
+
+    fn run(&mut self, events: Vec<egui::Event>) -> Frame {
+        let mut frame = Frame { rect: None, has_focus: false, popup_open: false };
+        let _ = self.ctx.clone().run(egui::RawInput { events, ..Default::default() }, |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| ui.label("background"));
+            if !self.open {
+                return;
+            }
+            let modal = egui::Modal::new(egui::Id::new("modal")).show(ctx, |ui| {
+                let output = EditableComboBox::new("modal combo").show_with_output(
+                    ui,
+                    &mut self.value,
+                    ["Alice", "Bob", "Carol"],
+                );
+                frame.rect = Some(output.response.rect);
+                frame.has_focus = output.response.has_focus();
+                frame.popup_open = output.popup_open;
+            });
+            if modal.should_close() {
+                self.open = false;
+            }
+        });
+        frame
+    }
+
+    /// Shows the dialog for a few frames and clicks the editor to open the popup,
+    /// returning the rect of the editor.
+    fn open_popup(&mut self) -> egui::Rect {
+        self.run(vec![]);
+        let rect = self.run(vec![]).rect.expect("modal open");
+        self.run(click(rect.center()));
+        let frame = self.run(vec![]);
+        assert!(frame.has_focus && frame.popup_open);
+        rect
+    }
+}
+
+#[test]
+fn popup_option_is_clickable() {
+    let mut dialog = Dialog::new();
+    let rect = dialog.open_popup();
+    // The first option is displayed right below the editor.
+    dialog.run(click(rect.center_bottom() + egui::vec2(0.0, 12.0)));
+    let frame = dialog.run(vec![]);
+    assert_eq!(dialog.value, "Alice");
+    assert!(dialog.open);
+    assert!(frame.has_focus, "focus returns to the editor after a selection");
+    assert!(!frame.popup_open);
+}
+
+#[test]
+fn escape_closes_popup_before_modal() {
+    let mut dialog = Dialog::new();
+    dialog.open_popup();
+
+    dialog.run(key(egui::Key::Escape));
+    let frame = dialog.run(vec![]);
+    assert!(dialog.open, "the first Escape only closes the popup");
+    assert!(frame.has_focus, "focus stays in the editor");
+    assert!(!frame.popup_open);
+
+    dialog.run(key(egui::Key::Escape));
+    dialog.run(vec![]);
+    assert!(!dialog.open, "the second Escape closes the modal");
+}
+
+#[test]
+fn down_reopens_dismissed_popup() {
+    let mut dialog = Dialog::new();
+    dialog.open_popup();
+    dialog.run(key(egui::Key::Escape));
+    dialog.run(vec![]);
+
+    dialog.run(key(egui::Key::ArrowDown));
+    let frame = dialog.run(vec![]);
+    assert!(frame.has_focus && frame.popup_open);
+    assert_eq!(dialog.value, "Bob");
+}
@@ -0,0 +1,134 @@
+//! Checks that the headless [`ComboModel`] agrees with the popup of the combo box,
+//! which reimplements the same filtering and cursor rules on the rendered rows.
+
+mod common;
+
+use common::{click, key, run_frame, text};
+use egui::Key;
+use egui_editable_combobox::{
+    ComboModel, EditableComboBox, MatchMode, Separated, ShowOutput, with_separators,
+};
+use proptest::prelude::*;
+
+#[derive(Debug, Clone, Default)]
+struct Case {
+    options:       Vec<String>,
+    /// Whether the options are grouped by their initial letter with separators.
+    grouped:       bool,
+    value:         String,
+    text:          String,
+    keys:          Vec<Key>,
+    reverse_order: bool,
+    pin_selected:  bool,
+    prefix_only:   bool,
+}
+
+/// The number of options matching the text, the cursor and the value after pressing Enter.
+type Outcome = (usize, Option<usize>, String);
+
+impl Case {
+    fn options(&self) -> impl Iterator<Item = Separated<&str>> {
+        let grouped = self.grouped;
+        with_separators(self.options.iter().map(String::as_str), move |prev, next| {
+            grouped && prev.get(..1) != next.get(..1)
+        })
+    }
+
+    fn model(&self) -> Outcome {
+        let mut model = ComboModel::<String, _>::new(self.options())
+            .reverse_order(self.reverse_order)
+            .pin_selected(self.pin_selected);
+        if self.prefix_only {
+            model = model.match_mode(MatchMode::PrefixOnly);
+        }
+        model.open(&self.value);
+        model.input_text(&self.text);
+        for &key in &self.keys {
+            model.key(key);
+        }
+        let (count, cursor) = (model.filtered().len(), model.cursor());
+        (count, cursor, model.key(Key::Enter).unwrap_or_else(|| self.value.clone()))
+    }
+
+    fn combo_box(&self) -> Outcome {
+        let ctx = egui::Context::default();
+        let mut value = self.value.clone();
+        let mut run = |events| {
+            let mut output = None;
+            run_frame(&ctx, events, |ui| {
+                let mut combo = EditableComboBox::new("parity")
+                    .reverse_order(self.reverse_order)
+                    .pin_selected(self.pin_selected);
+                if self.prefix_only {
+                    combo = combo.match_mode(MatchMode::PrefixOnly);
+                }
+                output = Some(combo.show_with_output(ui, &mut value, self.options()));
+            });
+            output.expect("combo box shown")
+        };
+        let step = |run: &mut dyn FnMut(Vec<egui::Event>) -> ShowOutput, events| {
+            run(events);
+            run(vec![])
+        };
+
+        run(vec![]);
+        let rect = run(vec![]).response.rect;
+        let mut output = step(&mut run, click(rect.center()));
+        if !self.text.is_empty() {
+            output = step(&mut run, text(&self.text));
+        }
+        for &motion in &self.keys {
+            output = step(&mut run, key(motion));
+        }
+        step(&mut run, key(Key::Enter));
+        (output.filtered_count, output.cursor, value)
+    }
+
+    fn assert_parity(&self) {
+        assert_eq!(self.model(), self.combo_box(), "{self:?}");
+    }
+}
+
+fn strings(strs: &[&str]) -> Vec<String> { strs.iter().copied().map(String::from).collect() }
+
+#[test]
+fn model_agrees_with_popup() {
+    let continents = strings(&["Africa", "America", "Antarctica", "Asia", "Europe"]);
+    let cases = [
+        Case { text: "an".into(), ..Case::default() },
+        Case { keys: vec![Key::ArrowDown, Key::ArrowDown], ..Case::default() },
+        Case { value: "Asia".into(), keys: vec![Key::ArrowDown], ..Case::default() },
+        Case { keys: vec![Key::End, Key::ArrowUp], reverse_order: true, ..Case::default() },
+        Case { value: "Asia".into(), text: "am".into(), pin_selected: true, ..Case::default() },
+        Case { text: "a".into(), prefix_only: true, ..Case::default() },
+        Case { grouped: true, keys: vec![Key::ArrowUp, Key::Home], ..Case::default() },
+        Case { text: "z".into(), ..Case::default() },
+    ];
+    for case in cases {
+        Case { options: continents.clone(), ..case }.assert_parity();
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn model_agrees_with_popup_on_any_input(
+        options in prop::collection::vec("[a-c]{1,3}", 0..8),
+        grouped: bool,
+        value in "[a-c]{0,3}",
+        text in "[a-c]{0,2}",
+        keys in prop::collection::vec(
+            prop::sample::select(vec![Key::ArrowUp, Key::ArrowDown, Key::Home, Key::End]),
+            0..6,
+        ),
+        reverse_order: bool,
+        pin_selected: bool,
+        prefix_only: bool,
+    ) {
+        let case = Case {
+            options, grouped, value, text, keys, reverse_order, pin_selected, prefix_only,
+        };
+        prop_assert_eq!(case.model(), case.combo_box(), "{:?}", case);
+    }
+}
@@ -0,0 +1,87 @@
+//! Checks that the values of `show_multi` are copied as delimited text,
+//! and that pasted lists are split and matched against the options.
+
+mod common;
+
+use common::{click, run_frame};
+use egui_editable_combobox::EditableComboBox;
+
+struct Tags {
+    ctx:       egui::Context,
+    tags:      Vec<String>,
+    free_text: bool,
+    rect:      egui::Rect,
+}
+
+impl Tags {
+    fn new(tags: &[&str], free_text: bool) -> Self {
+        Self {
+            ctx: egui::Context::default(),
+            tags: tags.iter().map(|&tag| String::from(tag)).collect(),
+            free_text,
+            rect: egui::Rect::NOTHING,
+        }
+    }
+
+    fn run(&mut self, events: Vec<egui::Event>) -> egui::FullOutput {
+        run_frame(&self.ctx.clone(), events, |ui| {
+            let response = EditableComboBox::new("tags")
+                .allow_free_text(self.free_text)
+                .show_multi(ui, &mut self.tags, ["urgent", "later", "blocked"]);
+            self.rect = response.rect;
+        })
+    }
+
+    fn focus(&mut self) {
+        self.run(vec![]);
+        self.run(vec![]);
+        self.run(click(self.rect.center()));
+        self.run(vec![]);
+    }
+
+    /// Whether `text` is painted in an idle frame.
+    fn shows(&mut self, text: &str) -> bool {
+        self.run(vec![]).shapes.into_iter().any(|clipped| {
+            matches!(clipped.shape, egui::Shape::Text(shape) if shape.galley.text() == text)
+        })
+    }
+}
+
+#[test]
+fn copy_from_empty_editor_copies_values() {
+    let mut tags = Tags::new(&["urgent", "later"], false);
+    tags.focus();
+    let output = tags.run(vec![egui::Event::Copy]);
+    assert!(
+        output
+            .platform_output
+            .commands
+            .contains(&egui::OutputCommand::CopyText(String::from("urgent, later")))
+    );
+}
+
+#[test]
+fn paste_adds_matching_items() {
+    let mut tags = Tags::new(&["urgent"], false);
+    tags.focus();
+    tags.run(vec![egui::Event::Paste(String::from("Later, urgent,blocked\nunknown"))]);
+    assert_eq!(tags.tags, ["urgent", "later", "blocked"]);
+    assert!(tags.shows("unknown"), "unmatched items are left in the editor");
+}
+
+#[test]
+fn paste_adds_free_text_items() {
+    let mut tags = Tags::new(&[], true);
+    tags.focus();
+    tags.run(vec![egui::Event::Paste(String::from("blocked, someday"))]);
+    assert_eq!(tags.tags, ["blocked", "someday"]);
+}
+
+#[test]
+fn paste_without_separator_edits_text() {
+    let mut tags = Tags::new(&[], false);
+    tags.focus();
+    tags.run(vec![egui::Event::Paste(String::from("later"))]);
+    assert!(tags.tags.is_empty());
+    assert!(tags.shows("later"));
+}
@@ -0,0 +1,71 @@
+//! Checks that picking a value with `show_multi` reports the picked option to the hooks
+//! and the selection history, and that matches are highlighted, as `show` does.
+
+mod common;
+
+use std::cell::{Cell, RefCell};
+
+use common::{click, enter, run_frame, text};
+use egui_editable_combobox::EditableComboBox;
+
+#[test]
+fn pick_reports_option() {
+    let ctx = egui::Context::default();
+    let mut tags = vec![String::from("urgent")];
+    let selected = RefCell::new(Vec::new());
+    let rect = Cell::new(egui::Rect::NOTHING);
+    let run = |tags: &mut Vec<String>, events| {
+        run_frame(&ctx, events, |ui| {
+            let response = EditableComboBox::new("tags")
+                .on_select(|index| selected.borrow_mut().push(index))
+                .announce_commit(|tag| format!("Added {tag}"))
+                .show_multi(ui, tags, ["urgent", "later", "blocked"]);
+            rect.set(response.rect);
+        })
+    };
+
+    run(&mut tags, vec![]);
+    run(&mut tags, vec![]);
+    run(&mut tags, click(rect.get().center()));
+    run(&mut tags, text("blo"));
+    run(&mut tags, vec![]);
+    let output = run(&mut tags, enter());
+
+    assert_eq!(tags, ["urgent", "blocked"]);
+    // The index in the options passed in, although the picked `urgent` is hidden.
+    assert_eq!(*selected.borrow(), [2]);
+    let history = EditableComboBox::selection_history(&ctx, "tags");
+    let record = history.last().expect("pick recorded");
+    assert_eq!((record.text.as_str(), record.source_index), ("blocked", Some(2)));
+    let announced = output.platform_output.events.iter().any(|event| {
+        matches!(event, egui::output::OutputEvent::ValueChanged(info)
+            if info.label.as_deref() == Some("Added blocked"))
+    });
+    assert!(announced);
+}
+
+#[test]
+fn matches_are_highlighted() {
+    let ctx = egui::Context::default();
+    let mut tags = Vec::new();
+    let rect = Cell::new(egui::Rect::NOTHING);
+    let mut run = |events| {
+        run_frame(&ctx, events, |ui| {
+            rect.set(EditableComboBox::new("tags").show_multi(ui, &mut tags, ["later"]).rect);
+        })
+    };
+
+    run(vec![]);
+    run(vec![]);
+    run(click(rect.get().center()));
+    run(text("ate"));
+    let output = run(vec![]);
+    let sections = output.shapes.iter().find_map(|clipped| match &clipped.shape {
+        egui::Shape::Text(shape) if shape.galley.text() == "later" => {
+            Some(shape.galley.job.sections.len())
+        }
+        _ => None,
+    });
+    // The text before, inside and after the match.
+    assert_eq!(sections, Some(3));
+}
@@ -0,0 +1,54 @@
+//! Checks that numeric options suggest the preset numbers
+//! and accept any typed number clamped into the range.
+
+use egui_editable_combobox::{
+    ComboModel, FilterResult, FilterState, NumericOption, NumericOptions, NumericValue, ValueOption,
+};
+
+#[test]
+fn suggests_matching_presets() {
+    let mut ports = ComboModel::new(NumericOptions::new([22_u16, 80, 443, 8080]));
+    ports.input_text("80");
+    // 80, 8080 and the typed number, which is hidden by the exact match.
+    assert_eq!(ports.filtered(), [1, 3]);
+    assert_eq!(ports.commit(), Some(NumericValue(80)));
+}
+
+#[test]
+fn typed_number_is_clamped() {
+    let mut ports = ComboModel::new(NumericOptions::new([22_u16, 80]).clamp(1024..=49151));
+    ports.input_text(" 3000 ");
+    assert_eq!(ports.commit(), Some(NumericValue(3000)));
+    ports.input_text("99");
+    assert_eq!(ports.commit(), Some(NumericValue(1024)));
+    ports.input_text("http");
+    assert_eq!(ports.commit(), None);
+}
+
+#[test]
+fn stepped_range() {
+    let options: Vec<_> = NumericOptions::stepped(0.0..=1.0, 0.25).into_iter().collect();
+    // 0, 0.25, 0.5, 0.75, 1 and the typed number.
+    assert_eq!(options.len(), 6);
+
+    let mut zoom = ComboModel::new(NumericOptions::stepped(0.0_f32..=1.0, 0.25));
+    zoom.input_text("0.7");
+    assert_eq!(zoom.commit(), Some(NumericValue(0.75)));
+    zoom.input_text("NaN");
+    assert_eq!(zoom.commit(), None);
+}
+
+fn typed_option(options: NumericOptions<u16>) -> NumericOption<u16> {
+    options.into_iter().last().expect("typed option")
+}
+
+#[test]
+fn typed_option_carries_parsed_number() {
+    let typed = typed_option(NumericOptions::new([22]).clamp(1024..=49151));
+    let state = FilterState { prev_matches: 0, had_exact: false };
+    assert!(matches!(typed.filter_by_text("3000", state), FilterResult::Partial));
+    assert_eq!(typed.into_value(""), NumericValue(3000), "the filtered number is kept");
+
+    let typed = typed_option(NumericOptions::new([22]).clamp(1024..=49151));
+    assert_eq!(typed.into_value("http"), NumericValue(1024), "never a number, zero clamped");
+}
@@ -0,0 +1,59 @@
+//! Checks that `Option` values are selected and cleared through the `None` row
+//! added by `with_none`.
+
+mod common;
+
+use common::{click, run_frame};
+use egui::Key;
+use egui_editable_combobox::{ComboModel, EditableComboBox, ValueChange, with_none};
+
+const NAMES: [&str; 3] = ["Alice", "Bob", "Carol"];
+
+#[test]
+fn none_row_is_selectable() {
+    let mut model = ComboModel::<Option<String>, _>::new(with_none("(nobody)", NAMES));
+
+    model.open(&None);
+    assert_eq!(model.cursor(), Some(0));
+    model.open(&Some(String::from("Bob")));
+    assert_eq!(model.cursor(), Some(2));
+
+    model.key(Key::Home);
+    assert_eq!(model.key(Key::Enter), Some(None));
+    model.key(Key::ArrowDown);
+    assert_eq!(model.key(Key::Enter), Some(Some(String::from("Alice"))));
+}
+
+#[test]
+fn none_row_matches_empty_text_and_its_label() {
+    let mut model = ComboModel::<Option<String>, _>::new(with_none("(nobody)", NAMES));
+    assert_eq!(model.filtered(), [0, 1, 2, 3]);
+    model.input_text("nob");
+    assert_eq!(model.filtered(), [0]);
+    model.input_text("ali");
+    assert_eq!(model.filtered(), [1]);
+}
+
+#[test]
+fn clear_button_resets_to_none() {
+    let ctx = egui::Context::default();
+    let mut value = Some(String::from("Bob"));
+    let mut run = |events: Vec<egui::Event>| {
+        let mut output = None;
+        run_frame(&ctx, events, |ui| {
+            output = Some(EditableComboBox::new("optional").clear_button("").show_with_output(
+                ui,
+                &mut value,
+                with_none("(nobody)", NAMES),
+            ));
+        });
+        output.expect("combo box shown")
+    };
+    run(vec![]);
+    let rect = run(vec![]).response.rect;
+
+    // The clear button is at the trailing edge of the editor.
+    let output = run(click(rect.right_center() - egui::vec2(8.0, 0.0)));
+    assert_eq!(output.change, Some(ValueChange::Cleared));
+    assert_eq!(value, None);
+}
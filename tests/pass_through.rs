@@ -0,0 +1,62 @@
+//! Checks that pass-through shortcuts are ignored by a focused combo box
+//! and still reach the app.
+
+mod common;
+
+use common::{click, key, run_frame};
+use egui::{Key, KeyboardShortcut, Modifiers};
+use egui_editable_combobox::{EditableComboBox, InteractionConfig, Shortcuts};
+
+struct Frame {
+    rect:        egui::Rect,
+    cursor:      Option<usize>,
+    /// Whether the app saw Down pressed after showing the combo box.
+    app_pressed: bool,
+}
+
+fn run(ctx: &egui::Context, pass_through: &[Key], events: Vec<egui::Event>) -> Frame {
+    let mut frame =
+        Frame { rect: egui::Rect::NOTHING, cursor: None, app_pressed: false };
+    run_frame(ctx, events, |ui| {
+        let shortcuts = Shortcuts {
+            pass_through: pass_through
+                .iter()
+                .map(|key| KeyboardShortcut::new(Modifiers::NONE, *key))
+                .collect(),
+            ..Default::default()
+        };
+        let mut value = String::from("Bob");
+        let output = EditableComboBox::new("pass through")
+            .interaction(InteractionConfig { shortcuts, ..Default::default() })
+            .show_with_output(ui, &mut value, ["Alice", "Bob", "Carol"]);
+        frame.rect = output.response.rect;
+        frame.cursor = output.cursor;
+        frame.app_pressed = ui.input(|input| input.key_pressed(Key::ArrowDown));
+    });
+    frame
+}
+
+/// Opens the popup and presses Down,
+/// returning whether the app saw Down pressed and the cursor afterwards.
+fn press_down(pass_through: &[Key]) -> (bool, Option<usize>) {
+    let ctx = egui::Context::default();
+    run(&ctx, pass_through, vec![]);
+    let rect = run(&ctx, pass_through, vec![]).rect;
+    run(&ctx, pass_through, click(rect.center()));
+    assert_eq!(run(&ctx, pass_through, vec![]).cursor, Some(1));
+    let app_pressed = run(&ctx, pass_through, key(Key::ArrowDown)).app_pressed;
+    (app_pressed, run(&ctx, pass_through, vec![]).cursor)
+}
+
+#[test]
+fn pass_through_key_reaches_app() {
+    let (app_pressed, cursor) = press_down(&[Key::ArrowDown]);
+    assert!(app_pressed);
+    assert_eq!(cursor, Some(1), "the cursor is not moved");
+}
+
+#[test]
+fn other_keys_move_cursor() {
+    let (_, cursor) = press_down(&[Key::F5]);
+    assert_eq!(cursor, Some(2));
+}
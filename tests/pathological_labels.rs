@@ -0,0 +1,73 @@
+//! Checks that options containing newlines and other control characters
+//! are filtered as if they were spaces and displayed on a single line.
+
+mod common;
+
+use common::run_frame;
+use egui_editable_combobox::{ComboModel, EditableComboBox, FuzzyOption};
+
+#[test]
+fn newlines_match_spaces() {
+    let mut model = ComboModel::<String, _>::new(["first\nsecond", "tab\tseparated", "plain"]);
+    model.input_text("t s");
+    assert_eq!(model.filtered(), [0]);
+    model.input_text("b s");
+    assert_eq!(model.filtered(), [1]);
+    model.input_text("\n");
+    assert_eq!(model.filtered(), [] as [usize; 0]);
+}
+
+#[test]
+fn fuzzy_matches_control_characters_as_spaces() {
+    let mut model =
+        ComboModel::<String, _>::new(["first\nsecond", "plain\r\ntext"].map(FuzzyOption));
+    model.input_text("t s");
+    assert_eq!(model.filtered(), [0]);
+    model.input_text("n  t");
+    assert_eq!(model.filtered(), [1]);
+}
+
+/// Opens the popup with the given options and returns its size.
+fn popup_size(options: [&str; 2], flatten_labels: bool) -> egui::Vec2 {
+    let ctx = egui::Context::default();
+    let mut value = String::new();
+    let mut run = |events| {
+        let mut rect = egui::Rect::NOTHING;
+        run_frame(&ctx, events, |ui| {
+            rect = EditableComboBox::new("labels")
+                .flatten_labels(flatten_labels)
+                .show(ui, &mut value, options)
+                .rect;
+        });
+        rect
+    };
+
+    let rect = run(vec![]);
+    run(vec![egui::Event::PointerButton {
+        pos:       rect.center(),
+        button:    egui::PointerButton::Primary,
+        pressed:   true,
+        modifiers: egui::Modifiers::NONE,
+    }]);
+    run(vec![egui::Event::PointerButton {
+        pos:       rect.center(),
+        button:    egui::PointerButton::Primary,
+        pressed:   false,
+        modifiers: egui::Modifiers::NONE,
+    }]);
+    run(vec![]);
+    run(vec![]);
+
+    let popup_id = EditableComboBox::new("labels").popup_id();
+    ctx.memory(|mem| mem.area_rect(popup_id)).expect("popup open").size()
+}
+
+#[test]
+fn flattened_labels_have_single_line_height() {
+    let plain = popup_size(["first second", "third"], true);
+    let flattened = popup_size(["first\nsecond", "third"], true);
+    assert_eq!(plain.y, flattened.y);
+
+    let multiline = popup_size(["first\nsecond", "third"], false);
+    assert!(multiline.y > plain.y, "{multiline:?} should be taller than {plain:?}");
+}
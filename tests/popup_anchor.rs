@@ -0,0 +1,99 @@
+//! Checks that the popup is positioned at the editor on screen,
+//! even if the editor is clipped or in a transformed layer,
+//! and on the side requested by the placement.
+
+mod common;
+
+use common::click;
+use egui_editable_combobox::{EditableComboBox, PopupPlacement};
+
+/// Opens the popup of a combo box shown at `pos` in an area with `transform`,
+/// and returns the rect of the editor on screen and the rect of the popup.
+fn open_popup(
+    transform: egui::emath::TSTransform,
+    pos: egui::Pos2,
+    placement: PopupPlacement,
+) -> (egui::Rect, egui::Rect) {
+    let ctx = egui::Context::default();
+    let layer_id = egui::LayerId::new(egui::Order::Middle, egui::Id::new("transformed"));
+    ctx.set_transform_layer(layer_id, transform);
+
+    let mut value = String::new();
+    let mut editor_rect = egui::Rect::NOTHING;
+    let mut popup_id = None;
+    let mut run = |events| {
+        let _ = ctx.run(egui::RawInput { events, ..Default::default() }, |ctx| {
+            egui::Area::new(layer_id.id).fixed_pos(pos).show(ctx, |ui| {
+                // A scroll area clipping the editor,
+                // which must not clip the popup.
+                egui::ScrollArea::vertical().max_height(30.0).show(ui, |ui| {
+                    let combo = EditableComboBox::new("anchor").popup_placement(placement);
+                    popup_id = Some(combo.popup_id());
+                    editor_rect = transform * combo.show(ui, &mut value, ["Alpha", "Beta"]).rect;
+                });
+            });
+        });
+        editor_rect
+    };
+
+    run(vec![]);
+    let rect = run(vec![]);
+    run(click(rect.center()));
+    run(vec![]);
+    let editor_rect = run(vec![]);
+
+    let popup_id = popup_id.expect("combo box shown");
+    let popup_rect = ctx.memory(|mem| mem.area_rect(popup_id)).expect("popup open");
+    (editor_rect, popup_rect)
+}
+
+fn assert_below(editor_rect: egui::Rect, popup_rect: egui::Rect) {
+    assert!(
+        (popup_rect.left() - editor_rect.left()).abs() <= 1.0
+            && (popup_rect.top() - editor_rect.bottom()).abs() <= 8.0,
+        "popup at {popup_rect:?} should be below the editor at {editor_rect:?}",
+    );
+    assert!(popup_rect.height() > 30.0, "popup clipped to {popup_rect:?}");
+}
+
+#[test]
+fn popup_is_below_editor() {
+    let (editor_rect, popup_rect) = open_popup(
+        egui::emath::TSTransform::IDENTITY,
+        egui::pos2(10.0, 10.0),
+        PopupPlacement::Auto,
+    );
+    assert_below(editor_rect, popup_rect);
+}
+
+#[test]
+fn popup_is_below_transformed_editor() {
+    let transform = egui::emath::TSTransform::new(egui::vec2(100.0, 50.0), 1.5);
+    let (editor_rect, popup_rect) =
+        open_popup(transform, egui::pos2(10.0, 10.0), PopupPlacement::Auto);
+    assert_below(editor_rect, popup_rect);
+}
+
+#[test]
+fn popup_is_above_editor_when_requested() {
+    let (editor_rect, popup_rect) = open_popup(
+        egui::emath::TSTransform::IDENTITY,
+        egui::pos2(10.0, 300.0),
+        PopupPlacement::AboveOrBelow,
+    );
+    assert!(
+        (popup_rect.left() - editor_rect.left()).abs() <= 1.0
+            && (popup_rect.bottom() - editor_rect.top()).abs() <= 8.0,
+        "popup at {popup_rect:?} should be above the editor at {editor_rect:?}",
+    );
+}
+
+#[test]
+fn popup_falls_back_below_editor_without_room_above() {
+    let (editor_rect, popup_rect) = open_popup(
+        egui::emath::TSTransform::IDENTITY,
+        egui::pos2(10.0, 10.0),
+        PopupPlacement::AboveOrBelow,
+    );
+    assert_below(editor_rect, popup_rect);
+}
@@ -0,0 +1,42 @@
+//! Checks that options wider than the popup are wrapped onto multiple lines
+//! with `PopupOverflow::Wrap`, and the popup grows to fit them.
+
+mod common;
+
+use common::{click, run_frame};
+use egui_editable_combobox::{EditableComboBox, PopupOverflow};
+
+const LONG: &str = "an option far too long to fit into the narrow popup on a single line";
+
+/// Opens the popup and returns its size.
+fn popup_size(popup_overflow: PopupOverflow) -> egui::Vec2 {
+    let ctx = egui::Context::default();
+    let mut value = String::new();
+    let mut run = |events| {
+        let mut rect = egui::Rect::NOTHING;
+        run_frame(&ctx, events, |ui| {
+            rect = EditableComboBox::new("overflow")
+                .max_popup_width(120.0)
+                .popup_overflow(popup_overflow)
+                .show(ui, &mut value, [LONG, "short"])
+                .rect;
+        });
+        rect
+    };
+
+    let rect = run(vec![]);
+    run(click(rect.center()));
+    run(vec![]);
+    run(vec![]);
+
+    let popup_id = EditableComboBox::new("overflow").popup_id();
+    ctx.memory(|mem| mem.area_rect(popup_id)).expect("popup open").size()
+}
+
+#[test]
+fn wrapped_rows_grow_taller() {
+    let truncated = popup_size(PopupOverflow::Truncate);
+    let wrapped = popup_size(PopupOverflow::Wrap);
+    assert!(wrapped.y > truncated.y, "{wrapped:?} should be taller than {truncated:?}");
+    assert!(wrapped.x <= truncated.x, "{wrapped:?} should be as narrow as {truncated:?}");
+}
@@ -0,0 +1,73 @@
+//! Checks that `show_with_provider` polls the provider with the typed text
+//! and commits the options it provides once they are ready.
+
+mod common;
+
+use core::task::Poll;
+
+use common::{click, enter, painted_texts, run_frame, text};
+use egui_editable_combobox::{EditableComboBox, OptionProvider};
+
+/// Provides names starting with the polled text once `ready` is set.
+#[derive(Default)]
+struct Names {
+    ready:  bool,
+    /// The texts polled, without repeating the previous one.
+    polled: Vec<String>,
+}
+
+impl OptionProvider<String> for Names {
+    type Opt = &'static str;
+
+    fn poll_options<'a>(
+        &'a mut self,
+        text: &str,
+    ) -> Poll<impl IntoIterator<Item = &'static str> + use<'a>> {
+        if self.polled.last().is_none_or(|last| last != text) {
+            self.polled.push(text.to_owned());
+        }
+        if !self.ready {
+            return Poll::Pending;
+        }
+        let prefix = text.to_owned();
+        Poll::Ready(["Alice", "Bob"].into_iter().filter(move |name| name.starts_with(&prefix)))
+    }
+}
+
+/// Shows the combo box bound to `name`, returning the rect of the editor and the texts painted.
+fn run(
+    ctx: &egui::Context,
+    name: &mut String,
+    names: &mut Names,
+    events: Vec<egui::Event>,
+) -> (egui::Rect, Vec<String>) {
+    let mut rect = egui::Rect::NOTHING;
+    let output = run_frame(ctx, events, |ui| {
+        rect = EditableComboBox::new("name").show_with_provider(ui, name, names).rect;
+    });
+    (rect, painted_texts(output))
+}
+
+#[test]
+fn provided_options_are_committed_once_ready() {
+    let ctx = egui::Context::default();
+    let mut name = String::new();
+    let mut names = Names::default();
+    run(&ctx, &mut name, &mut names, vec![]);
+    let (rect, _) = run(&ctx, &mut name, &mut names, vec![]);
+    assert!(names.polled.is_empty(), "the provider is only polled while the popup is displayed");
+
+    run(&ctx, &mut name, &mut names, click(rect.center()));
+    run(&ctx, &mut name, &mut names, text("B"));
+    let (_, texts) = run(&ctx, &mut name, &mut names, vec![]);
+    assert!(texts.iter().any(|text| text == "Loading…"), "{texts:?}");
+
+    names.ready = true;
+    run(&ctx, &mut name, &mut names, text("o"));
+    let (_, texts) = run(&ctx, &mut name, &mut names, vec![]);
+    assert!(texts.iter().any(|text| text == "Bob"), "{texts:?}");
+    assert!(!texts.iter().any(|text| text == "Alice"), "{texts:?}");
+    run(&ctx, &mut name, &mut names, enter());
+    assert_eq!(name, "Bob");
+    assert_eq!(names.polled, ["", "B", "Bo"]);
+}
@@ -0,0 +1,36 @@
+//! Checks that `show_readonly` returns the picked value for the frame it was picked
+//! without mutating the current value.
+
+mod common;
+
+use common::{click, enter, run_frame, text};
+use egui_editable_combobox::EditableComboBox;
+
+/// Shows the combo box displaying `fruit`, returning the rect of the editor and the value picked.
+fn run(
+    ctx: &egui::Context,
+    fruit: &String,
+    events: Vec<egui::Event>,
+) -> (egui::Rect, Option<String>) {
+    let mut shown = (egui::Rect::NOTHING, None);
+    run_frame(ctx, events, |ui| {
+        let response =
+            EditableComboBox::new("fruit").show_readonly(ui, fruit, ["apple", "banana", "cherry"]);
+        shown = (response.response.rect, response.inner);
+    });
+    shown
+}
+
+#[test]
+fn picked_value_is_returned_once() {
+    let ctx = egui::Context::default();
+    let fruit = String::from("apple");
+    run(&ctx, &fruit, vec![]);
+    let (rect, _) = run(&ctx, &fruit, vec![]);
+    run(&ctx, &fruit, click(rect.center()));
+    run(&ctx, &fruit, text("ban"));
+    assert_eq!(run(&ctx, &fruit, vec![]).1, None, "typing picks nothing");
+    assert_eq!(run(&ctx, &fruit, enter()).1.as_deref(), Some("banana"));
+    assert_eq!(run(&ctx, &fruit, vec![]).1, None, "the pick is only returned in its frame");
+    assert_eq!(fruit, "apple", "the current value is only read");
+}
@@ -0,0 +1,96 @@
+//! Checks that replaying a recorded interaction with a combo box
+//! reproduces its transitions and value, wherever the combo box is laid out.
+
+mod common;
+
+use common::{click, enter, key, run_frame, text};
+use egui_editable_combobox::{EditableComboBox, Recording, Transition};
+
+const OPTIONS: [&str; 3] = ["Alpha", "Beta", "Gamma"];
+
+/// Shows `labels` labels above the combo box.
+fn form(
+    ui: &mut egui::Ui,
+    labels: usize,
+    value: &mut String,
+    recording: &mut Recording,
+) -> egui::Rect {
+    for i in 0..labels {
+        ui.label(format!("Field {i}"));
+    }
+    EditableComboBox::new("greek").record(recording).show(ui, value, OPTIONS).rect
+}
+
+/// Records focusing the combo box, typing and committing with Enter,
+/// then completing with Tab before committing.
+fn record() -> (Recording, String) {
+    let ctx = egui::Context::default();
+    let mut recording = Recording::default();
+    let mut value = String::new();
+    let mut rect = egui::Rect::NOTHING;
+    let mut run = |events| {
+        run_frame(&ctx, events, |ui| rect = form(ui, 1, &mut value, &mut recording));
+        rect
+    };
+
+    run(vec![]);
+    let rect = run(vec![]);
+    run(click(rect.center()));
+    run(vec![]);
+    run(text("Be"));
+    run(vec![]);
+    run(enter());
+    run(vec![]);
+    run(click(rect.center()));
+    run(vec![]);
+    run(text("Gam"));
+    run(vec![]);
+    run(key(egui::Key::Tab));
+    run(vec![]);
+    run(enter());
+    run(vec![]);
+    (recording, value)
+}
+
+#[test]
+fn records_focus_edits_and_commits() {
+    let (recording, value) = record();
+    assert_eq!(value, "Gamma");
+
+    let transitions: Vec<_> = recording.transitions().cloned().collect();
+    assert_eq!(
+        transitions,
+        [
+            Transition::GainedFocus,
+            Transition::TextEdited(String::from("Be")),
+            Transition::LostFocus,
+            Transition::Selected { source_index: 1 },
+            Transition::GainedFocus,
+            // Completing with Tab is not an edit by the user.
+            Transition::TextEdited(String::from("Gam")),
+            Transition::LostFocus,
+            Transition::Selected { source_index: 2 },
+        ]
+    );
+}
+
+#[test]
+fn replay_reproduces_transitions_and_value() {
+    let (recording, recorded_value) = record();
+
+    // The combo box is further down than when recorded.
+    let ctx = egui::Context::default();
+    let mut replayed = Recording::default();
+    let mut value = String::new();
+    recording.replay(&ctx, |ui| {
+        form(ui, 3, &mut value, &mut replayed);
+    });
+
+    assert_eq!(value, recorded_value);
+    assert!(
+        replayed.transitions().eq(recording.transitions()),
+        "{:?} != {:?}",
+        replayed.transitions().collect::<Vec<_>>(),
+        recording.transitions().collect::<Vec<_>>(),
+    );
+}
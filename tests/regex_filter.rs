@@ -0,0 +1,63 @@
+//! Checks that `regex_filter` interprets the typed text as a regex,
+//! falling back to a literal substring while the pattern is incomplete.
+#![cfg(feature = "regex")]
+
+mod common;
+
+use common::{click, run_frame};
+use egui_editable_combobox::EditableComboBox;
+
+const SYMBOLS: &[&str] = &["get_user_id", "set_user_id", "get_name", "parse(input)"];
+
+struct Symbols {
+    ctx:   egui::Context,
+    value: String,
+    rect:  egui::Rect,
+}
+
+impl Symbols {
+    fn new() -> Self {
+        Self { ctx: egui::Context::default(), value: String::new(), rect: egui::Rect::NOTHING }
+    }
+
+    /// Runs a frame, returning the number of displayed options.
+    fn run(&mut self, events: Vec<egui::Event>) -> usize {
+        let mut count = 0;
+        let _ = run_frame(&self.ctx.clone(), events, |ui| {
+            let output = EditableComboBox::new("symbols").regex_filter(true).show_with_output(
+                ui,
+                &mut self.value,
+                SYMBOLS.iter().copied(),
+            );
+            self.rect = output.response.rect;
+            count = output.filtered_count;
+        });
+        count
+    }
+
+    /// Focuses the editor and types `text`, returning the number of displayed options.
+    fn type_text(&mut self, text: &str) -> usize {
+        self.run(vec![]);
+        self.run(vec![]);
+        self.run(click(self.rect.center()));
+        self.run(vec![egui::Event::Text(text.into())]);
+        self.run(vec![])
+    }
+}
+
+#[test]
+fn pattern_filters_options() {
+    let mut symbols = Symbols::new();
+    assert_eq!(symbols.type_text("^GET_.*id$"), 1);
+
+    // The first displayed option is right below the editor.
+    symbols.run(click(symbols.rect.center_bottom() + egui::vec2(0.0, 12.0)));
+    symbols.run(vec![]);
+    assert_eq!(symbols.value, "get_user_id");
+}
+
+#[test]
+fn invalid_pattern_matches_literally() {
+    let mut symbols = Symbols::new();
+    assert_eq!(symbols.type_text("parse("), 1);
+}
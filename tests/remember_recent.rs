@@ -0,0 +1,117 @@
+//! Checks that recently committed values are listed in a "Recent" section
+//! at the top of the popup while the filter text is empty.
+
+mod common;
+
+use common::{click, key, painted_texts, run_frame};
+use egui_editable_combobox::EditableComboBox;
+
+const PEOPLE: [&str; 4] = ["Alice", "Bob", "Carol", "Dave"];
+
+struct Frame {
+    /// The texts painted in the frame, in paint order.
+    texts:  Vec<String>,
+    /// The option under the keyboard cursor.
+    cursor: Option<&'static str>,
+}
+
+struct People {
+    ctx:   egui::Context,
+    value: String,
+    rect:  egui::Rect,
+}
+
+impl People {
+    fn new() -> Self {
+        let mut people = Self {
+            ctx:   egui::Context::default(),
+            value: String::new(),
+            rect:  egui::Rect::NOTHING,
+        };
+        people.run(vec![]);
+        people.run(vec![]);
+        people
+    }
+
+    fn run(&mut self, events: Vec<egui::Event>) -> Frame {
+        let mut cursor = None;
+        let output = run_frame(&self.ctx.clone(), events, |ui| {
+            let output = EditableComboBox::new("people").remember_recent(2).show_with_output(
+                ui,
+                &mut self.value,
+                PEOPLE,
+            );
+            self.rect = output.response.rect;
+            cursor = output.cursor.map(|index| PEOPLE[index]);
+        });
+        let texts = painted_texts(output);
+        Frame { texts, cursor }
+    }
+
+    /// Focuses the editor, which clears the text and opens the popup.
+    fn open(&mut self) -> Frame {
+        self.run(click(egui::pos2(500.0, 500.0)));
+        self.run(vec![]);
+        self.run(click(self.rect.center()));
+        self.run(vec![])
+    }
+
+    fn select(&mut self, name: &str) {
+        self.open();
+        self.run(vec![egui::Event::Text(name.into())]);
+        self.run(vec![]);
+        self.run(key(egui::Key::Enter));
+        self.run(vec![]);
+        assert_eq!(self.value, name);
+    }
+
+    /// The displayed options in order, by moving the cursor from the top.
+    fn displayed(&mut self) -> Vec<&'static str> {
+        self.run(key(egui::Key::Home));
+        (0..PEOPLE.len())
+            .map(|_| {
+                let cursor = self.run(vec![]).cursor.expect("options displayed");
+                self.run(key(egui::Key::ArrowDown));
+                cursor
+            })
+            .collect()
+    }
+}
+
+#[test]
+fn recent_values_are_listed_first() {
+    let mut people = People::new();
+    assert!(!people.open().texts.contains(&String::from("Recent")));
+
+    for name in ["Dave", "Carol", "Bob"] {
+        people.select(name);
+    }
+    assert!(people.open().texts.contains(&String::from("Recent")));
+    // Only the last two values are remembered, most recent first.
+    assert_eq!(people.displayed(), ["Bob", "Carol", "Alice", "Dave"]);
+}
+
+#[test]
+fn typed_text_hides_recent_section() {
+    let mut people = People::new();
+    people.select("Carol");
+    people.open();
+    people.run(vec![egui::Event::Text("a".into())]);
+    let frame = people.run(vec![]);
+    assert!(!frame.texts.contains(&String::from("Recent")), "{:?}", frame.texts);
+}
+
+#[test]
+fn recent_values_follow_selection_history() {
+    let mut people = People::new();
+    for name in ["Dave", "Carol", "Dave"] {
+        people.select(name);
+    }
+    let history = EditableComboBox::selection_history(&people.ctx, "people");
+    let texts: Vec<&str> = history.iter().map(|record| record.text.as_str()).collect();
+    assert_eq!(texts, ["Dave", "Carol", "Dave"]);
+
+    people.open();
+    // A value committed twice is listed once.
+    assert_eq!(people.displayed(), ["Dave", "Carol", "Alice", "Bob"]);
+}
@@ -0,0 +1,99 @@
+//! Checks renaming an option inline in the popup with F2,
+//! committing the new label with Enter or cancelling with Escape.
+
+mod common;
+
+use std::cell::RefCell;
+
+use common::{click, enter, key, key_with, painted_texts, run_frame, text};
+use egui_editable_combobox::EditableComboBox;
+
+/// Shows a combo box of presets which can be renamed.
+struct Presets {
+    ctx:        egui::Context,
+    presets:    Vec<String>,
+    value:      String,
+    renames:    RefCell<Vec<(usize, String)>>,
+    rect:       egui::Rect,
+    editor_id:  egui::Id,
+    popup_open: bool,
+}
+
+impl Presets {
+    fn new() -> Self {
+        Self {
+            ctx:        egui::Context::default(),
+            presets:    ["Light", "Dark", "Contrast"].map(String::from).to_vec(),
+            value:      String::from("Light"),
+            renames:    RefCell::default(),
+            rect:       egui::Rect::NOTHING,
+            editor_id:  egui::Id::NULL,
+            popup_open: false,
+        }
+    }
+
+    /// Runs a frame, applying the renames to the presets and returning the painted texts.
+    fn run(&mut self, events: Vec<egui::Event>) -> Vec<String> {
+        let renames = &self.renames;
+        let output = run_frame(&self.ctx.clone(), events, |ui| {
+            let output = EditableComboBox::new("preset")
+                .on_rename(|index, label| renames.borrow_mut().push((index, label)))
+                .show_with_output(ui, &mut self.value, self.presets.iter().cloned());
+            self.rect = output.response.rect;
+            self.editor_id = output.response.id;
+            self.popup_open = output.popup_open;
+        });
+        for (index, label) in self.renames.borrow().iter() {
+            self.presets[*index].clone_from(label);
+        }
+        painted_texts(output)
+    }
+
+    /// Focuses the editor and starts renaming the second preset.
+    fn rename_dark(&mut self) {
+        self.run(vec![]);
+        self.run(vec![]);
+        self.run(click(self.rect.center()));
+        self.run(vec![]);
+        self.run(key(egui::Key::ArrowDown));
+        self.run(key(egui::Key::F2));
+        self.run(vec![]);
+        self.run(vec![]);
+    }
+
+    fn main_editor_focused(&self) -> bool { self.ctx.memory(|mem| mem.has_focus(self.editor_id)) }
+}
+
+#[test]
+fn f2_renames_cursor_row() {
+    let mut presets = Presets::new();
+    presets.rename_dark();
+    assert!(!presets.main_editor_focused(), "the rename editor has focus");
+
+    presets.run(key_with(egui::Key::A, egui::Modifiers::COMMAND));
+    presets.run(text("Night"));
+    presets.run(enter());
+    let texts = presets.run(vec![]);
+
+    assert_eq!(*presets.renames.borrow(), [(1, String::from("Night"))]);
+    assert!(texts.contains(&String::from("Night")));
+    assert_eq!(presets.value, "Light", "renaming does not select the option");
+    assert!(presets.popup_open);
+    assert!(presets.main_editor_focused());
+}
+
+#[test]
+fn escape_cancels_renaming() {
+    let mut presets = Presets::new();
+    presets.rename_dark();
+
+    presets.run(text(" mode"));
+    presets.run(key(egui::Key::Escape));
+    let texts = presets.run(vec![]);
+
+    assert!(presets.renames.borrow().is_empty());
+    assert!(texts.contains(&String::from("Dark")));
+    assert!(!texts.contains(&String::from("Dark mode")));
+    assert!(presets.popup_open, "only renaming is cancelled");
+    assert!(presets.main_editor_focused());
+}
@@ -0,0 +1,113 @@
+//! Checks that the combo box only discards passes and requests repaints
+//! while something on screen is actually changing.
+
+mod common;
+
+use common::{click, key, run_frame, text};
+use egui_editable_combobox::EditableComboBox;
+
+/// Shows a combo box with the given options, tracking the passes and repaints of each frame.
+struct Frames {
+    ctx:           egui::Context,
+    value:         String,
+    options:       Vec<String>,
+    filter_budget: Option<usize>,
+    rect:          egui::Rect,
+}
+
+/// The passes of a frame, and whether the combo box requested a repaint after it.
+#[derive(Debug, PartialEq)]
+struct Cost {
+    passes:  u64,
+    repaint: bool,
+}
+
+impl Frames {
+    fn new(value: &str, options: impl IntoIterator<Item = String>) -> Self {
+        Self {
+            ctx:           egui::Context::default(),
+            value:         value.into(),
+            options:       options.into_iter().collect(),
+            filter_budget: None,
+            rect:          egui::Rect::NOTHING,
+        }
+    }
+
+    fn run(&mut self, events: Vec<egui::Event>) -> Cost {
+        let before = self.ctx.cumulative_pass_nr();
+        run_frame(&self.ctx.clone(), events, |ui| {
+            let mut combo_box = EditableComboBox::new("repaint");
+            if let Some(budget) = self.filter_budget {
+                combo_box = combo_box.filter_budget(budget);
+            }
+            self.rect = combo_box.show(ui, &mut self.value, self.options.iter().cloned()).rect;
+        });
+        // Text edits and areas of egui also repaint while their animations run.
+        let repaint = self.ctx.repaint_causes().iter().any(|cause| cause.file.starts_with("src/"));
+        Cost { passes: self.ctx.cumulative_pass_nr() - before, repaint }
+    }
+
+    /// Settles the layout, then clicks the editor, returning the cost of the click.
+    fn focus(&mut self) -> Cost {
+        self.run(vec![]);
+        self.run(vec![]);
+        self.run(click(self.rect.center()))
+    }
+}
+
+fn names(count: usize) -> impl Iterator<Item = String> { (0..count).map(|i| format!("name {i}")) }
+
+#[test]
+fn focusing_empty_text_is_not_discarded() {
+    let mut frames = Frames::new("", names(3));
+    assert_eq!(frames.focus().passes, 1);
+
+    let mut frames = Frames::new("name 1", names(3));
+    assert_eq!(frames.focus().passes, 2, "the text is cleared on focus");
+}
+
+#[test]
+fn budgeted_filter_repaints_until_done() {
+    let mut frames = Frames::new("", names(3));
+    frames.filter_budget = Some(2);
+    frames.ctx.style_mut(|style| style.animation_time = 0.0);
+    frames.focus();
+    assert!(frames.run(vec![]).repaint, "the last option is still pending");
+    assert!(!frames.run(vec![]).repaint);
+}
+
+#[test]
+fn budgeted_filter_stops_repainting_when_closed() {
+    let mut frames = Frames::new("", names(100));
+    frames.filter_budget = Some(1);
+    frames.ctx.style_mut(|style| style.animation_time = 0.0);
+    frames.focus();
+    assert!(frames.run(text("name")).repaint);
+    // Clicking elsewhere closes the popup before the options are filtered.
+    frames.run(click(frames.rect.center() + egui::vec2(300.0, 300.0)));
+    assert!(!frames.run(vec![]).repaint);
+    assert_eq!(frames.run(vec![]), Cost { passes: 1, repaint: false });
+}
+
+#[test]
+fn rows_fading_out_of_view_are_not_animated() {
+    // The last option is far below the visible rows of the popup.
+    let mut frames = Frames::new("", names(50).chain([String::from("last")]));
+    frames.focus();
+    frames.run(vec![]);
+    frames.run(text("n"));
+    frames.run(vec![]);
+    frames.run(key(egui::Key::Backspace));
+    assert!(!frames.run(vec![]).repaint);
+}
+
+#[test]
+fn rows_fading_into_view_are_animated() {
+    let mut frames = Frames::new("", ["first", "last"].map(String::from));
+    frames.focus();
+    frames.run(vec![]);
+    frames.run(text("f"));
+    frames.run(vec![]);
+    frames.run(key(egui::Key::Backspace));
+    assert!(frames.run(vec![]).repaint);
+}
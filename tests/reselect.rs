@@ -0,0 +1,57 @@
+//! Checks that selecting the current value again is reported as a re-selection
+//! instead of a change.
+
+mod common;
+
+use common::{click, enter, run_frame};
+use egui_editable_combobox::{EditableComboBox, ShowOutput, ValueChange};
+
+fn run(ctx: &egui::Context, value: &mut String, events: Vec<egui::Event>) -> ShowOutput {
+    let mut output = None;
+    run_frame(ctx, events, |ui| {
+        output = Some(EditableComboBox::new("reselect").show_with_output(
+            ui,
+            value,
+            ["Alice", "Bob", "Carol"],
+        ));
+    });
+    output.expect("combo box shown")
+}
+
+/// Opens the popup with the cursor on the current value and presses Enter,
+/// returning the output of the frame handling Enter.
+fn enter_on_cursor(value: &str) -> (String, ShowOutput) {
+    let ctx = egui::Context::default();
+    let mut value = String::from(value);
+    run(&ctx, &mut value, vec![]);
+    let rect = run(&ctx, &mut value, vec![]).response.rect;
+    run(&ctx, &mut value, click(rect.center()));
+    run(&ctx, &mut value, vec![]);
+    let output = run(&ctx, &mut value, enter());
+    (value, output)
+}
+
+#[test]
+fn enter_on_current_value_reselects() {
+    let (value, output) = enter_on_cursor("Bob");
+    assert_eq!(value, "Bob");
+    assert_eq!(output.reselected, Some(ValueChange::Entered));
+    assert_eq!(output.change, None);
+    assert!(!output.response.changed());
+}
+
+#[test]
+fn click_on_other_value_changes() {
+    let ctx = egui::Context::default();
+    let mut value = String::from("Bob");
+    run(&ctx, &mut value, vec![]);
+    let rect = run(&ctx, &mut value, vec![]).response.rect;
+    run(&ctx, &mut value, click(rect.center()));
+    run(&ctx, &mut value, vec![]);
+    // The first option is displayed right below the editor.
+    let output = run(&ctx, &mut value, click(rect.center_bottom() + egui::vec2(0.0, 12.0)));
+    assert_eq!(value, "Alice");
+    assert_eq!(output.change, Some(ValueChange::Clicked));
+    assert_eq!(output.reselected, None);
+    assert!(output.response.changed());
+}
@@ -0,0 +1,44 @@
+//! Checks that `RichOption` displays its icon and description in the popup rows,
+//! also while the matches are highlighted.
+
+mod common;
+
+use common::{click, painted_texts, run_frame};
+use egui_editable_combobox::{EditableComboBox, RichOption};
+
+const USERS: [(&str, &str); 2] = [("Alice", "alice@example.com"), ("Bob", "bob@example.com")];
+
+/// Runs a frame, returning the rect of the editor and the texts painted in the frame.
+fn run(
+    ctx: &egui::Context,
+    value: &mut String,
+    events: Vec<egui::Event>,
+) -> (egui::Rect, Vec<String>) {
+    let mut rect = egui::Rect::NOTHING;
+    let output = run_frame(ctx, events, |ui| {
+        let options =
+            USERS.map(|(name, email)| RichOption::new(name).icon("->").description(email));
+        rect = EditableComboBox::new("users").show(ui, value, options).rect;
+    });
+    let texts = painted_texts(output);
+    (rect, texts)
+}
+
+#[test]
+fn rows_display_icon_and_description() {
+    let ctx = egui::Context::default();
+    let mut value = String::new();
+    run(&ctx, &mut value, vec![]);
+    let (rect, _) = run(&ctx, &mut value, vec![]);
+    run(&ctx, &mut value, click(rect.center()));
+    let (_, texts) = run(&ctx, &mut value, vec![]);
+    assert!(texts.iter().any(|text| text == "Alice\nalice@example.com"), "{texts:?}");
+    assert!(texts.iter().any(|text| text == "Bob\nbob@example.com"), "{texts:?}");
+    assert_eq!(texts.iter().filter(|text| *text == "->").count(), 2, "{texts:?}");
+
+    // The highlighted label keeps the icon and the description.
+    run(&ctx, &mut value, vec![egui::Event::Text("li".into())]);
+    let (_, texts) = run(&ctx, &mut value, vec![]);
+    assert!(texts.iter().any(|text| text == "Alice\nalice@example.com"), "{texts:?}");
+    assert_eq!(texts.iter().filter(|text| *text == "->").count(), 1, "{texts:?}");
+}
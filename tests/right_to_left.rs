@@ -0,0 +1,78 @@
+//! Checks that right-to-left text moves the clear button and ghost text
+//! to the left, the trailing edge of the right-aligned text.
+
+mod common;
+
+use common::{click, run_frame, text};
+use egui_editable_combobox::EditableComboBox;
+
+const SHALOM: &str = "שלום";
+
+/// Shows a combo box with a clear button, returning its rect.
+fn show_clearable(ctx: &egui::Context, value: &mut String, events: Vec<egui::Event>) -> egui::Rect {
+    let mut rect = egui::Rect::NOTHING;
+    run_frame(ctx, events, |ui| {
+        rect = EditableComboBox::new("rtl").clear_button("").show(ui, value, [SHALOM]).rect;
+    });
+    rect
+}
+
+/// Whether clicking the left edge of the editor clears `value`.
+fn cleared_from_left(value: &str) -> bool {
+    let ctx = egui::Context::default();
+    let mut value = String::from(value);
+    show_clearable(&ctx, &mut value, vec![]);
+    let rect = show_clearable(&ctx, &mut value, vec![]);
+    show_clearable(&ctx, &mut value, click(rect.left_center() + egui::vec2(8.0, 0.0)));
+    value.is_empty()
+}
+
+#[test]
+fn clear_button_is_on_the_left_of_rtl_text() {
+    assert!(cleared_from_left(SHALOM));
+    assert!(!cleared_from_left("Bob"));
+}
+
+#[test]
+fn leading_neutral_characters_are_skipped() {
+    assert!(cleared_from_left("12. שלום"));
+    assert!(!cleared_from_left("12. Bob שלום"));
+}
+
+#[test]
+fn isolates_and_marks_decide_direction() {
+    // The isolated Hebrew does not decide the direction of the text.
+    assert!(!cleared_from_left("\u{2067}שלום\u{2069} Bob"));
+    // The right-to-left mark is strongly right-to-left.
+    assert!(cleared_from_left("\u{200F}12 Bob"));
+    assert!(!cleared_from_left("\u{200E}שלום"));
+}
+
+#[test]
+fn ghost_text_is_left_of_rtl_text() {
+    let ctx = egui::Context::default();
+    let mut value = String::new();
+    let mut run = |events| {
+        let mut rect = egui::Rect::NOTHING;
+        let output = run_frame(&ctx, events, |ui| {
+            let combo_box = EditableComboBox::new("rtl").ghost_text(true);
+            rect = combo_box.show(ui, &mut value, [SHALOM]).rect;
+        });
+        (output, rect)
+    };
+    run(vec![]);
+    let (_, rect) = run(vec![]);
+    run(click(rect.center()));
+    run(text("של"));
+    let (output, _) = run(vec![]);
+
+    let text_x = |wanted: &str| {
+        output.shapes.iter().find_map(|clipped| match &clipped.shape {
+            egui::Shape::Text(shape) if shape.galley.text() == wanted => Some(shape.pos.x),
+            _ => None,
+        })
+    };
+    let typed = text_x("של").expect("typed text painted");
+    let ghost = text_x("ום").expect("ghost text painted");
+    assert!(ghost < typed, "ghost at {ghost} should be left of the text at {typed}");
+}
@@ -0,0 +1,131 @@
+//! Checks that the secondary action of a row is triggered by its button or Alt+Enter
+//! without selecting the row, and that its shortcut can match the physical key.
+
+mod common;
+
+use std::cell::RefCell;
+
+use common::{click, key_with, run_frame};
+use egui_editable_combobox::{EditableComboBox, InteractionConfig, KeyResolution, Shortcuts};
+
+/// Shows a combo box of users with an info button on all but the first row.
+struct Users {
+    ctx:        egui::Context,
+    value:      String,
+    actions:    RefCell<Vec<usize>>,
+    rect:       egui::Rect,
+    popup_open: bool,
+    shortcuts:  Shortcuts,
+}
+
+impl Users {
+    fn new() -> Self {
+        Self {
+            ctx:        egui::Context::default(),
+            value:      String::new(),
+            actions:    RefCell::default(),
+            rect:       egui::Rect::NOTHING,
+            popup_open: false,
+            shortcuts:  Shortcuts::default(),
+        }
+    }
+
+    /// Runs a frame, returning the positions of the info buttons.
+    fn run(&mut self, events: Vec<egui::Event>) -> Vec<egui::Pos2> {
+        let actions = &self.actions;
+        let output = run_frame(&self.ctx.clone(), events, |ui| {
+            let interaction =
+                InteractionConfig { shortcuts: self.shortcuts.clone(), ..Default::default() };
+            let output = EditableComboBox::new("user")
+                .interaction(interaction)
+                .row_action(
+                    |index| (index > 0).then(|| String::from("ℹ")),
+                    |index| actions.borrow_mut().push(index),
+                )
+                .show_with_output(ui, &mut self.value, ["alice", "bob", "carol"]);
+            self.rect = output.response.rect;
+            self.popup_open = output.popup_open;
+        });
+        output
+            .shapes
+            .into_iter()
+            .filter_map(|clipped| match clipped.shape {
+                egui::Shape::Text(text) if text.galley.text() == "ℹ" => {
+                    Some(text.pos + text.galley.rect.center().to_vec2())
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn focus(&mut self) -> Vec<egui::Pos2> {
+        self.run(vec![]);
+        self.run(vec![]);
+        self.run(click(self.rect.center()));
+        self.run(vec![])
+    }
+}
+
+#[test]
+fn button_triggers_action_without_selecting() {
+    let mut users = Users::new();
+    let buttons = users.focus();
+    assert_eq!(buttons.len(), 2, "the first row has no button");
+
+    users.run(click(buttons[1]));
+    users.run(vec![]);
+
+    assert_eq!(*users.actions.borrow(), [2]);
+    assert_eq!(users.value, "");
+    assert!(users.popup_open);
+}
+
+#[test]
+fn alt_enter_triggers_action_on_cursor_row() {
+    let mut users = Users::new();
+    users.focus();
+    users.run(key_with(egui::Key::ArrowDown, egui::Modifiers::NONE));
+    users.run(key_with(egui::Key::Enter, egui::Modifiers::ALT));
+    users.run(vec![]);
+
+    assert_eq!(*users.actions.borrow(), [1]);
+    assert_eq!(users.value, "");
+    assert!(users.popup_open);
+}
+
+/// Presses the key at the position of Q on a US keyboard with Alt,
+/// which produces A on an AZERTY layout.
+fn alt_azerty_q() -> Vec<egui::Event> {
+    [true, false]
+        .into_iter()
+        .map(|pressed| egui::Event::Key {
+            key: egui::Key::A,
+            physical_key: Some(egui::Key::Q),
+            pressed,
+            repeat: false,
+            modifiers: egui::Modifiers::ALT,
+        })
+        .collect()
+}
+
+/// Binds the row action to Alt+Q with `resolution`, presses [`alt_azerty_q`]
+/// on the first row with a button and returns the triggered actions.
+fn azerty_actions(resolution: KeyResolution) -> Vec<usize> {
+    let mut users = Users::new();
+    users.shortcuts = Shortcuts {
+        row_action: egui::KeyboardShortcut::new(egui::Modifiers::ALT, egui::Key::Q),
+        resolution,
+        ..Default::default()
+    };
+    users.focus();
+    users.run(key_with(egui::Key::ArrowDown, egui::Modifiers::NONE));
+    users.run(alt_azerty_q());
+    users.run(vec![]);
+    users.actions.take()
+}
+
+#[test]
+fn physical_resolution_matches_key_position() {
+    assert_eq!(azerty_actions(KeyResolution::Physical), [1]);
+    assert_eq!(azerty_actions(KeyResolution::Logical), [] as [usize; 0], "A is not Q");
+}
@@ -0,0 +1,64 @@
+//! Checks that the row spacing of the popup changes how many rows fit in it,
+//! observed through the rows skipped by Page Down.
+
+mod common;
+
+use common::{click, run_frame};
+use egui_editable_combobox::EditableComboBox;
+
+fn page_down() -> Vec<egui::Event> {
+    [true, false]
+        .into_iter()
+        .map(|pressed| egui::Event::Key {
+            key: egui::Key::PageDown,
+            physical_key: None,
+            pressed,
+            repeat: false,
+            modifiers: egui::Modifiers::NONE,
+        })
+        .collect()
+}
+
+/// Opens the popup of a combo box configured by `configure`, presses Page Down,
+/// and returns the index of the cursor option.
+fn rows_per_page(
+    configure: impl Fn(EditableComboBox<'static>) -> EditableComboBox<'static>,
+) -> usize {
+    let ctx = egui::Context::default();
+    let options: Vec<String> = (0..100).map(|i| format!("option {i}")).collect();
+    let mut value = String::from("option 0");
+    let mut run = |events: Vec<egui::Event>| {
+        let mut shown = None;
+        run_frame(&ctx, events, |ui| {
+            let output = configure(EditableComboBox::new("rows")).show_with_output(
+                ui,
+                &mut value,
+                options.iter().map(String::as_str),
+            );
+            shown = Some((output.response.rect, output.cursor));
+        });
+        shown.expect("combo box shown")
+    };
+    run(vec![]);
+    let (rect, _) = run(vec![]);
+    run(click(rect.center()));
+    run(vec![]);
+    let (_, cursor) = run(page_down());
+    cursor.expect("popup open")
+}
+
+#[test]
+fn dense_fits_more_rows() {
+    let normal = rows_per_page(|combo| combo);
+    let dense = rows_per_page(|combo| combo.dense(true));
+    assert!(dense > normal, "dense {dense}, normal {normal}");
+}
+
+#[test]
+fn row_spacing_overrides_style() {
+    let normal = rows_per_page(|combo| combo);
+    let spaced = rows_per_page(|combo| combo.row_spacing(20.0));
+    assert!(spaced < normal, "spaced {spaced}, normal {normal}");
+    let dense_spaced = rows_per_page(|combo| combo.dense(true).row_spacing(20.0));
+    assert!(dense_spaced < rows_per_page(|combo| combo.dense(true)));
+}
@@ -0,0 +1,73 @@
+//! Checks that combo boxes created in a loop with `scoped` keep separate state
+//! that follows their items when the items are reordered.
+
+mod common;
+
+use common::{click, enter, run_frame};
+use egui_editable_combobox::EditableComboBox;
+
+struct Task {
+    id:       u64,
+    assignee: String,
+}
+
+struct Board {
+    ctx:       egui::Context,
+    tasks:     Vec<Task>,
+    parent_id: egui::Id,
+    rects:     Vec<egui::Rect>,
+}
+
+impl Board {
+    fn new() -> Self {
+        Self {
+            ctx:       egui::Context::default(),
+            tasks:     vec![
+                Task { id: 7, assignee: String::from("Alice") },
+                Task { id: 3, assignee: String::from("Bob") },
+            ],
+            parent_id: egui::Id::NULL,
+            rects:     Vec::new(),
+        }
+    }
+
+    fn run(&mut self, events: Vec<egui::Event>) {
+        run_frame(&self.ctx.clone(), events, |ui| {
+            self.parent_id = ui.id();
+            self.rects = self
+                .tasks
+                .iter_mut()
+                .map(|task| {
+                    EditableComboBox::scoped(ui.id(), task.id)
+                        .show(ui, &mut task.assignee, ["Alice", "Bob", "Carol"])
+                        .rect
+                })
+                .collect();
+        });
+    }
+
+    fn is_editing(&self, task_id: u64) -> bool {
+        EditableComboBox::is_editing(&self.ctx, self.parent_id.with(task_id))
+    }
+}
+
+#[test]
+fn state_follows_item_key() {
+    let mut board = Board::new();
+    board.run(vec![]);
+    board.run(vec![]);
+    board.run(click(board.rects[0].center()));
+    board.run(vec![egui::Event::Text("Car".into())]);
+    board.run(vec![]);
+    assert!(board.is_editing(7));
+    assert!(!board.is_editing(3));
+
+    // The text being edited moves with the task.
+    board.tasks.reverse();
+    board.run(vec![]);
+    assert!(board.is_editing(7));
+    board.run(enter());
+    board.run(vec![]);
+    assert_eq!(board.tasks[0].assignee, "Bob");
+    assert_eq!(board.tasks[1].assignee, "Carol");
+}
@@ -0,0 +1,93 @@
+//! Checks that the popup scrolls to keep the cursor row visible
+//! when the cursor is moved by the keyboard or the popup is opened.
+
+mod common;
+
+use common::{click, key, run_frame};
+use egui_editable_combobox::EditableComboBox;
+
+struct List {
+    ctx:     egui::Context,
+    options: Vec<String>,
+    value:   String,
+    rect:    egui::Rect,
+    cursor:  Option<usize>,
+}
+
+impl List {
+    fn new(value: &str) -> Self {
+        Self {
+            ctx:     egui::Context::default(),
+            options: (0..500).map(|i| format!("option {i}")).collect(),
+            value:   value.into(),
+            rect:    egui::Rect::NOTHING,
+            cursor:  None,
+        }
+    }
+
+    fn run(&mut self, events: Vec<egui::Event>) {
+        run_frame(&self.ctx.clone(), events, |ui| {
+            let output = EditableComboBox::new("long list").show_with_output(
+                ui,
+                &mut self.value,
+                self.options.iter().map(String::as_str),
+            );
+            self.rect = output.response.rect;
+            self.cursor = output.cursor;
+        });
+    }
+
+    /// Runs a frame with `events` followed by an idle frame,
+    /// returning the vertical scroll offset of the popup.
+    fn step(&mut self, events: Vec<egui::Event>) -> f32 {
+        self.run(events);
+        self.run(vec![]);
+        let scroll_id = EditableComboBox::new("long list").scroll_id();
+        egui::scroll_area::State::load(&self.ctx, scroll_id).map_or(0.0, |state| state.offset.y)
+    }
+
+    fn open(&mut self) -> f32 {
+        self.run(vec![]);
+        self.run(vec![]);
+        self.step(click(self.rect.center()))
+    }
+}
+
+#[test]
+fn opening_scrolls_to_selected_option() {
+    let mut list = List::new("option 250");
+    assert!(list.open() > 0.0);
+    assert_eq!(list.cursor, Some(250));
+
+    // The rows at the top of the popup are close to the selected option.
+    list.step(click(list.rect.center_bottom() + egui::vec2(0.0, 12.0)));
+    let selected: usize = list.value["option ".len()..].parse().expect("option selected");
+    assert!((230..=250).contains(&selected), "selected {selected}");
+}
+
+#[test]
+fn keyboard_motion_scrolls_cursor_into_view() {
+    let mut list = List::new("option 0");
+    assert_eq!(list.open(), 0.0);
+
+    let mut offset = 0.0;
+    for _ in 0..5 {
+        let paged = list.step(key(egui::Key::PageDown));
+        assert!(paged > offset, "paging down scrolls down");
+        offset = paged;
+    }
+    assert!(list.step(key(egui::Key::End)) > offset);
+    assert_eq!(list.cursor, Some(499));
+    assert_eq!(list.step(key(egui::Key::Home)), 0.0);
+    assert_eq!(list.cursor, Some(0));
+}
+
+#[test]
+fn enter_selects_cursor_moved_out_of_view_in_same_frame() {
+    let mut list = List::new("option 0");
+    list.open();
+    let mut events = key(egui::Key::End);
+    events.extend(key(egui::Key::Enter));
+    list.step(events);
+    assert_eq!(list.value, "option 499");
+}
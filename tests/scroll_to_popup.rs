@@ -0,0 +1,57 @@
+//! Checks that `scroll_to_popup` scrolls the parent scroll area
+//! so that the editor and the opened popup are both visible.
+
+mod common;
+
+use common::{click, run_frame_sized};
+use egui_editable_combobox::{EditableComboBox, PopupPlacement};
+
+const SCREEN: egui::Vec2 = egui::vec2(400.0, 300.0);
+
+/// Opens the popup below a combo box at the bottom of a scrolled form,
+/// returning the scroll offset of the form, the rect of the editor and the rect of the popup.
+fn open(scroll_to_popup: bool) -> (f32, egui::Rect, egui::Rect) {
+    let ctx = egui::Context::default();
+    let mut value = String::new();
+    let options: Vec<String> = (0..10).map(|i| format!("option {i}")).collect();
+    let mut run = |events| {
+        let mut shown = (0.0, egui::Rect::NOTHING);
+        run_frame_sized(&ctx, SCREEN, events, |ui| {
+            let output = egui::ScrollArea::vertical().show(ui, |ui| {
+                ui.add_space(250.0);
+                let rect = EditableComboBox::new("form field")
+                    .popup_placement(PopupPlacement::Fixed(egui::RectAlign::BOTTOM_START))
+                    .scroll_to_popup(scroll_to_popup)
+                    .show(ui, &mut value, options.iter().map(String::as_str))
+                    .rect;
+                ui.add_space(600.0);
+                rect
+            });
+            shown = (output.state.offset.y, output.inner);
+        });
+        shown
+    };
+
+    run(vec![]);
+    let (_, rect) = run(vec![]);
+    run(click(rect.center()));
+    for _ in 0..3 {
+        run(vec![]);
+    }
+    let (offset, rect) = run(vec![]);
+    let popup_id = EditableComboBox::new("form field").popup_id();
+    let popup = ctx.memory(|mem| mem.area_rect(popup_id)).expect("popup open");
+    (offset, rect, popup)
+}
+
+#[test]
+fn parent_scrolls_to_popup() {
+    let (offset, ..) = open(false);
+    assert_eq!(offset, 0.0, "the form is not scrolled by default");
+
+    let (offset, editor, popup) = open(true);
+    assert!(offset > 0.0, "the form is scrolled down");
+    let visible = egui::Rect::from_min_size(egui::Pos2::ZERO, SCREEN);
+    assert!(visible.contains_rect(editor), "{editor:?} is out of view");
+    assert!(visible.contains_rect(popup), "{popup:?} is out of view");
+}
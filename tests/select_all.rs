@@ -0,0 +1,118 @@
+//! Checks that the select all footer of `show_multi` acts on all matching options,
+//! not only the rows in view, and asks to confirm selecting many.
+
+mod common;
+
+use common::{click, run_frame, text};
+use egui_editable_combobox::EditableComboBox;
+
+fn options() -> impl Iterator<Item = String> {
+    (0..50).map(|i| format!("item {i}")).chain([String::from("other")])
+}
+
+struct Tags {
+    ctx:           egui::Context,
+    tags:          Vec<String>,
+    confirm_above: usize,
+    rect:          egui::Rect,
+    output:        Option<egui::FullOutput>,
+}
+
+impl Tags {
+    fn new(tags: &[&str], confirm_above: usize) -> Self {
+        Self {
+            ctx: egui::Context::default(),
+            tags: tags.iter().map(|&tag| String::from(tag)).collect(),
+            confirm_above,
+            rect: egui::Rect::NOTHING,
+            output: None,
+        }
+    }
+
+    fn run(&mut self, events: Vec<egui::Event>) {
+        let output = run_frame(&self.ctx.clone(), events, |ui| {
+            let response = EditableComboBox::new("tags")
+                .select_all_footer(self.confirm_above)
+                .show_multi(ui, &mut self.tags, options());
+            self.rect = response.rect;
+        });
+        self.output = Some(output);
+    }
+
+    fn focus(&mut self) {
+        self.run(vec![]);
+        self.run(vec![]);
+        self.run(click(self.rect.center()));
+        self.run(vec![]);
+    }
+
+    /// Clicks the `label` painted in the last frame, then runs an idle frame.
+    fn click_label(&mut self, label: &str) {
+        let shapes = self.output.take().expect("frame run").shapes;
+        let pos = shapes
+            .into_iter()
+            .find_map(|clipped| match clipped.shape {
+                egui::Shape::Text(shape) if shape.galley.text() == label => {
+                    Some(shape.visual_bounding_rect().center())
+                }
+                _ => None,
+            })
+            .unwrap_or_else(|| panic!("{label:?} not painted"));
+        self.run(click(pos));
+        self.run(vec![]);
+    }
+
+    fn popup_open(&self) -> bool {
+        let popup_id = EditableComboBox::new("tags").popup_id();
+        egui::Popup::is_id_open(&self.ctx, popup_id)
+    }
+}
+
+#[test]
+fn select_all_adds_matches_out_of_view() {
+    let mut tags = Tags::new(&["item 3"], 100);
+    tags.focus();
+    tags.run(text("item"));
+    tags.run(vec![]);
+    tags.click_label("Select all matches (49)");
+
+    assert_eq!(tags.tags.len(), 50);
+    assert!(tags.tags.contains(&String::from("item 49")), "rows out of view are selected");
+    assert!(!tags.tags.contains(&String::from("other")), "only matches are selected");
+    assert!(tags.popup_open());
+}
+
+#[test]
+fn select_all_many_asks_for_confirmation() {
+    let mut tags = Tags::new(&[], 10);
+    tags.focus();
+    tags.run(text("item 1"));
+    tags.run(vec![]);
+    // "item 1" and "item 10" to "item 19"
+    tags.click_label("Select all matches (11)");
+    assert!(tags.tags.is_empty());
+
+    tags.click_label("Select all");
+    assert_eq!(tags.tags.len(), 11);
+}
+
+#[test]
+fn confirmation_can_be_cancelled() {
+    let mut tags = Tags::new(&[], 10);
+    tags.focus();
+    tags.run(text("item"));
+    tags.run(vec![]);
+    tags.click_label("Select all matches (50)");
+    tags.click_label("Cancel");
+    assert!(tags.tags.is_empty());
+    tags.click_label("Select all matches (50)");
+}
+
+#[test]
+fn clear_selection_removes_all_values() {
+    let mut tags = Tags::new(&["item 1", "other"], 100);
+    tags.focus();
+    tags.click_label("Clear selection");
+    assert!(tags.tags.is_empty());
+    assert!(tags.popup_open());
+}
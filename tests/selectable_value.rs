@@ -0,0 +1,61 @@
+//! Checks that the current value stays selectable text when the editor is focused
+//! with `selectable_value`, and that the popup still lists all options.
+
+mod common;
+
+use common::{click, run_frame};
+use egui_editable_combobox::{EditableComboBox, ShowOutput};
+
+fn select_all_and_copy() -> Vec<egui::Event> {
+    [true, false]
+        .into_iter()
+        .map(|pressed| egui::Event::Key {
+            key: egui::Key::A,
+            physical_key: None,
+            pressed,
+            repeat: false,
+            modifiers: egui::Modifiers::COMMAND,
+        })
+        .chain([egui::Event::Copy])
+        .collect()
+}
+
+/// Runs a frame, returning the output of the combo box and the text copied in the frame.
+fn run(
+    ctx: &egui::Context,
+    value: &mut String,
+    events: Vec<egui::Event>,
+) -> (ShowOutput, Option<String>) {
+    let mut output = None;
+    let full_output = run_frame(ctx, events, |ui| {
+        output = Some(EditableComboBox::new("selectable").selectable_value(true).show_with_output(
+            ui,
+            value,
+            ["Alice", "Bob", "Carol"],
+        ));
+    });
+    let copied =
+        full_output.platform_output.commands.into_iter().find_map(|command| match command {
+            egui::OutputCommand::CopyText(text) => Some(text),
+            _ => None,
+        });
+    (output.expect("combo box shown"), copied)
+}
+
+#[test]
+fn focusing_keeps_value_and_lists_all_options() {
+    let ctx = egui::Context::default();
+    let mut value = String::from("Bob");
+    run(&ctx, &mut value, vec![]);
+    let rect = run(&ctx, &mut value, vec![]).0.response.rect;
+    run(&ctx, &mut value, click(rect.center()));
+    let (output, _) = run(&ctx, &mut value, vec![]);
+    assert!(output.response.has_focus() && output.popup_open);
+
+    let (_, copied) = run(&ctx, &mut value, select_all_and_copy());
+    assert_eq!(copied.as_deref(), Some("Bob"));
+
+    // The first option is displayed right below the editor, unfiltered by the value.
+    run(&ctx, &mut value, click(rect.center_bottom() + egui::vec2(0.0, 12.0)));
+    assert_eq!(value, "Alice");
+}
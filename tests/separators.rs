@@ -0,0 +1,140 @@
+//! Checks that separators between groups of options work with the other filtering settings.
+
+mod common;
+
+use common::{click, key, key_with, run_frame, text};
+use egui_editable_combobox::{EditableComboBox, Keyworded, MatchMode, with_separators};
+
+const FUNCTIONS: [&str; 4] = ["print", "println", "sprintf", "sprintln"];
+
+/// The centers of the horizontal lines painted in a frame, unlike the vertical text cursor.
+fn separator_centers(output: &egui::FullOutput) -> Vec<egui::Pos2> {
+    output
+        .shapes
+        .iter()
+        .filter_map(|clipped| match clipped.shape {
+            egui::Shape::LineSegment { points: [start, end], .. } if start.y == end.y => {
+                Some(start.lerp(end, 0.5))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// The number of horizontal lines painted in a frame.
+fn separator_count(output: &egui::FullOutput) -> usize { separator_centers(output).len() }
+
+/// Runs a frame of a combo box listing the functions grouped by their initial letter,
+/// returning its rect, the number of options displayed and the number of separators.
+fn run(ctx: &egui::Context, events: Vec<egui::Event>) -> (egui::Rect, usize, usize) {
+    let mut value = String::new();
+    let mut shown = (egui::Rect::NOTHING, 0);
+    let output = run_frame(ctx, events, |ui| {
+        let options = with_separators(FUNCTIONS, |prev, next| prev[..1] != next[..1]);
+        let output = EditableComboBox::new("function")
+            .match_mode(MatchMode::CaseSensitiveSubstring)
+            .show_with_output(ui, &mut value, options);
+        shown = (output.response.rect, output.filtered_count);
+    });
+    (shown.0, shown.1, separator_count(&output))
+}
+
+#[test]
+fn match_mode_keeps_separators() {
+    let ctx = egui::Context::default();
+    let (rect, ..) = run(&ctx, vec![]);
+    run(&ctx, click(rect.center()));
+    let (_, count, separators) = run(&ctx, vec![]);
+    assert_eq!((count, separators), (4, 1));
+
+    run(&ctx, text("ln"));
+    let (_, count, separators) = run(&ctx, vec![]);
+    assert_eq!((count, separators), (2, 1));
+}
+
+#[test]
+fn match_mode_keeps_keywords() {
+    let ctx = egui::Context::default();
+    let mut value = String::new();
+    let mut run = |events| {
+        let mut shown = (egui::Rect::NOTHING, 0);
+        run_frame(&ctx, events, |ui| {
+            let output =
+                EditableComboBox::new("drink").match_mode(MatchMode::PrefixOnly).show_with_output(
+                    ui,
+                    &mut value,
+                    [Keyworded::new("Espresso", "coffee hot"), Keyworded::new("Lemonade", "cold")],
+                );
+            shown = (output.response.rect, output.filtered_count);
+        });
+        shown
+    };
+    let (rect, _) = run(vec![]);
+    run(click(rect.center()));
+    run(text("cold"));
+    assert_eq!(run(vec![]).1, 1);
+}
+
+#[test]
+fn group_shortcuts_jump_between_groups() {
+    let ctx = egui::Context::default();
+    let mut name = String::new();
+    let mut shown = (egui::Rect::NOTHING, None);
+    let mut run = |events| {
+        run_frame(&ctx, events, |ui| {
+            let names = ["Alice", "Amy", "Bob", "Bill", "Carol"];
+            let options = with_separators(names, |prev, next| prev[..1] != next[..1]);
+            let output = EditableComboBox::new("name").show_with_output(ui, &mut name, options);
+            shown = (output.response.rect, output.cursor);
+        });
+        shown
+    };
+    let ctrl = |key| key_with(key, egui::Modifiers::CTRL);
+
+    let (rect, _) = run(vec![]);
+    run(click(rect.center()));
+    assert_eq!(run(vec![]).1, Some(0));
+    // The source indices count the separators after Amy and Bill.
+    assert_eq!(run(ctrl(egui::Key::ArrowDown)).1, Some(3));
+    assert_eq!(run(ctrl(egui::Key::ArrowDown)).1, Some(6));
+    // Like the arrow keys, the shortcuts wrap around.
+    assert_eq!(run(ctrl(egui::Key::ArrowDown)).1, Some(0));
+    assert_eq!(run(ctrl(egui::Key::ArrowUp)).1, Some(6));
+    assert_eq!(run(key(egui::Key::ArrowUp)).1, Some(4));
+    assert_eq!(run(ctrl(egui::Key::ArrowUp)).1, Some(0));
+}
+
+#[test]
+fn separators_are_skipped_and_never_committed() {
+    let ctx = egui::Context::default();
+    let mut name = String::new();
+    let mut run = |events| {
+        let mut shown = None;
+        let full_output = run_frame(&ctx, events, |ui| {
+            let names = ["Alice", "Amy", "Bob"];
+            let options = with_separators(names, |prev, next| prev[..1] != next[..1]);
+            let output = EditableComboBox::new("name").show_with_output(ui, &mut name, options);
+            shown = Some((output.response.rect, output.cursor, output.change));
+        });
+        let (rect, cursor, change) = shown.expect("combo box shown");
+        (rect, cursor, change, separator_centers(&full_output))
+    };
+
+    let (rect, ..) = run(vec![]);
+    run(click(rect.center()));
+    let (_, cursor, _, separators) = run(vec![]);
+    assert_eq!(cursor, Some(0));
+    let &[separator] = separators.as_slice() else { panic!("one separator: {separators:?}") };
+
+    assert_eq!(run(key(egui::Key::ArrowDown)).1, Some(1));
+    // The separator after Amy has the source index 2.
+    assert_eq!(run(key(egui::Key::ArrowDown)).1, Some(3));
+    assert_eq!(run(key(egui::Key::ArrowUp)).1, Some(1));
+
+    let (_, _, change, separators) = run(click(separator));
+    assert_eq!(change, None);
+    assert_eq!(separators.len(), 1, "the popup stays open");
+    let (.., change, _) = run(vec![]);
+    assert_eq!(change, None);
+    assert_eq!(name, "");
+}
@@ -0,0 +1,49 @@
+//! Checks that `show_with_setter` reports changes through the setter
+//! without mutating the current value.
+
+mod common;
+
+use common::{click, enter, run_frame, text};
+use egui_editable_combobox::EditableComboBox;
+
+/// Shows the combo box bound to `fruit`, returning the rect of the editor.
+fn run(
+    ctx: &egui::Context,
+    fruit: &String,
+    set: &mut Vec<String>,
+    events: Vec<egui::Event>,
+) -> egui::Rect {
+    let mut rect = egui::Rect::NOTHING;
+    run_frame(ctx, events, |ui| {
+        rect = EditableComboBox::new("fruit")
+            .show_with_setter(ui, fruit, |fruit| set.push(fruit), ["apple", "banana", "cherry"])
+            .rect;
+    });
+    rect
+}
+
+/// Focuses the editor, types `typed` and presses Enter, returning the values set.
+fn enter_typed(typed: &str) -> Vec<String> {
+    let ctx = egui::Context::default();
+    let fruit = String::from("apple");
+    let mut set = Vec::new();
+    run(&ctx, &fruit, &mut set, vec![]);
+    let rect = run(&ctx, &fruit, &mut set, vec![]);
+    run(&ctx, &fruit, &mut set, click(rect.center()));
+    run(&ctx, &fruit, &mut set, text(typed));
+    run(&ctx, &fruit, &mut set, vec![]);
+    run(&ctx, &fruit, &mut set, enter());
+    run(&ctx, &fruit, &mut set, vec![]);
+    assert_eq!(fruit, "apple", "the current value is only read");
+    set
+}
+
+#[test]
+fn setter_receives_new_value() {
+    assert_eq!(enter_typed("ban"), ["banana"]);
+}
+
+#[test]
+fn reselecting_current_value_sets_nothing() {
+    assert_eq!(enter_typed("app"), [] as [String; 0]);
+}
@@ -0,0 +1,181 @@
+//! Checks that a combo box keeps its editing state in the `StateStore` set by the app,
+//! so that each document of a multi-document editor has its own typed text,
+//! popup cursor and history.
+
+mod common;
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use common::{click, enter, key, painted_texts, run_frame, text};
+use egui_editable_combobox::{
+    EditableComboBox, HistoryMode, InteractionConfig, SelectionRecord, StateStore,
+};
+
+/// The editing state of a document.
+#[derive(Default)]
+struct Document {
+    font:    Mutex<String>,
+    texts:   Mutex<HashMap<egui::Id, String>>,
+    cursors: Mutex<HashMap<egui::Id, usize>>,
+    history: Mutex<HashMap<egui::Id, Vec<SelectionRecord>>>,
+}
+
+impl StateStore for Document {
+    fn text(&self, _ctx: &egui::Context, id: egui::Id) -> Option<String> {
+        self.texts.lock().unwrap().get(&id).cloned()
+    }
+
+    fn set_text(&self, _ctx: &egui::Context, id: egui::Id, text: Option<String>) {
+        let mut texts = self.texts.lock().unwrap();
+        match text {
+            Some(text) => texts.insert(id, text),
+            None => texts.remove(&id),
+        };
+    }
+
+    fn cursor(&self, _ctx: &egui::Context, id: egui::Id) -> Option<usize> {
+        self.cursors.lock().unwrap().get(&id).copied()
+    }
+
+    fn set_cursor(&self, _ctx: &egui::Context, id: egui::Id, source_index: Option<usize>) {
+        let mut cursors = self.cursors.lock().unwrap();
+        match source_index {
+            Some(source_index) => cursors.insert(id, source_index),
+            None => cursors.remove(&id),
+        };
+    }
+
+    fn history(&self, _ctx: &egui::Context, id: egui::Id) -> Vec<SelectionRecord> {
+        self.history.lock().unwrap().get(&id).cloned().unwrap_or_default()
+    }
+
+    fn set_history(&self, _ctx: &egui::Context, id: egui::Id, history: Vec<SelectionRecord>) {
+        self.history.lock().unwrap().insert(id, history);
+    }
+}
+
+struct Editor {
+    ctx:       egui::Context,
+    documents: [Document; 2],
+    /// The index of the displayed document.
+    current:   usize,
+    enabled:   bool,
+    rect:      egui::Rect,
+    /// The source index of the cursor option in the last frame.
+    cursor:    Option<usize>,
+}
+
+impl Editor {
+    fn new() -> Self {
+        let documents = [Document::default(), Document::default()];
+        *documents[0].font.lock().unwrap() = String::from("Serif");
+        *documents[1].font.lock().unwrap() = String::from("Mono");
+        Self {
+            ctx: egui::Context::default(),
+            documents,
+            current: 0,
+            enabled: true,
+            rect: egui::Rect::NOTHING,
+            cursor: None,
+        }
+    }
+
+    /// Runs a frame, returning the painted texts.
+    fn run(&mut self, events: Vec<egui::Event>) -> Vec<String> {
+        let document = &self.documents[self.current];
+        let output = run_frame(&self.ctx.clone(), events, |ui| {
+            let mut font = document.font.lock().unwrap();
+            let output = EditableComboBox::new("font")
+                .state_store(document)
+                .interactive(self.enabled)
+                .interaction(InteractionConfig {
+                    history: HistoryMode::Readline,
+                    ..Default::default()
+                })
+                .show_with_output(ui, &mut *font, ["Serif", "Sans", "Mono"]);
+            self.rect = output.response.rect;
+            self.cursor = output.cursor;
+        });
+        painted_texts(output)
+    }
+
+    fn focus(&mut self) {
+        self.run(vec![]);
+        self.run(vec![]);
+        self.run(click(self.rect.center()));
+        self.run(vec![]);
+    }
+}
+
+#[test]
+fn typed_text_lives_with_document() {
+    let mut editor = Editor::new();
+    editor.focus();
+    editor.run(text("Sa"));
+    assert_eq!(editor.documents[0].texts.lock().unwrap().values().next().unwrap(), "Sa");
+
+    // The other document has not been edited.
+    editor.current = 1;
+    assert!(!editor.run(vec![]).contains(&String::from("Sa")));
+
+    editor.current = 0;
+    assert!(editor.run(vec![]).contains(&String::from("Sa")));
+}
+
+#[test]
+fn history_lives_with_document() {
+    let mut editor = Editor::new();
+    editor.focus();
+    editor.run(text("Sans"));
+    editor.run(vec![]);
+    editor.run(enter());
+    editor.run(vec![]);
+    assert_eq!(*editor.documents[0].font.lock().unwrap(), "Sans");
+
+    let history = editor.documents[0].history.lock().unwrap();
+    let texts: Vec<Vec<&str>> =
+        history.values().map(|records| records.iter().map(|r| r.text.as_str()).collect()).collect();
+    assert_eq!(texts, [vec!["Sans"]]);
+    assert!(EditableComboBox::selection_history(&editor.ctx, "font").is_empty());
+    assert!(editor.documents[1].history.lock().unwrap().is_empty());
+}
+
+#[test]
+fn cursor_lives_with_document() {
+    let mut editor = Editor::new();
+    editor.focus();
+    editor.run(text("S"));
+    editor.run(key(egui::Key::ArrowDown));
+    editor.run(vec![]);
+    assert_eq!(editor.cursor, Some(1), "Sans is under the cursor");
+
+    // The other document has its own text and cursor.
+    editor.current = 1;
+    editor.run(vec![]);
+    assert_eq!(editor.cursor, Some(2), "the text of the other document is Mono");
+
+    editor.current = 0;
+    let texts = editor.run(vec![]);
+    assert!(texts.contains(&String::from("S")));
+    assert_eq!(editor.cursor, Some(1));
+    assert_eq!(
+        editor.documents[0].cursors.lock().unwrap().values().copied().collect::<Vec<_>>(),
+        [1]
+    );
+}
+
+#[test]
+fn disabling_discards_state_in_store() {
+    let mut editor = Editor::new();
+    editor.focus();
+    editor.run(text("Sa"));
+
+    editor.enabled = false;
+    editor.run(vec![]);
+    let texts = editor.run(vec![]);
+    assert!(!texts.contains(&String::from("Sa")));
+    let stored: Vec<_> = editor.documents[0].texts.lock().unwrap().values().cloned().collect();
+    assert_eq!(stored, ["Serif"], "the typed text is replaced by the current value");
+    assert!(editor.documents[0].cursors.lock().unwrap().is_empty());
+}
@@ -0,0 +1,64 @@
+//! Checks that values committed in one combo box are suggested
+//! by the other combo boxes sharing its suggestion pool.
+
+mod common;
+
+use common::{click, enter, run_frame};
+use egui_editable_combobox::{EditableComboBox, SuggestionPool};
+
+/// A book and an article form, each with an author field.
+struct Forms {
+    ctx:     egui::Context,
+    authors: [String; 2],
+    rects:   [egui::Rect; 2],
+}
+
+impl Forms {
+    fn run(&mut self, events: Vec<egui::Event>) {
+        let pool = SuggestionPool::new("author");
+        run_frame(&self.ctx.clone(), events, |ui| {
+            for (index, id) in ["book author", "article author"].into_iter().enumerate() {
+                self.rects[index] = EditableComboBox::new(id)
+                    .allow_free_text(true)
+                    .suggestion_pool(pool)
+                    .show(ui, &mut self.authors[index], pool.suggestions(ui.ctx()))
+                    .rect;
+            }
+        });
+    }
+
+    fn focus(&mut self, index: usize) {
+        self.run(vec![]);
+        self.run(click(self.rects[index].center()));
+        self.run(vec![]);
+    }
+}
+
+#[test]
+fn committed_values_are_shared() {
+    let mut forms = Forms {
+        ctx:     egui::Context::default(),
+        authors: Default::default(),
+        rects:   [egui::Rect::NOTHING; 2],
+    };
+    forms.run(vec![]);
+    forms.focus(0);
+    forms.run(vec![egui::Event::Text(String::from("Ursula K. Le Guin"))]);
+    forms.run(enter());
+    forms.run(vec![]);
+    assert_eq!(forms.authors[0], "Ursula K. Le Guin");
+
+    let pool = SuggestionPool::new("author");
+    assert_eq!(pool.suggestions(&forms.ctx), ["Ursula K. Le Guin"]);
+
+    // The article author field suggests the author entered for the book.
+    forms.focus(1);
+    forms.run(vec![egui::Event::Text(String::from("le gu"))]);
+    forms.run(vec![]);
+    forms.run(enter());
+    forms.run(vec![]);
+    assert_eq!(forms.authors[1], "Ursula K. Le Guin");
+
+    pool.clear(&forms.ctx);
+    assert!(pool.suggestions(&forms.ctx).is_empty());
+}
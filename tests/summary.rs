@@ -0,0 +1,43 @@
+//! Checks that `show_with_summary` paints the summary of the current value
+//! except while the user is editing it.
+
+mod common;
+
+use common::{click, enter, painted_texts, run_frame, text};
+use egui_editable_combobox::EditableComboBox;
+
+/// Shows the combo box bound to `user`, returning the rect of the editor and the texts painted.
+fn run(
+    ctx: &egui::Context,
+    user: &mut String,
+    events: Vec<egui::Event>,
+) -> (egui::Rect, Vec<String>) {
+    let mut rect = egui::Rect::NOTHING;
+    let output = run_frame(ctx, events, |ui| {
+        rect = EditableComboBox::new("user")
+            .show_with_summary(ui, user, ["alice", "bob"], |ui, user| {
+                ui.label(format!("{user}@example.com"));
+            })
+            .rect;
+    });
+    (rect, painted_texts(output))
+}
+
+#[test]
+fn summary_follows_committed_value() {
+    let ctx = egui::Context::default();
+    let mut user = String::from("alice");
+    run(&ctx, &mut user, vec![]);
+    let (rect, texts) = run(&ctx, &mut user, vec![]);
+    assert!(texts.iter().any(|text| text == "alice@example.com"), "{texts:?}");
+
+    run(&ctx, &mut user, click(rect.center()));
+    run(&ctx, &mut user, text("bo"));
+    let (_, texts) = run(&ctx, &mut user, vec![]);
+    assert!(!texts.iter().any(|text| text.ends_with("@example.com")), "{texts:?}");
+
+    run(&ctx, &mut user, enter());
+    let (_, texts) = run(&ctx, &mut user, vec![]);
+    assert_eq!(user, "bob");
+    assert!(texts.iter().any(|text| text == "bob@example.com"), "{texts:?}");
+}
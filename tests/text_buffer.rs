@@ -0,0 +1,75 @@
+//! Checks that a custom [`egui::TextBuffer`] holds the typed text
+//! and that the settings made before switching the buffer type are kept.
+
+mod common;
+
+use std::any::TypeId;
+use std::cell::RefCell;
+use std::ops::Range;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use common::{click, enter, run_frame, text};
+use egui::TextBuffer;
+use egui_editable_combobox::EditableComboBox;
+
+/// The number of insertions into any [`Tracked`] buffer.
+static INSERTIONS: AtomicUsize = AtomicUsize::new(0);
+
+/// A text buffer counting the insertions of typed text.
+#[derive(Default, Clone)]
+struct Tracked(String);
+
+impl TextBuffer for Tracked {
+    fn is_mutable(&self) -> bool { true }
+
+    fn as_str(&self) -> &str { &self.0 }
+
+    fn insert_text(&mut self, text: &str, char_index: usize) -> usize {
+        INSERTIONS.fetch_add(1, Ordering::Relaxed);
+        self.0.insert_text(text, char_index)
+    }
+
+    fn delete_char_range(&mut self, char_range: Range<usize>) {
+        self.0.delete_char_range(char_range)
+    }
+
+    fn type_id(&self) -> TypeId { TypeId::of::<Self>() }
+}
+
+#[test]
+fn custom_buffer_filters_and_commits() {
+    let ctx = egui::Context::default();
+    let mut value = String::new();
+    let selected = RefCell::new(Vec::new());
+    let mut rect = egui::Rect::NOTHING;
+    let mut cursor = None;
+    let mut run = |value: &mut String, events| {
+        run_frame(&ctx, events, |ui| {
+            let output = EditableComboBox::new("buffer")
+                .reverse_order(true)
+                .on_select(|index| selected.borrow_mut().push(index))
+                .text_buffer::<Tracked>()
+                .show_with_output(ui, value, ["apple", "apricot", "banana"]);
+            rect = output.response.rect;
+            cursor = output.cursor;
+        });
+        (rect, cursor)
+    };
+
+    run(&mut value, vec![]);
+    let (rect, _) = run(&mut value, vec![]);
+    run(&mut value, click(rect.center()));
+    let (_, cursor) = run(&mut value, vec![]);
+    assert_eq!(cursor, Some(2), "the options are still displayed in reverse order");
+
+    let before = INSERTIONS.load(Ordering::Relaxed);
+    run(&mut value, text("ap"));
+    assert!(INSERTIONS.load(Ordering::Relaxed) > before, "the text is typed into the buffer");
+    let (_, cursor) = run(&mut value, vec![]);
+    assert_eq!(cursor, Some(1));
+    run(&mut value, enter());
+    run(&mut value, vec![]);
+
+    assert_eq!(value, "apricot");
+    assert_eq!(*selected.borrow(), [1]);
+}
@@ -0,0 +1,102 @@
+//! Checks that the URL preset remembers entered URLs,
+//! displays the rest of a remembered URL as ghost text while typing,
+//! and rejects invalid URLs.
+
+mod common;
+
+use common::{click, key, painted_texts, run_frame, text};
+use egui_editable_combobox::{UrlPicker, UrlValue};
+
+fn url(text: &str) -> UrlValue { text.parse().expect("valid URL") }
+
+struct Bar {
+    ctx:    egui::Context,
+    url:    Option<UrlValue>,
+    scheme: Option<&'static str>,
+    rect:   egui::Rect,
+}
+
+impl Bar {
+    fn new() -> Self {
+        Self {
+            ctx:    egui::Context::default(),
+            url:    None,
+            scheme: None,
+            rect:   egui::Rect::NOTHING,
+        }
+    }
+
+    /// Runs a frame, returning the painted texts.
+    fn run(&mut self, events: Vec<egui::Event>) -> Vec<String> {
+        let output = run_frame(&self.ctx.clone(), events, |ui| {
+            let mut picker = UrlPicker::new("address");
+            if let Some(scheme) = self.scheme {
+                picker = picker.locked_scheme(scheme);
+            }
+            self.rect = picker.show(ui, &mut self.url).rect;
+        });
+        painted_texts(output)
+    }
+
+    /// Focuses the editor, types `typed` and returns the texts of the next frame.
+    fn type_text(&mut self, typed: &str) -> Vec<String> {
+        self.run(vec![]);
+        self.run(vec![]);
+        self.run(click(self.rect.center()));
+        self.run(text(typed));
+        self.run(vec![])
+    }
+
+    /// Commits the typed text with Enter and moves focus away.
+    fn enter(&mut self) {
+        self.run(key(egui::Key::Enter));
+        self.run(click(egui::pos2(500.0, 500.0)));
+        self.run(vec![]);
+    }
+}
+
+#[test]
+fn parses_urls() {
+    let docs = url("http://localhost:8080/docs?page=2");
+    assert_eq!(docs.scheme(), "http");
+    assert_eq!(docs.host(), "localhost:8080");
+    assert_eq!(url("example.com").as_str(), "https://example.com");
+    for invalid in ["", "https://", "1http://example.com", "example .com"] {
+        assert!(invalid.parse::<UrlValue>().is_err(), "{invalid:?}");
+    }
+}
+
+#[test]
+fn remembers_and_completes_urls() {
+    let mut bar = Bar::new();
+    bar.type_text("github.com/emilk/egui");
+    bar.enter();
+    assert_eq!(bar.url, Some(url("https://github.com/emilk/egui")));
+    assert_eq!(UrlPicker::history(&bar.ctx, "address"), ["https://github.com/emilk/egui"]);
+
+    bar.url = None;
+    let texts = bar.type_text("git");
+    assert!(texts.contains(&String::from("hub.com/emilk/egui")), "ghost text in {texts:?}");
+    bar.run(key(egui::Key::Tab));
+    bar.run(vec![]);
+    bar.enter();
+    assert_eq!(bar.url, Some(url("https://github.com/emilk/egui")));
+}
+
+#[test]
+fn invalid_url_is_not_committed() {
+    let mut bar = Bar::new();
+    bar.type_text("not a url");
+    bar.enter();
+    assert_eq!(bar.url, None);
+    assert!(UrlPicker::history(&bar.ctx, "address").is_empty());
+}
+
+#[test]
+fn locked_scheme_is_prepended() {
+    let mut bar = Bar::new();
+    bar.scheme = Some("https");
+    bar.type_text("example.com");
+    bar.enter();
+    assert_eq!(bar.url, Some(url("https://example.com")));
+}
@@ -0,0 +1,66 @@
+//! Checks that free text rejected by the `validate` hook is outlined as an error
+//! and is not committed.
+
+mod common;
+
+use common::{click, enter, run_frame};
+use egui_editable_combobox::EditableComboBox;
+
+struct Host {
+    ctx:   egui::Context,
+    value: String,
+    rect:  egui::Rect,
+}
+
+impl Host {
+    fn new() -> Self {
+        Self {
+            ctx:   egui::Context::default(),
+            value: String::from("localhost"),
+            rect:  egui::Rect::NOTHING,
+        }
+    }
+
+    /// Runs a frame, returning whether the editor is outlined in the error color.
+    fn run(&mut self, events: Vec<egui::Event>) -> bool {
+        let output = run_frame(&self.ctx.clone(), events, |ui| {
+            let output = EditableComboBox::new("host")
+                .allow_free_text(true)
+                .validate(|text| {
+                    if text.contains(' ') { Err("spaces are not allowed".into()) } else { Ok(()) }
+                })
+                .show_with_output(ui, &mut self.value, ["localhost", "example.com"]);
+            self.rect = output.response.rect;
+        });
+        let error = self.ctx.style().visuals.error_fg_color;
+        output.shapes.into_iter().any(|clipped| {
+            matches!(clipped.shape, egui::Shape::Rect(rect) if rect.stroke.color == error)
+        })
+    }
+
+    fn type_text(&mut self, text: &str) -> bool {
+        self.run(vec![]);
+        self.run(vec![]);
+        self.run(click(self.rect.center()));
+        self.run(vec![egui::Event::Text(text.into())]);
+        self.run(vec![])
+    }
+}
+
+#[test]
+fn invalid_text_is_outlined_and_not_committed() {
+    let mut host = Host::new();
+    assert!(host.type_text("my host"));
+    host.run(enter());
+    host.run(vec![]);
+    assert_eq!(host.value, "localhost");
+}
+
+#[test]
+fn valid_text_is_committed() {
+    let mut host = Host::new();
+    assert!(!host.type_text("db.internal"));
+    host.run(enter());
+    host.run(vec![]);
+    assert_eq!(host.value, "db.internal");
+}
@@ -0,0 +1,78 @@
+//! Checks that a validated custom row displays why the text is invalid
+//! and cannot be selected until the text is valid.
+
+mod common;
+
+use std::sync::Arc;
+
+use common::{click, enter, painted_texts, run_frame};
+use egui_editable_combobox::{CustomOption, CustomValue, EditableComboBox, Validator};
+
+struct Branches {
+    ctx:       egui::Context,
+    validator: Arc<Validator>,
+    value:     CustomValue<String>,
+    rect:      egui::Rect,
+}
+
+impl Branches {
+    fn new() -> Self {
+        Self {
+            ctx:       egui::Context::default(),
+            validator: Arc::new(Validator::new().min_chars(2).max_chars(8)),
+            value:     CustomValue::Value(String::from("main")),
+            rect:      egui::Rect::NOTHING,
+        }
+    }
+
+    /// Runs a frame, returning the texts painted in it.
+    fn run(&mut self, events: Vec<egui::Event>) -> Vec<String> {
+        let output = run_frame(&self.ctx.clone(), events, |ui| {
+            let options = ["main", "develop"]
+                .map(CustomOption::Value)
+                .into_iter()
+                .chain([CustomOption::Validated(self.validator.clone())]);
+            self.rect = EditableComboBox::new("branch").show(ui, &mut self.value, options).rect;
+        });
+        painted_texts(output)
+    }
+
+    fn type_text(&mut self, text: &str) -> Vec<String> {
+        self.run(vec![]);
+        self.run(vec![]);
+        self.run(click(self.rect.center()));
+        self.run(vec![egui::Event::Text(text.into())]);
+        self.run(vec![])
+    }
+
+    fn value(&self) -> String {
+        match &self.value {
+            CustomValue::Value(value) => format!("value {value}"),
+            CustomValue::Custom(custom) => format!("custom {custom}"),
+        }
+    }
+}
+
+#[test]
+fn invalid_custom_value_is_not_selectable() {
+    let mut branches = Branches::new();
+    let texts = branches.type_text("feature/login");
+    assert!(texts.iter().any(|text| text == " (at most 8 characters)"), "{texts:?}");
+
+    branches.run(enter());
+    // The custom row is the only row, right below the editor.
+    branches.run(click(branches.rect.center_bottom() + egui::vec2(0.0, 12.0)));
+    branches.run(vec![]);
+    assert_eq!(branches.value(), "value main");
+}
+
+#[test]
+fn valid_custom_value_is_selectable() {
+    let mut branches = Branches::new();
+    let texts = branches.type_text("fix");
+    assert!(!texts.iter().any(|text| text.contains("characters")), "{texts:?}");
+
+    branches.run(enter());
+    branches.run(vec![]);
+    assert_eq!(branches.value(), "custom fix");
+}
@@ -0,0 +1,45 @@
+//! Checks that the popup stays open and on screen
+//! when the viewport shrinks while typing, e.g. as a virtual keyboard opens on the web.
+
+mod common;
+
+use common::{click, run_frame_sized, text};
+use egui_editable_combobox::EditableComboBox;
+
+const FULL: egui::Vec2 = egui::vec2(400.0, 800.0);
+/// The viewport above a virtual keyboard.
+const ABOVE_KEYBOARD: egui::Vec2 = egui::vec2(400.0, 560.0);
+
+#[test]
+fn popup_stays_open_above_virtual_keyboard() {
+    let ctx = egui::Context::default();
+    let mut value = String::new();
+    let options: Vec<String> = (0..20).map(|i| format!("option {i}")).collect();
+    let mut run = |size, events| {
+        let mut output = None;
+        run_frame_sized(&ctx, size, events, |ui| {
+            // The combo box is low on the screen, where the virtual keyboard appears.
+            ui.add_space(420.0);
+            output = Some(EditableComboBox::new("resize").show_with_output(
+                ui,
+                &mut value,
+                options.iter().map(String::as_str),
+            ));
+        });
+        output.expect("combo box shown")
+    };
+
+    run(FULL, vec![]);
+    let rect = run(FULL, vec![]).response.rect;
+    run(FULL, click(rect.center()));
+    run(FULL, text("option"));
+
+    for _ in 0..3 {
+        let output = run(ABOVE_KEYBOARD, vec![]);
+        assert!(output.response.has_focus());
+        assert!(output.popup_open);
+    }
+    let popup_id = EditableComboBox::new("resize").popup_id();
+    let popup = ctx.memory(|mem| mem.area_rect(popup_id)).expect("popup shown");
+    assert!(popup.bottom() <= ABOVE_KEYBOARD.y, "{popup:?} is behind the keyboard");
+}
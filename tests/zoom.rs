@@ -0,0 +1,63 @@
+//! Checks that the popup keeps its size in points under different zoom factors.
+
+mod common;
+
+use common::click;
+use egui_editable_combobox::EditableComboBox;
+
+const OPTIONS: [&str; 30] = [
+    "Alpha", "Bravo", "Charlie", "Delta", "Echo", "Foxtrot", "Golf", "Hotel", "India", "Juliett",
+    "Kilo", "Lima", "Mike", "November", "Oscar", "Papa", "Quebec", "Romeo", "Sierra", "Tango",
+    "Uniform", "Victor", "Whiskey", "X-ray", "Yankee", "Zulu", "One", "Two", "Three", "Four",
+];
+
+/// Opens the popup at `zoom_factor` and returns its rect in points.
+fn popup_rect(ctx: &egui::Context, zoom_factor: f32) -> egui::Rect {
+    ctx.set_zoom_factor(zoom_factor);
+    let mut value = String::new();
+    let mut popup_id = None;
+    for events in [vec![], vec![], click(egui::pos2(20.0, 15.0)), vec![], vec![]] {
+        let input = egui::RawInput {
+            events,
+            screen_rect: Some(egui::Rect::from_min_size(
+                egui::Pos2::ZERO,
+                egui::vec2(800.0, 600.0) * zoom_factor,
+            )),
+            ..Default::default()
+        };
+        let _ = ctx.run(input, |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| {
+                let combo = EditableComboBox::new("zoom").min_popup_width(240.0);
+                popup_id = Some(combo.popup_id());
+                combo.show(ui, &mut value, OPTIONS);
+            });
+        });
+    }
+    let popup_id = popup_id.expect("combo box shown");
+    ctx.memory(|mem| mem.area_rect(popup_id)).expect("popup open")
+}
+
+#[test]
+fn popup_size_is_zoom_independent() {
+    let reference = popup_rect(&egui::Context::default(), 1.0);
+    assert!(reference.width() >= 240.0, "min_popup_width is applied: {reference:?}");
+
+    for zoom_factor in [1.5, 2.0] {
+        let rect = popup_rect(&egui::Context::default(), zoom_factor);
+        assert!(
+            (rect.width() - reference.width()).abs() <= 1.0
+                && (rect.height() - reference.height()).abs() <= 1.0,
+            "popup at zoom {zoom_factor} is {rect:?}, expected the size of {reference:?}",
+        );
+    }
+}
+
+#[test]
+fn popup_is_remeasured_after_zooming() {
+    let ctx = egui::Context::default();
+    let reference = popup_rect(&ctx, 1.0);
+    let zoomed = popup_rect(&ctx, 2.0);
+    let restored = popup_rect(&ctx, 1.0);
+    assert!((zoomed.width() - reference.width()).abs() <= 1.0, "{zoomed:?} vs {reference:?}");
+    assert!((restored.width() - reference.width()).abs() <= 1.0, "{restored:?} vs {reference:?}");
+}
@@ -0,0 +1,74 @@
+use egui_editable_combobox::{CustomOption, CustomValue, EditableComboBox, ParseDisplayValue};
+use strum::IntoEnumIterator;
+
+#[derive(Clone, PartialEq, strum::EnumIter, strum::Display, strum::EnumString)]
+pub enum Continent {
+    Africa,
+    America,
+    Antarctica,
+    Eurasia,
+    Oceania,
+}
+
+struct App {
+    value: CustomValue<ParseDisplayValue<Continent>>,
+}
+
+impl Default for App {
+    fn default() -> Self {
+        Self { value: CustomValue::Value(ParseDisplayValue(Continent::Antarctica)) }
+    }
+}
+
+impl eframe::App for App {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.heading("egui-editable-combobox");
+            EditableComboBox::new("continent").show(
+                ui,
+                &mut self.value,
+                Continent::iter()
+                    .map(ParseDisplayValue)
+                    .map(CustomOption::Value)
+                    .chain([CustomOption::Custom]),
+            );
+            ui.label(format!(
+                "Selected continent: {}",
+                match &self.value {
+                    CustomValue::Value(v) => v.0.to_string(),
+                    CustomValue::Custom(manual) => manual.clone(),
+                },
+            ));
+        });
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn main() {
+    use eframe::wasm_bindgen::JsCast as _;
+
+    eframe::WebLogger::init(log::LevelFilter::Debug).ok();
+
+    wasm_bindgen_futures::spawn_local(async {
+        let canvas = eframe::web_sys::window()
+            .and_then(|window| window.document())
+            .and_then(|document| document.get_element_by_id("the_canvas_id"))
+            .expect("canvas element is missing from index.html")
+            .dyn_into::<eframe::web_sys::HtmlCanvasElement>()
+            .expect("the_canvas_id is not a canvas");
+
+        eframe::WebRunner::new()
+            .start(canvas, eframe::WebOptions::default(), Box::new(|_cc| Ok(Box::<App>::default())))
+            .await
+            .expect("failed to start eframe");
+    });
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn main() -> eframe::Result {
+    eframe::run_native(
+        "egui-editable-combobox demo",
+        eframe::NativeOptions::default(),
+        Box::new(|_cc| Ok(Box::<App>::default())),
+    )
+}